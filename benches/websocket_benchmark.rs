@@ -1,5 +1,6 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 
+use binance::transport::read_frames;
 use binance::websockets::*;
 
 use core::time::Duration;
@@ -8,15 +9,15 @@ fn criterion_benchmark(c: &mut Criterion) {
 
     let mut group = c.benchmark_group("websockets-decoder");
 
-    let all_symbols_json = reqwest::blocking::get("https://api.binance.com/api/v3/ticker/price")
-        .unwrap().text().unwrap();
-
-    let btc_symbol_json = reqwest::blocking::get("https://api.binance.com/api/v3/ticker/price?symbol=BTCUSDT")
-        .unwrap().text().unwrap();
-
-    let mut web_socket_subscribed: WebSockets<'_> = WebSockets::new_with_subscription("!ticker@arr", |_event: WebsocketEvent| {
-        Ok(())
-    });
+    // Recorded `btcusdt@ticker` frame.
+    let frames = read_frames("tests/mocks/websocket/ticker_frames.jsonl").unwrap();
+    let btc_symbol_json = &frames[1];
+    // `!ticker@arr` carries the tickers of every symbol, about 2000 on spot. It
+    // is made of copies of the BTCUSDT ticker under other symbols.
+    let tickers: Vec<String> = (0..2000)
+        .map(|i| btc_symbol_json.replace("\"BTCUSDT\"", &format!("\"SYMBOL{}\"", i)))
+        .collect();
+    let all_symbols_json = &format!("[{}]", tickers.join(","));
 
     let mut web_socket: WebSockets<'_> = WebSockets::new(|_event: WebsocketEvent| {
         Ok(())
@@ -24,10 +25,10 @@ fn criterion_benchmark(c: &mut Criterion) {
 
     group.sample_size(200);
     group.measurement_time(Duration::new(35, 0));
-    group.bench_function("handle_msg all symbols", |b| b.iter(|| web_socket_subscribed.test_handle_msg(&all_symbols_json)));
-    group.bench_function("handle_msg BTCUSDT symbol", |b| b.iter(|| web_socket.test_handle_msg(&btc_symbol_json)));
+    group.bench_function("handle_msg all symbols", |b| b.iter(|| web_socket.test_handle_msg(all_symbols_json)));
+    group.bench_function("handle_msg BTCUSDT symbol", |b| b.iter(|| web_socket.test_handle_msg(btc_symbol_json)));
     group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
-criterion_main!(benches);
\ No newline at end of file
+criterion_main!(benches);
@@ -3,8 +3,8 @@ use binance::config::*;
 use binance::general::*;
 use binance::account::*;
 use binance::market::*;
-use binance::model::KlineSummary;
-use binance::errors::ErrorKind as BinanceLibErrorKind;
+use binance::model::{Amount, KlineSummary};
+use binance::errors::BinanceError;
 
 fn main() {
     // The general spot API endpoints; shown with
@@ -23,22 +23,22 @@ fn main() {
 fn general(use_testnet: bool) {
     let general: General = if use_testnet {
         let config = Config::default().set_rest_api_endpoint("https://testnet.binance.vision");
-        Binance::new_with_config(None, None, &config)
+        Binance::new_with_config(None, None, &config).unwrap()
     } else {
-        Binance::new(None, None)
+        Binance::new(None, None).unwrap()
     };
 
     let ping = general.ping();
     match ping {
         Ok(answer) => println!("{:?}", answer),
         Err(err) => {
-            match err.0 {
-                BinanceLibErrorKind::BinanceError(response) => match response.code {
+            match err {
+                BinanceError::Api { code, msg } => match code {
                     -1000_i16 => println!("An unknown error occured while processing the request"),
-                    _ => println!("Non-catched code {}: {}", response.code, response.msg),
+                    _ => println!("Non-catched code {}: {}", code, msg),
                 },
-                BinanceLibErrorKind::Msg(msg) => println!("Binancelib error msg: {}", msg),
-                _ => println!("Other errors: {}.", err.0),
+                BinanceError::Msg(msg) => println!("Binancelib error msg: {}", msg),
+                _ => println!("Other errors: {}.", err),
             };
         }
     }
@@ -67,7 +67,7 @@ fn account() {
     let api_key = Some("YOUR_API_KEY".into());
     let secret_key = Some("YOUR_SECRET_KEY".into());
 
-    let account: Account = Binance::new(api_key, secret_key);
+    let account: Account = Binance::new(api_key, secret_key).unwrap();
 
     match account.get_account() {
         Ok(answer) => println!("{:?}", answer.balances),
@@ -79,7 +79,9 @@ fn account() {
         Err(e) => println!("Error: {}", e),
     }
 
-    match account.limit_buy("WTCETH", 10, 0.014000) {
+    // Parsed so that the prices are exact with the `decimal` feature too.
+    let buy_price: Amount = "0.014".parse().unwrap();
+    match account.limit_buy("WTCETH", 10, buy_price) {
         Ok(answer) => println!("{:?}", answer),
         Err(e) => println!("Error: {}", e),
     }
@@ -94,7 +96,8 @@ fn account() {
         Err(e) => println!("Error: {}", e),
     }
 
-    match account.limit_sell("WTCETH", 10, 0.035000) {
+    let sell_price: Amount = "0.035".parse().unwrap();
+    match account.limit_sell("WTCETH", 10, sell_price) {
         Ok(answer) => println!("{:?}", answer),
         Err(e) => println!("Error: {}", e),
     }
@@ -133,7 +136,7 @@ fn account() {
 
 #[allow(dead_code)]
 fn market_data() {
-    let market: Market = Binance::new(None, None);
+    let market: Market = Binance::new(None, None).unwrap();
 
     // Order book at default depth
     match market.get_depth("BNBETH") {
@@ -2,7 +2,7 @@ use binance::api::*;
 use binance::futures::general::*;
 use binance::futures::market::*;
 use binance::futures::model::*;
-use binance::errors::ErrorKind as BinanceLibErrorKind;
+use binance::errors::BinanceError;
 
 fn main() {
     general();
@@ -11,18 +11,18 @@ fn main() {
 }
 
 fn general() {
-    let general: FuturesGeneral = Binance::new(None, None);
+    let general: FuturesGeneral = Binance::new(None, None).unwrap();
 
     match general.ping() {
         Ok(answer) => println!("{:?}", answer),
         Err(err) => {
-            match err.0 {
-                BinanceLibErrorKind::BinanceError(response) => match response.code {
+            match err {
+                BinanceError::Api { code, msg } => match code {
                     -1000_i16 => println!("An unknown error occured while processing the request"),
-                    _ => println!("Non-catched code {}: {}", response.code, response.msg),
+                    _ => println!("Non-catched code {}: {}", code, msg),
                 },
-                BinanceLibErrorKind::Msg(msg) => println!("Binancelib error msg: {}", msg),
-                _ => println!("Other errors: {}.", err.0),
+                BinanceError::Msg(msg) => println!("Binancelib error msg: {}", msg),
+                _ => println!("Other errors: {}.", err),
             };
         }
     }
@@ -44,7 +44,7 @@ fn general() {
 }
 
 fn market_data() {
-    let market: FuturesMarket = Binance::new(None, None);
+    let market: FuturesMarket = Binance::new(None, None).unwrap();
 
     match market.get_depth("btcusdt") {
         Ok(answer) => println!("Depth update ID: {:?}", answer.last_update_id),
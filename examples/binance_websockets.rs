@@ -1,4 +1,5 @@
 use binance::api::*;
+use binance::model::Amount;
 use binance::userstream::*;
 use binance::websockets::*;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -15,7 +16,7 @@ fn main() {
 
 fn user_stream() {
     let api_key_user = Some("YOUR_API_KEY".into());
-    let user_stream: UserStream = Binance::new(api_key_user, None);
+    let user_stream: UserStream = Binance::new(api_key_user, None).unwrap();
 
     if let Ok(answer) = user_stream.start() {
         println!("Data Stream Started ...");
@@ -38,7 +39,7 @@ fn user_stream() {
 fn user_stream_websocket() {
     let keep_running = AtomicBool::new(true); // Used to control the event loop
     let api_key_user = Some("YOUR_KEY".into());
-    let user_stream: UserStream = Binance::new(api_key_user, None);
+    let user_stream: UserStream = Binance::new(api_key_user, None).unwrap();
 
     if let Ok(answer) = user_stream.start() {
         let listen_key = answer.listen_key;
@@ -55,7 +56,7 @@ fn user_stream_websocket() {
                 }
                 WebsocketEvent::OrderTrade(trade) => {
                     println!(
-                        "Symbol: {}, Side: {}, Price: {}, Execution Type: {}",
+                        "Symbol: {}, Side: {:?}, Price: {}, Execution Type: {:?}",
                         trade.symbol, trade.side, trade.price, trade.execution_type
                     );
                 }
@@ -163,15 +164,15 @@ fn kline_websocket() {
 fn last_price_for_one_symbol() {
     let keep_running = AtomicBool::new(true);
     let agg_trade: String = String::from("btcusdt@ticker");
-    let mut btcusdt: f32 = "0".parse().unwrap();
+    let mut btcusdt: Amount = "0".parse().unwrap();
 
     let mut web_socket: WebSockets<'_> = WebSockets::new(|event: WebsocketEvent| {
         if let WebsocketEvent::DayTicker(ticker_event) = event {
-            btcusdt = ticker_event.average_price.parse().unwrap();
-            let btcusdt_close: f32 = ticker_event.current_close.parse().unwrap();
+            btcusdt = ticker_event.average_price;
+            let btcusdt_close = ticker_event.current_close;
             println!("{} - {}", btcusdt, btcusdt_close);
 
-            if btcusdt_close as i32 == 7000 {
+            if btcusdt_close == Amount::from(7000) {
                 // Break the event loop
                 keep_running.store(false, Ordering::Relaxed);
             }
@@ -2,10 +2,11 @@ use crate::util::*;
 use crate::model::*;
 use crate::client::*;
 use crate::errors::*;
-use std::collections::BTreeMap;
 use crate::api::API;
 use crate::api::Spot;
 
+pub use crate::model::{OrderSide, OrderType, TimeInForce};
+
 #[derive(Clone)]
 pub struct Account {
     pub client: Client,
@@ -15,9 +16,9 @@ pub struct Account {
 
 struct OrderRequest {
     pub symbol: String,
-    pub qty: f64,
-    pub price: f64,
-    pub stop_price: Option<f64>,
+    pub qty: Amount,
+    pub price: Amount,
+    pub stop_price: Option<Amount>,
     pub order_side: OrderSide,
     pub order_type: OrderType,
     pub time_in_force: TimeInForce,
@@ -25,65 +26,17 @@ struct OrderRequest {
 
 struct OrderQuoteQuantityRequest {
     pub symbol: String,
-    pub quote_order_qty: f64,
-    pub price: f64,
+    pub quote_order_qty: Amount,
+    pub price: Amount,
     pub order_side: OrderSide,
     pub order_type: OrderType,
     pub time_in_force: TimeInForce,
 }
 
-pub enum OrderType {
-    Limit,
-    Market,
-    StopLossLimit,
-}
-
-impl From<OrderType> for String {
-    fn from(item: OrderType) -> Self {
-        match item {
-            OrderType::Limit => String::from("LIMIT"),
-            OrderType::Market => String::from("MARKET"),
-            OrderType::StopLossLimit => String::from("STOP_LOSS_LIMIT"),
-        }
-    }
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub enum OrderSide {
-    Buy,
-    Sell,
-}
-
-impl From<OrderSide> for String {
-    fn from(item: OrderSide) -> Self {
-        match item {
-            OrderSide::Buy => String::from("BUY"),
-            OrderSide::Sell => String::from("SELL"),
-        }
-    }
-}
-
-#[allow(clippy::all)]
-pub enum TimeInForce {
-    GTC,
-    IOC,
-    FOK,
-}
-
-impl From<TimeInForce> for String {
-    fn from(item: TimeInForce) -> Self {
-        match item {
-            TimeInForce::GTC => String::from("GTC"),
-            TimeInForce::IOC => String::from("IOC"),
-            TimeInForce::FOK => String::from("FOK"),
-        }
-    }
-}
-
 impl Account {
     // Account Information
     pub fn get_account(&self) -> Result<AccountInformation> {
-        let request = build_signed_request(BTreeMap::new(), self.recv_window)?;
+        let request = build_signed_request(RequestParams::new(), self.recv_window)?;
         self.client
             .get_signed(API::Spot(Spot::Account), Some(request))
     }
@@ -112,8 +65,8 @@ impl Account {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
 
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client
@@ -122,7 +75,7 @@ impl Account {
 
     // All current open orders
     pub fn get_all_open_orders(&self) -> Result<Vec<Order>> {
-        let parameters: BTreeMap<String, String> = BTreeMap::new();
+        let parameters = RequestParams::new();
 
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client
@@ -134,8 +87,8 @@ impl Account {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client
             .delete_signed(API::Spot(Spot::OpenOrders), Some(request))
@@ -146,9 +99,9 @@ impl Account {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("orderId".into(), order_id.to_string());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
+        parameters.insert("orderId", order_id.to_string());
 
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client
@@ -162,9 +115,9 @@ impl Account {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("orderId".into(), order_id.to_string());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
+        parameters.insert("orderId", order_id.to_string());
 
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client
@@ -173,15 +126,16 @@ impl Account {
     }
 
     // Place a LIMIT order - BUY
-    pub fn limit_buy<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<Transaction>
+    pub fn limit_buy<S, F, P>(&self, symbol: S, qty: F, price: P) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
+        P: Into<Amount>,
     {
         let buy: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price,
+            price: price.into(),
             stop_price: None,
             order_side: OrderSide::Buy,
             order_type: OrderType::Limit,
@@ -189,22 +143,22 @@ impl Account {
         };
         let order = self.build_order(buy);
         let request = build_signed_request(order, self.recv_window)?;
-        println!("request: {}", request);
         self.client.post_signed(API::Spot(Spot::Order), request)
     }
 
     /// Place a test limit order - BUY
     ///
     /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_limit_buy<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<()>
+    pub fn test_limit_buy<S, F, P>(&self, symbol: S, qty: F, price: P) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
+        P: Into<Amount>,
     {
         let buy: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price,
+            price: price.into(),
             stop_price: None,
             order_side: OrderSide::Buy,
             order_type: OrderType::Limit,
@@ -218,15 +172,16 @@ impl Account {
     }
 
     // Place a LIMIT order - SELL
-    pub fn limit_sell<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<Transaction>
+    pub fn limit_sell<S, F, P>(&self, symbol: S, qty: F, price: P) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
+        P: Into<Amount>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price,
+            price: price.into(),
             stop_price: None,
             order_side: OrderSide::Sell,
             order_type: OrderType::Limit,
@@ -240,15 +195,16 @@ impl Account {
     /// Place a test LIMIT order - SELL
     ///
     /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_limit_sell<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<()>
+    pub fn test_limit_sell<S, F, P>(&self, symbol: S, qty: F, price: P) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
+        P: Into<Amount>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price,
+            price: price.into(),
             stop_price: None,
             order_side: OrderSide::Sell,
             order_type: OrderType::Limit,
@@ -265,12 +221,12 @@ impl Account {
     pub fn market_buy<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let buy: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price: 0.0,
+            price: Amount::default(),
             stop_price: None,
             order_side: OrderSide::Buy,
             order_type: OrderType::Market,
@@ -287,12 +243,12 @@ impl Account {
     pub fn test_market_buy<S, F>(&self, symbol: S, qty: F) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let buy: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price: 0.0,
+            price: Amount::default(),
             stop_price: None,
             order_side: OrderSide::Buy,
             order_type: OrderType::Market,
@@ -311,12 +267,12 @@ impl Account {
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let buy: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
             symbol: symbol.into(),
             quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
+            price: Amount::default(),
             order_side: OrderSide::Buy,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
@@ -334,12 +290,12 @@ impl Account {
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let buy: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
             symbol: symbol.into(),
             quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
+            price: Amount::default(),
             order_side: OrderSide::Buy,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
@@ -355,12 +311,12 @@ impl Account {
     pub fn market_sell<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price: 0.0,
+            price: Amount::default(),
             stop_price: None,
             order_side: OrderSide::Sell,
             order_type: OrderType::Market,
@@ -377,12 +333,12 @@ impl Account {
     pub fn test_market_sell<S, F>(&self, symbol: S, qty: F) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price: 0.0,
+            price: Amount::default(),
             stop_price: None,
             order_side: OrderSide::Sell,
             order_type: OrderType::Market,
@@ -401,12 +357,12 @@ impl Account {
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let sell: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
             symbol: symbol.into(),
             quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
+            price: Amount::default(),
             order_side: OrderSide::Sell,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
@@ -424,12 +380,12 @@ impl Account {
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let sell: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
             symbol: symbol.into(),
             quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
+            price: Amount::default(),
             order_side: OrderSide::Sell,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
@@ -447,26 +403,30 @@ impl Account {
     ///```no_run
     /// use binance::api::Binance;
     /// use binance::account::*;
+    /// use binance::model::Amount;
     ///
     /// fn main() {
     ///     let api_key = Some("api_key".into());
     ///     let secret_key = Some("secret_key".into());
-    ///     let account: Account = Binance::new(api_key, secret_key);
-    ///     let result = account.stop_limit_buy_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC);
+    ///     let account: Account = Binance::new(api_key, secret_key).unwrap();
+    ///     let price: Amount = "0.1".parse().unwrap();
+    ///     let stop_price: Amount = "0.09".parse().unwrap();
+    ///     let result = account.stop_limit_buy_order("LTCBTC", 1, price, stop_price, TimeInForce::GTC);
     /// }
     /// ```
-    pub fn stop_limit_buy_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: f64, time_in_force: TimeInForce,
+    pub fn stop_limit_buy_order<S, F, P>(
+        &self, symbol: S, qty: F, price: P, stop_price: P, time_in_force: TimeInForce,
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
+        P: Into<Amount>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price,
-            stop_price: Some(stop_price),
+            price: price.into(),
+            stop_price: Some(stop_price.into()),
             order_side: OrderSide::Buy,
             order_type: OrderType::StopLossLimit,
             time_in_force,
@@ -484,26 +444,30 @@ impl Account {
     ///```no_run
     /// use binance::api::Binance;
     /// use binance::account::*;
+    /// use binance::model::Amount;
     ///
     /// fn main() {
     ///     let api_key = Some("api_key".into());
     ///     let secret_key = Some("secret_key".into());
-    ///     let account: Account = Binance::new(api_key, secret_key);
-    ///     let result = account.test_stop_limit_buy_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC);
+    ///     let account: Account = Binance::new(api_key, secret_key).unwrap();
+    ///     let price: Amount = "0.1".parse().unwrap();
+    ///     let stop_price: Amount = "0.09".parse().unwrap();
+    ///     let result = account.test_stop_limit_buy_order("LTCBTC", 1, price, stop_price, TimeInForce::GTC);
     /// }
     /// ```
-    pub fn test_stop_limit_buy_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: f64, time_in_force: TimeInForce,
+    pub fn test_stop_limit_buy_order<S, F, P>(
+        &self, symbol: S, qty: F, price: P, stop_price: P, time_in_force: TimeInForce,
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
+        P: Into<Amount>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price,
-            stop_price: Some(stop_price),
+            price: price.into(),
+            stop_price: Some(stop_price.into()),
             order_side: OrderSide::Buy,
             order_type: OrderType::StopLossLimit,
             time_in_force,
@@ -521,26 +485,30 @@ impl Account {
     ///```no_run
     /// use binance::api::Binance;
     /// use binance::account::*;
+    /// use binance::model::Amount;
     ///
     /// fn main() {
     ///     let api_key = Some("api_key".into());
     ///     let secret_key = Some("secret_key".into());
-    ///     let account: Account = Binance::new(api_key, secret_key);
-    ///     let result = account.stop_limit_sell_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC);
+    ///     let account: Account = Binance::new(api_key, secret_key).unwrap();
+    ///     let price: Amount = "0.1".parse().unwrap();
+    ///     let stop_price: Amount = "0.09".parse().unwrap();
+    ///     let result = account.stop_limit_sell_order("LTCBTC", 1, price, stop_price, TimeInForce::GTC);
     /// }
     /// ```
-    pub fn stop_limit_sell_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: f64, time_in_force: TimeInForce,
+    pub fn stop_limit_sell_order<S, F, P>(
+        &self, symbol: S, qty: F, price: P, stop_price: P, time_in_force: TimeInForce,
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
+        P: Into<Amount>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price,
-            stop_price: Some(stop_price),
+            price: price.into(),
+            stop_price: Some(stop_price.into()),
             order_side: OrderSide::Sell,
             order_type: OrderType::StopLossLimit,
             time_in_force,
@@ -558,26 +526,30 @@ impl Account {
     ///```no_run
     /// use binance::api::Binance;
     /// use binance::account::*;
+    /// use binance::model::Amount;
     ///
     /// fn main() {
     ///     let api_key = Some("api_key".into());
     ///     let secret_key = Some("secret_key".into());
-    ///     let account: Account = Binance::new(api_key, secret_key);
-    ///     let result = account.test_stop_limit_sell_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC);
+    ///     let account: Account = Binance::new(api_key, secret_key).unwrap();
+    ///     let price: Amount = "0.1".parse().unwrap();
+    ///     let stop_price: Amount = "0.09".parse().unwrap();
+    ///     let result = account.test_stop_limit_sell_order("LTCBTC", 1, price, stop_price, TimeInForce::GTC);
     /// }
     /// ```
-    pub fn test_stop_limit_sell_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: f64, time_in_force: TimeInForce,
+    pub fn test_stop_limit_sell_order<S, F, P>(
+        &self, symbol: S, qty: F, price: P, stop_price: P, time_in_force: TimeInForce,
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
+        P: Into<Amount>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price,
-            stop_price: Some(stop_price),
+            price: price.into(),
+            stop_price: Some(stop_price.into()),
             order_side: OrderSide::Sell,
             order_type: OrderType::StopLossLimit,
             time_in_force,
@@ -591,19 +563,20 @@ impl Account {
 
     /// Place a custom order
     #[allow(clippy::too_many_arguments)]
-    pub fn custom_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: Option<f64>, order_side: OrderSide,
+    pub fn custom_order<S, F, P>(
+        &self, symbol: S, qty: F, price: P, stop_price: Option<P>, order_side: OrderSide,
         order_type: OrderType, time_in_force: TimeInForce,
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
+        P: Into<Amount>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price,
-            stop_price,
+            price: price.into(),
+            stop_price: stop_price.map(Into::into),
             order_side,
             order_type,
             time_in_force,
@@ -617,19 +590,20 @@ impl Account {
     ///
     /// This order is sandboxed: it is validated, but not sent to the matching engine.
     #[allow(clippy::too_many_arguments)]
-    pub fn test_custom_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: Option<f64>, order_side: OrderSide,
+    pub fn test_custom_order<S, F, P>(
+        &self, symbol: S, qty: F, price: P, stop_price: Option<P>, order_side: OrderSide,
         order_type: OrderType, time_in_force: TimeInForce,
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
+        P: Into<Amount>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price,
-            stop_price,
+            price: price.into(),
+            stop_price: stop_price.map(Into::into),
             order_side,
             order_type,
             time_in_force,
@@ -646,9 +620,9 @@ impl Account {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("orderId".into(), order_id.to_string());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
+        parameters.insert("orderId", order_id.to_string());
 
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client
@@ -662,9 +636,9 @@ impl Account {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("orderId".into(), order_id.to_string());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
+        parameters.insert("orderId", order_id.to_string());
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client
             .delete_signed::<Empty>(API::Spot(Spot::OrderTest), Some(request))
@@ -676,29 +650,29 @@ impl Account {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
 
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client
             .get_signed(API::Spot(Spot::MyTrades), Some(request))
     }
 
-    fn build_order(&self, order: OrderRequest) -> BTreeMap<String, String> {
-        let mut order_parameters: BTreeMap<String, String> = BTreeMap::new();
+    fn build_order(&self, order: OrderRequest) -> RequestParams {
+        let mut order_parameters = RequestParams::new();
 
-        order_parameters.insert("symbol".into(), order.symbol);
-        order_parameters.insert("side".into(), order.order_side.into());
-        order_parameters.insert("type".into(), order.order_type.into());
-        order_parameters.insert("quantity".into(), order.qty.to_string());
+        order_parameters.insert("symbol", order.symbol);
+        order_parameters.insert("side", order.order_side);
+        order_parameters.insert("type", order.order_type);
+        order_parameters.insert("quantity", order.qty.to_string());
 
         if let Some(stop_price) = order.stop_price {
-            order_parameters.insert("stopPrice".into(), stop_price.to_string());
+            order_parameters.insert("stopPrice", stop_price.to_string());
         }
 
-        if order.price != 0.0 {
-            order_parameters.insert("price".into(), order.price.to_string());
-            order_parameters.insert("timeInForce".into(), order.time_in_force.into());
+        if order.price != Amount::default() {
+            order_parameters.insert("price", order.price.to_string());
+            order_parameters.insert("timeInForce", order.time_in_force);
         }
 
         order_parameters
@@ -706,17 +680,17 @@ impl Account {
 
     fn build_quote_quantity_order(
         &self, order: OrderQuoteQuantityRequest,
-    ) -> BTreeMap<String, String> {
-        let mut order_parameters: BTreeMap<String, String> = BTreeMap::new();
+    ) -> RequestParams {
+        let mut order_parameters = RequestParams::new();
 
-        order_parameters.insert("symbol".into(), order.symbol);
-        order_parameters.insert("side".into(), order.order_side.into());
-        order_parameters.insert("type".into(), order.order_type.into());
-        order_parameters.insert("quoteOrderQty".into(), order.quote_order_qty.to_string());
+        order_parameters.insert("symbol", order.symbol);
+        order_parameters.insert("side", order.order_side);
+        order_parameters.insert("type", order.order_type);
+        order_parameters.insert("quoteOrderQty", order.quote_order_qty.to_string());
 
-        if order.price != 0.0 {
-            order_parameters.insert("price".into(), order.price.to_string());
-            order_parameters.insert("timeInForce".into(), order.time_in_force.into());
+        if order.price != Amount::default() {
+            order_parameters.insert("price", order.price.to_string());
+            order_parameters.insert("timeInForce", order.time_in_force);
         }
 
         order_parameters
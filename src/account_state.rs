@@ -0,0 +1,502 @@
+//! A local copy of the balances, open orders and positions of an account,
+//! kept up to date from its user data stream.
+//!
+//! The state is seeded from the REST API, then follows the events of the
+//! stream. Reconciling it with the REST API from time to time reports what
+//! the events missed, e.g. while the stream was reconnecting:
+//!
+//! ```rust,no_run
+//! use binance::account::*;
+//! use binance::account_state::*;
+//! use binance::api::*;
+//! use binance::config::*;
+//! use binance::userstream::*;
+//! use binance::websockets::*;
+//! use std::sync::atomic::AtomicBool;
+//! use std::time::Duration;
+//!
+//! let config = Config::default();
+//! let account: Account = Binance::new_with_config(Some("key".into()), Some("secret".into()), &config).unwrap();
+//! let mut manager = UserDataStreamManager::new(Some("key".into()), UserStreamType::Spot, &config).unwrap();
+//! manager.start().unwrap();
+//!
+//! let state = AccountState::spot(account).unwrap();
+//! let _reconciler = state.reconcile_every(Duration::from_secs(300), |drift| {
+//!     if let Ok(drift) = drift {
+//!         if !drift.is_empty() {
+//!             println!("Out of sync: {:?}", drift);
+//!         }
+//!     }
+//! });
+//!
+//! manager
+//!     .run(
+//!         |event: WebsocketEvent| {
+//!             state.handle_event(&event);
+//!             println!("BTC: {:?}", state.balance("BTC"));
+//!             Ok(())
+//!         },
+//!         &AtomicBool::new(true),
+//!     )
+//!     .unwrap();
+//! ```
+
+use crate::account::Account;
+use crate::errors::*;
+use crate::util::lock;
+use crate::futures::account::FuturesAccount;
+use crate::model::{Amount, Balance, OrderSide, OrderStatus, OrderType};
+use crate::websockets::WebsocketEvent;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// An order still open on the exchange.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenOrder {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub status: OrderStatus,
+    pub price: Amount,
+    pub orig_qty: Amount,
+    pub executed_qty: Amount,
+    pub update_time: u64,
+}
+
+/// A futures position, `amount` being negative when short.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    pub symbol: String,
+    /// `BOTH` in one-way mode, `LONG` or `SHORT` in hedge mode.
+    pub position_side: String,
+    pub amount: Amount,
+    pub entry_price: Amount,
+    pub unrealized_pnl: Amount,
+}
+
+/// What a reconciliation found different from the REST API, and corrected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Drift {
+    pub balances: Vec<String>,
+    pub orders: Vec<u64>,
+    /// Symbol and position side.
+    pub positions: Vec<(String, String)>,
+}
+
+impl Drift {
+    pub fn is_empty(&self) -> bool {
+        self.balances.is_empty() && self.orders.is_empty() && self.positions.is_empty()
+    }
+}
+
+#[derive(Clone)]
+enum Source {
+    Spot(Account),
+    Futures(FuturesAccount),
+}
+
+/// Balances, open orders and positions of a spot or futures account.
+///
+/// Clones share the same state, so that one can follow the stream while
+/// another is read from other threads. Futures balances are wallet balances,
+/// all `free`.
+#[derive(Clone)]
+pub struct AccountState {
+    source: Source,
+    state: Arc<Mutex<State>>,
+}
+
+// Removed entries are kept as `None`, so that a reconciliation doesn't restore
+// them from a snapshot taken before the removal. The next reconciliation
+// clears them, or they are dropped after `TOMBSTONE_TTL`.
+#[derive(Default)]
+struct State {
+    balances: BTreeMap<String, Tracked<Option<Balance>>>,
+    orders: BTreeMap<u64, Tracked<Option<OpenOrder>>>,
+    positions: BTreeMap<(String, String), Tracked<Option<Position>>>,
+}
+
+// Longer than a snapshot can take, its requests timing out after 30s by default.
+const TOMBSTONE_TTL: Duration = Duration::from_secs(600);
+
+struct Tracked<T> {
+    value: T,
+    updated_at: Instant,
+}
+
+impl<T> Tracked<T> {
+    fn new(value: T) -> Self {
+        Tracked {
+            value,
+            updated_at: Instant::now(),
+        }
+    }
+}
+
+// Everything the REST API reports, as the state holds it.
+#[derive(Default)]
+struct Snapshot {
+    balances: BTreeMap<String, Balance>,
+    orders: BTreeMap<u64, OpenOrder>,
+    positions: BTreeMap<(String, String), Position>,
+}
+
+impl AccountState {
+    /// Seeds the state from the spot account information and open orders.
+    pub fn spot(account: Account) -> Result<Self> {
+        Self::new(Source::Spot(account))
+    }
+
+    /// Seeds the state from the futures balances, positions and open orders.
+    pub fn futures(account: FuturesAccount) -> Result<Self> {
+        Self::new(Source::Futures(account))
+    }
+
+    fn new(source: Source) -> Result<Self> {
+        let state = AccountState {
+            source,
+            state: Arc::new(Mutex::new(State::default())),
+        };
+        state.reconcile()?;
+        Ok(state)
+    }
+
+    /// Zero for assets the account never held.
+    pub fn balance(&self, asset: &str) -> Balance {
+        match lock(&self.state).balances.get(asset).and_then(|balance| balance.value.clone()) {
+            Some(balance) => balance,
+            None => Balance {
+                asset: asset.into(),
+                free: Amount::default(),
+                locked: Amount::default(),
+            },
+        }
+    }
+
+    pub fn balances(&self) -> Vec<Balance> {
+        let state = lock(&self.state);
+        state.balances.values().filter_map(|balance| balance.value.clone()).collect()
+    }
+
+    /// Open orders of `symbol`, oldest first.
+    pub fn open_orders(&self, symbol: &str) -> Vec<OpenOrder> {
+        let state = lock(&self.state);
+        state
+            .orders
+            .values()
+            .filter_map(|order| order.value.clone())
+            .filter(|order| order.symbol == symbol)
+            .collect()
+    }
+
+    pub fn all_open_orders(&self) -> Vec<OpenOrder> {
+        let state = lock(&self.state);
+        state.orders.values().filter_map(|order| order.value.clone()).collect()
+    }
+
+    pub fn open_order(&self, order_id: u64) -> Option<OpenOrder> {
+        lock(&self.state).orders.get(&order_id).and_then(|order| order.value.clone())
+    }
+
+    /// The position of `symbol` in one-way mode, see `positions` in hedge mode.
+    pub fn position(&self, symbol: &str) -> Option<Position> {
+        let key = (symbol.to_string(), "BOTH".to_string());
+        lock(&self.state).positions.get(&key).and_then(|position| position.value.clone())
+    }
+
+    /// Every position that is not flat.
+    pub fn positions(&self) -> Vec<Position> {
+        let state = lock(&self.state);
+        state.positions.values().filter_map(|position| position.value.clone()).collect()
+    }
+
+    /// Applies a user data stream event, others are ignored.
+    ///
+    /// `BalanceUpdate` is left to the `AccountPosition` that follows it with
+    /// the resulting balance.
+    pub fn handle_event(&self, event: &WebsocketEvent) {
+        let mut state = lock(&self.state);
+        match event {
+            WebsocketEvent::AccountPosition(event) => {
+                for balance in &event.balances {
+                    state.set_balance(&balance.asset, balance.free, balance.locked);
+                }
+            }
+            WebsocketEvent::AccountUpdate(event) => {
+                for balance in &event.balance {
+                    state.set_balance(&balance.asset, balance.free, balance.locked);
+                }
+            }
+            WebsocketEvent::OrderTrade(event) => state.set_order(OpenOrder {
+                symbol: event.symbol.clone(),
+                order_id: event.order_id,
+                client_order_id: event.new_client_order_id.clone(),
+                side: event.side.clone(),
+                order_type: event.order_type.clone(),
+                status: event.order_status.clone(),
+                price: event.price,
+                orig_qty: event.qty,
+                executed_qty: event.accumulated_qty_filled_trades,
+                update_time: event.trade_order_time,
+            }),
+            WebsocketEvent::FuturesAccountUpdate(event) => {
+                for balance in &event.data.balance_updates {
+                    state.set_balance(&balance.asset, balance.balance, Amount::default());
+                }
+                for position in &event.data.position {
+                    state.set_position(Position {
+                        symbol: position.symbol.clone(),
+                        position_side: position.position_side.clone(),
+                        amount: position.position_amount,
+                        entry_price: position.entry_price,
+                        unrealized_pnl: position.unrealized,
+                    });
+                }
+            }
+            WebsocketEvent::FuturesOrderUpdate(event) => {
+                let order = &event.data;
+                state.set_order(OpenOrder {
+                    symbol: order.symbol.clone(),
+                    order_id: order.order_id,
+                    client_order_id: order.client_order_id.clone(),
+                    side: order.side.clone(),
+                    order_type: order.order_type.clone(),
+                    status: order.status.clone(),
+                    price: order.original_price,
+                    orig_qty: order.original_quantity,
+                    executed_qty: order.filled_accumulated_quantity,
+                    update_time: order.order_trade_time,
+                });
+            }
+            _ => (),
+        }
+    }
+
+    /// Replaces the state with what the REST API reports, and returns what
+    /// differed. What changed from events while the requests were in flight,
+    /// including orders, balances and positions that went away, is kept, and
+    /// not reported.
+    pub fn reconcile(&self) -> Result<Drift> {
+        let started_at = Instant::now();
+        let snapshot = self.snapshot()?;
+
+        let mut state = lock(&self.state);
+        let balances = reconcile(&mut state.balances, snapshot.balances, started_at, |local, remote| {
+            local.free == remote.free && local.locked == remote.locked
+        });
+        let orders = reconcile(&mut state.orders, snapshot.orders, started_at, |local, remote| {
+            local.status == remote.status && local.executed_qty == remote.executed_qty
+        });
+        let positions = reconcile(&mut state.positions, snapshot.positions, started_at, |local, remote| {
+            local.amount == remote.amount && local.entry_price == remote.entry_price
+        });
+        Ok(Drift {
+            balances,
+            orders,
+            positions,
+        })
+    }
+
+    /// Reconciles every `interval` from a background thread, handing the
+    /// outcome to `on_drift`. Stops when the returned handle is dropped.
+    pub fn reconcile_every<F>(&self, interval: Duration, mut on_drift: F) -> Reconciler
+    where
+        F: FnMut(Result<Drift>) + Send + 'static,
+    {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let state = self.clone();
+        let handle = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                on_drift(state.reconcile());
+            }
+        });
+        Reconciler {
+            stop: Some(stop),
+            handle: Some(handle),
+        }
+    }
+
+    fn snapshot(&self) -> Result<Snapshot> {
+        let mut snapshot = Snapshot::default();
+        match &self.source {
+            Source::Spot(account) => {
+                for balance in account.get_account()?.balances {
+                    if !is_zero(balance.free) || !is_zero(balance.locked) {
+                        snapshot.balances.insert(balance.asset.clone(), balance);
+                    }
+                }
+                for order in account.get_all_open_orders()? {
+                    snapshot.orders.insert(
+                        order.order_id,
+                        OpenOrder {
+                            symbol: order.symbol,
+                            order_id: order.order_id,
+                            client_order_id: order.client_order_id,
+                            side: order.side,
+                            order_type: order.type_name,
+                            status: order.status,
+                            price: order.price,
+                            orig_qty: order.orig_qty,
+                            executed_qty: order.executed_qty,
+                            update_time: order.update_time,
+                        },
+                    );
+                }
+            }
+            Source::Futures(account) => {
+                for balance in account.account_balance()? {
+                    if !is_zero(balance.balance) {
+                        snapshot.balances.insert(
+                            balance.asset.clone(),
+                            Balance {
+                                asset: balance.asset,
+                                free: balance.balance,
+                                locked: Amount::default(),
+                            },
+                        );
+                    }
+                }
+                for position in account.position_information()? {
+                    if !is_zero(position.position_amount) {
+                        snapshot.positions.insert(
+                            (position.symbol.clone(), position.position_side.clone()),
+                            Position {
+                                symbol: position.symbol,
+                                position_side: position.position_side,
+                                amount: position.position_amount,
+                                entry_price: position.entry_price,
+                                unrealized_pnl: position.unrealized_profit,
+                            },
+                        );
+                    }
+                }
+                for order in account.get_all_open_orders()? {
+                    snapshot.orders.insert(
+                        order.order_id,
+                        OpenOrder {
+                            symbol: order.symbol,
+                            order_id: order.order_id,
+                            client_order_id: order.client_order_id,
+                            side: order.side,
+                            order_type: order.order_type,
+                            status: order.status,
+                            price: order.price,
+                            orig_qty: order.orig_qty,
+                            executed_qty: order.executed_qty,
+                            update_time: order.update_time,
+                        },
+                    );
+                }
+            }
+        }
+        Ok(snapshot)
+    }
+}
+
+impl State {
+    fn set_balance(&mut self, asset: &str, free: Amount, locked: Amount) {
+        // Like the REST snapshot, which leaves out empty balances.
+        let balance = if is_zero(free) && is_zero(locked) {
+            None
+        } else {
+            Some(Balance {
+                asset: asset.into(),
+                free,
+                locked,
+            })
+        };
+        set(&mut self.balances, asset.into(), balance);
+    }
+
+    fn set_order(&mut self, order: OpenOrder) {
+        // Reports can arrive out of order from the REST responses.
+        if let Some(known) = self.orders.get(&order.order_id).and_then(|known| known.value.as_ref()) {
+            if order.update_time < known.update_time {
+                return;
+            }
+        }
+        let order_id = order.order_id;
+        let order = match order.status {
+            OrderStatus::New | OrderStatus::PartiallyFilled | OrderStatus::PendingCancel => Some(order),
+            _ => None,
+        };
+        set(&mut self.orders, order_id, order);
+    }
+
+    fn set_position(&mut self, position: Position) {
+        let key = (position.symbol.clone(), position.position_side.clone());
+        let position = if is_zero(position.amount) { None } else { Some(position) };
+        set(&mut self.positions, key, position);
+    }
+}
+
+// Sets an entry, `None` for a removal, and drops the expired tombstones.
+fn set<K: Ord, T>(entries: &mut BTreeMap<K, Tracked<Option<T>>>, key: K, value: Option<T>) {
+    if value.is_none() {
+        entries.retain(|_, entry| entry.value.is_some() || entry.updated_at.elapsed() < TOMBSTONE_TTL);
+    }
+    entries.insert(key, Tracked::new(value));
+}
+
+/// Stops the reconciliations of [`AccountState::reconcile_every`] on drop.
+pub struct Reconciler {
+    stop: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for Reconciler {
+    fn drop(&mut self) {
+        // Disconnecting the channel wakes the thread up.
+        self.stop.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+// Replaces `local` with `remote`, but for the entries updated or removed
+// since `started_at`, and returns the keys that were different.
+fn reconcile<K, T, F>(
+    local: &mut BTreeMap<K, Tracked<Option<T>>>, mut remote: BTreeMap<K, T>, started_at: Instant, same: F,
+) -> Vec<K>
+where
+    K: Ord + Clone,
+    F: Fn(&T, &T) -> bool,
+{
+    let keys: BTreeSet<K> = local.keys().chain(remote.keys()).cloned().collect();
+    let mut drift = Vec::new();
+    for key in keys {
+        let known = local.get(&key);
+        if known.is_some_and(|known| known.updated_at > started_at) {
+            continue;
+        }
+        let known = known.and_then(|known| known.value.as_ref());
+        let differs = match (known, remote.get(&key)) {
+            (Some(known), Some(value)) => !same(known, value),
+            (None, None) => false,
+            _ => true,
+        };
+        if differs {
+            drift.push(key.clone());
+        }
+        match remote.remove(&key) {
+            Some(value) if differs => {
+                local.insert(key, Tracked::new(Some(value)));
+            }
+            Some(_) => (),
+            None => {
+                local.remove(&key);
+            }
+        }
+    }
+    drift
+}
+
+fn is_zero(amount: Amount) -> bool {
+    amount == Amount::default()
+}
@@ -1,22 +1,30 @@
 use crate::account::*;
 use crate::client::*;
 use crate::config::*;
+use crate::credentials::*;
+use crate::errors::*;
 use crate::futures::account::FuturesAccount;
 use crate::futures::general::*;
 use crate::futures::market::*;
 use crate::general::*;
 use crate::market::*;
+use crate::sub_account::*;
 use crate::userstream::*;
 
+pub use crate::client::RateLimitUsage;
+
 #[allow(clippy::all)]
+#[derive(Clone, Copy)]
 pub enum API {
     Spot(Spot),
+    Sapi(Sapi),
     Futures(Futures),
 }
 
 /// Endpoint for production and test orders.
 ///
 /// Orders issued to test are validated, but not sent into the matching engine.
+#[derive(Clone, Copy)]
 pub enum Spot {
     Ping,
     Time,
@@ -43,6 +51,18 @@ pub enum Spot {
     UserDataStream,
 }
 
+/// Endpoints served under `/sapi`, such as sub-account management.
+#[derive(Clone, Copy)]
+pub enum Sapi {
+    SubAccountList,
+    SubAccountAssets,
+    SubAccountFuturesAccount,
+    SubAccountFuturesPositionRisk,
+    SubAccountUniversalTransfer,
+    UserDataStream,
+}
+
+#[derive(Clone, Copy)]
 pub enum Futures {
     Ping,
     Time,
@@ -62,6 +82,9 @@ pub enum Futures {
     BookTicker,
     AllForceOrders,
     AllOpenOrders,
+    OpenOrders,
+    PositionRisk,
+    Balance,
     Order,
     PositionSide,
     OpenInterest,
@@ -105,6 +128,14 @@ impl From<API> for String {
                 Spot::MyTrades => "/api/v3/myTrades",
                 Spot::UserDataStream => "/api/v3/userDataStream",
             },
+            API::Sapi(route) => match route {
+                Sapi::SubAccountList => "/sapi/v1/sub-account/list",
+                Sapi::SubAccountAssets => "/sapi/v3/sub-account/assets",
+                Sapi::SubAccountFuturesAccount => "/sapi/v1/sub-account/futures/account",
+                Sapi::SubAccountFuturesPositionRisk => "/sapi/v1/sub-account/futures/positionRisk",
+                Sapi::SubAccountUniversalTransfer => "/sapi/v1/sub-account/universalTransfer",
+                Sapi::UserDataStream => "/sapi/v1/userDataStream",
+            },
             API::Futures(route) => match route {
                 Futures::Ping => "/fapi/v1/ping",
                 Futures::Time => "/fapi/v1/time",
@@ -124,6 +155,9 @@ impl From<API> for String {
                 Futures::BookTicker => "/fapi/v1/ticker/bookTicker",
                 Futures::AllForceOrders => "/fapi/v1/allForceOrders",
                 Futures::AllOpenOrders => "/fapi/v1/allOpenOrders",
+                Futures::OpenOrders => "/fapi/v1/openOrders",
+                Futures::PositionRisk => "/fapi/v2/positionRisk",
+                Futures::Balance => "/fapi/v2/balance",
                 Futures::PositionSide => "/fapi/v1/positionSide/dual",
                 Futures::Order => "/fapi/v1/order",
                 Futures::OpenInterest => "/fapi/v1/openInterest",
@@ -142,70 +176,110 @@ impl From<API> for String {
     }
 }
 
-pub trait Binance {
-    fn new(api_key: Option<String>, secret_key: Option<String>) -> Self;
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
-    ) -> Self;
+impl API {
+    /// Request weight with the default parameters, as listed in the Binance docs.
+    /// Larger `limit`s or requests without a symbol can cost more.
+    pub fn weight(&self) -> u32 {
+        match self {
+            API::Spot(route) => match route {
+                Spot::ExchangeInfo => 20,
+                Spot::Depth => 5,
+                Spot::Trades | Spot::HistoricalTrades => 25,
+                Spot::AggTrades | Spot::Klines | Spot::AvgPrice => 2,
+                Spot::Ticker24hr | Spot::Price | Spot::BookTicker => 2,
+                Spot::OpenOrders | Spot::OpenOrderList => 6,
+                Spot::OrderList => 4,
+                Spot::AllOrders | Spot::AllOrderList | Spot::Account | Spot::MyTrades => 20,
+                Spot::UserDataStream => 2,
+                Spot::Ping | Spot::Time | Spot::Order | Spot::OrderTest | Spot::Oco => 1,
+            },
+            API::Sapi(route) => match route {
+                Sapi::SubAccountList | Sapi::UserDataStream => 1,
+                Sapi::SubAccountAssets => 60,
+                Sapi::SubAccountFuturesAccount | Sapi::SubAccountFuturesPositionRisk => 10,
+                Sapi::SubAccountUniversalTransfer => 360,
+            },
+            API::Futures(route) => match route {
+                Futures::Depth => 10,
+                Futures::Trades | Futures::BatchOrders => 5,
+                Futures::Klines
+                | Futures::ContinuousKlines
+                | Futures::IndexPriceKlines
+                | Futures::MarkPriceKlines => 5,
+                Futures::HistoricalTrades | Futures::AggTrades | Futures::AllForceOrders => 20,
+                Futures::PositionRisk | Futures::Balance => 5,
+                // Without a symbol.
+                Futures::OpenOrders => 40,
+                _ => 1,
+            },
+        }
+    }
 }
 
-impl Binance for General {
-    fn new(api_key: Option<String>, secret_key: Option<String>) -> General {
+pub trait Binance: Sized {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> Result<Self> {
         Self::new_with_config(api_key, secret_key, &Config::default())
     }
 
     fn new_with_config(
         api_key: Option<String>, secret_key: Option<String>, config: &Config,
-    ) -> General {
-        General {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
-        }
+    ) -> Result<Self> {
+        Self::new_with_credentials(Credentials::new(api_key, secret_key), config)
     }
+
+    /// Loads the keys once from `provider`, e.g. environment variables, files or a vault.
+    fn new_with_provider<P>(provider: &P, config: &Config) -> Result<Self>
+    where
+        P: CredentialProvider + ?Sized,
+    {
+        Self::new_with_credentials(provider.credentials()?, config)
+    }
+
+    fn new_with_credentials(credentials: Credentials, config: &Config) -> Result<Self>;
 }
 
-impl Binance for Account {
-    fn new(api_key: Option<String>, secret_key: Option<String>) -> Account {
-        Self::new_with_config(api_key, secret_key, &Config::default())
+impl Binance for General {
+    fn new_with_credentials(credentials: Credentials, config: &Config) -> Result<General> {
+        Ok(General {
+            client: Client::new(credentials, config.rest_api_endpoint.clone(), config)?,
+        })
     }
+}
 
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
-    ) -> Account {
-        Account {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
+impl Binance for Account {
+    fn new_with_credentials(credentials: Credentials, config: &Config) -> Result<Account> {
+        Ok(Account {
+            client: Client::new(credentials, config.rest_api_endpoint.clone(), config)?,
             recv_window: config.recv_window,
             timestamp_shift: None,
-        }
+        })
     }
 }
 
 impl Binance for Market {
-    fn new(api_key: Option<String>, secret_key: Option<String>) -> Market {
-        Self::new_with_config(api_key, secret_key, &Config::default())
-    }
-
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
-    ) -> Market {
-        Market {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
+    fn new_with_credentials(credentials: Credentials, config: &Config) -> Result<Market> {
+        Ok(Market {
+            client: Client::new(credentials, config.rest_api_endpoint.clone(), config)?,
             recv_window: config.recv_window,
-        }
+        })
     }
 }
 
 impl Binance for UserStream {
-    fn new(api_key: Option<String>, secret_key: Option<String>) -> UserStream {
-        Self::new_with_config(api_key, secret_key, &Config::default())
+    fn new_with_credentials(credentials: Credentials, config: &Config) -> Result<UserStream> {
+        Ok(UserStream {
+            client: Client::new(credentials, config.rest_api_endpoint.clone(), config)?,
+            recv_window: config.recv_window,
+        })
     }
+}
 
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
-    ) -> UserStream {
-        UserStream {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
+impl Binance for SubAccount {
+    fn new_with_credentials(credentials: Credentials, config: &Config) -> Result<SubAccount> {
+        Ok(SubAccount {
+            client: Client::new(credentials, config.rest_api_endpoint.clone(), config)?,
             recv_window: config.recv_window,
-        }
+        })
     }
 }
 
@@ -214,58 +288,152 @@ impl Binance for UserStream {
 // *****************************************************
 
 impl Binance for FuturesGeneral {
-    fn new(api_key: Option<String>, secret_key: Option<String>) -> FuturesGeneral {
-        Self::new_with_config(api_key, secret_key, &Config::default())
+    fn new_with_credentials(credentials: Credentials, config: &Config) -> Result<FuturesGeneral> {
+        Ok(FuturesGeneral {
+            client: Client::new(credentials, config.futures_rest_api_endpoint.clone(), config)?,
+        })
     }
+}
 
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
-    ) -> FuturesGeneral {
-        FuturesGeneral {
-            client: Client::new(
-                api_key,
-                secret_key,
-                config.futures_rest_api_endpoint.clone(),
-            ),
-        }
+impl Binance for FuturesMarket {
+    fn new_with_credentials(credentials: Credentials, config: &Config) -> Result<FuturesMarket> {
+        Ok(FuturesMarket {
+            client: Client::new(credentials, config.futures_rest_api_endpoint.clone(), config)?,
+            recv_window: config.recv_window,
+        })
     }
 }
 
-impl Binance for FuturesMarket {
-    fn new(api_key: Option<String>, secret_key: Option<String>) -> FuturesMarket {
-        Self::new_with_config(api_key, secret_key, &Config::default())
+impl Binance for FuturesAccount {
+    fn new_with_credentials(credentials: Credentials, config: &Config) -> Result<Self> {
+        Ok(Self {
+            client: Client::new(credentials, config.futures_rest_api_endpoint.clone(), config)?,
+            recv_window: config.recv_window,
+            request_builder: Default::default(),
+        })
     }
+}
 
-    fn new_with_config(
+// *****************************************************
+//              Shared client
+// *****************************************************
+
+/// Entry point sharing one HTTP connection pool between all the API structs.
+///
+/// ```rust,no_run
+/// use binance::api::*;
+/// use binance::config::*;
+///
+/// let binance = BinanceClient::new(Some("api_key".into()), Some("secret_key".into()), &Config::default()).unwrap();
+/// let prices = binance.spot().market().get_all_prices().unwrap();
+/// let balance = binance.spot().account().get_balance("BTC").unwrap();
+/// let mark_prices = binance.futures().market().get_mark_prices().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct BinanceClient {
+    spot: Client,
+    futures: Client,
+    recv_window: u64,
+}
+
+impl BinanceClient {
+    pub fn new(
         api_key: Option<String>, secret_key: Option<String>, config: &Config,
-    ) -> FuturesMarket {
-        FuturesMarket {
-            client: Client::new(
-                api_key,
-                secret_key,
-                config.futures_rest_api_endpoint.clone(),
-            ),
+    ) -> Result<Self> {
+        Self::new_with_credentials(Credentials::new(api_key, secret_key), config)
+    }
+
+    pub fn new_with_provider<P>(provider: &P, config: &Config) -> Result<Self>
+    where
+        P: CredentialProvider + ?Sized,
+    {
+        Self::new_with_credentials(provider.credentials()?, config)
+    }
+
+    pub fn new_with_credentials(credentials: Credentials, config: &Config) -> Result<Self> {
+        let spot = Client::new(credentials, config.rest_api_endpoint.clone(), config)?;
+        let futures = spot.with_host(config.futures_rest_api_endpoint.clone());
+        Ok(BinanceClient {
+            spot,
+            futures,
             recv_window: config.recv_window,
+        })
+    }
+
+    pub fn spot(&self) -> SpotApi<'_> {
+        SpotApi { client: &self.spot, recv_window: self.recv_window }
+    }
+
+    pub fn futures(&self) -> FuturesApi<'_> {
+        FuturesApi { client: &self.futures, recv_window: self.recv_window }
+    }
+}
+
+/// Spot endpoints of a [`BinanceClient`].
+pub struct SpotApi<'a> {
+    client: &'a Client,
+    recv_window: u64,
+}
+
+impl<'a> SpotApi<'a> {
+    pub fn general(&self) -> General {
+        General { client: self.client.clone() }
+    }
+
+    pub fn market(&self) -> Market {
+        Market { client: self.client.clone(), recv_window: self.recv_window }
+    }
+
+    pub fn account(&self) -> Account {
+        Account {
+            client: self.client.clone(),
+            recv_window: self.recv_window,
+            timestamp_shift: None,
         }
     }
+
+    pub fn user_stream(&self) -> UserStream {
+        UserStream { client: self.client.clone(), recv_window: self.recv_window }
+    }
+
+    pub fn sub_account(&self) -> SubAccount {
+        SubAccount { client: self.client.clone(), recv_window: self.recv_window }
+    }
+
+    pub fn rate_limit_usage(&self) -> RateLimitUsage {
+        self.client.rate_limit_usage()
+    }
 }
 
-impl Binance for FuturesAccount {
-    fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
-        Self::new_with_config(api_key, secret_key, &Config::default())
+/// USD-M futures endpoints of a [`BinanceClient`].
+pub struct FuturesApi<'a> {
+    client: &'a Client,
+    recv_window: u64,
+}
+
+impl<'a> FuturesApi<'a> {
+    pub fn general(&self) -> FuturesGeneral {
+        FuturesGeneral { client: self.client.clone() }
     }
 
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
-    ) -> Self {
-        Self {
-            client: Client::new(
-                api_key,
-                secret_key,
-                config.futures_rest_api_endpoint.clone(),
-            ),
-            recv_window: config.recv_window,
+    pub fn market(&self) -> FuturesMarket {
+        FuturesMarket { client: self.client.clone(), recv_window: self.recv_window }
+    }
+
+    pub fn account(&self) -> FuturesAccount {
+        FuturesAccount {
+            client: self.client.clone(),
+            recv_window: self.recv_window,
             request_builder: Default::default(),
         }
     }
+
+    /// Use the `*_futures` methods of the returned stream.
+    pub fn user_stream(&self) -> UserStream {
+        UserStream { client: self.client.clone(), recv_window: self.recv_window }
+    }
+
+    pub fn rate_limit_usage(&self) -> RateLimitUsage {
+        self.client.rate_limit_usage()
+    }
 }
@@ -0,0 +1,315 @@
+//! Candles built locally from trade streams.
+//!
+//! A [`CandleBuilder`] aggregates the trades of one symbol into
+//! [`KlineSummary`] candles, over any time interval or as volume, tick or
+//! dollar bars:
+//!
+//! ```rust,no_run
+//! use binance::candles::*;
+//! use binance::websockets::*;
+//! use std::sync::atomic::AtomicBool;
+//! use std::time::Duration;
+//!
+//! let mut builder = CandleBuilder::new("BTCUSDT", BarType::Time(Duration::from_secs(3)));
+//! let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+//!     for candle in builder.handle_event(&event) {
+//!         println!("{:?}", candle);
+//!     }
+//!     Ok(())
+//! });
+//! web_socket.connect("btcusdt@trade").unwrap();
+//! web_socket.event_loop(&AtomicBool::new(true)).unwrap();
+//! ```
+//!
+//! Time candles are aligned on the epoch like the exchange's, so 1 minute
+//! candles match the `1m` klines. They close once a trade past their end
+//! arrives, or on [`CandleBuilder::advance_to`] when the market is quiet.
+
+use crate::model::{Amount, KlineSummary};
+use crate::websockets::WebsocketEvent;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// How trades are grouped into candles.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BarType {
+    /// Fixed time intervals, of at least a millisecond.
+    Time(Duration),
+    /// Closes once this much of the base asset has traded.
+    Volume(Amount),
+    /// Closes after this many trades.
+    Tick(u64),
+    /// Closes once this much of the quote asset has traded.
+    Dollar(Amount),
+}
+
+/// Aggregates the trades of one symbol into candles.
+///
+/// Candles other than time candles close on the trade that reaches their
+/// threshold, trades are not split between candles.
+pub struct CandleBuilder {
+    symbol: String,
+    bar_type: BarType,
+    lateness: i64,
+    fill_gaps: bool,
+    late_events: u64,
+    // Time candles still open, by open time.
+    open: BTreeMap<i64, Candle>,
+    // Open time of the first time candle not emitted yet.
+    next_open: Option<i64>,
+    watermark: i64,
+    last_close: Option<Amount>,
+    // Volume, tick or dollar candle in progress and its measure so far.
+    current: Option<Candle>,
+    measure: Amount,
+}
+
+struct Candle {
+    kline: KlineSummary,
+    first_trade: i64,
+    last_trade: i64,
+}
+
+impl CandleBuilder {
+    pub fn new<S: Into<String>>(symbol: S, bar_type: BarType) -> Self {
+        if let BarType::Time(interval) = bar_type {
+            assert!(interval.as_millis() > 0, "Candle interval under a millisecond");
+        }
+        CandleBuilder {
+            symbol: symbol.into(),
+            bar_type,
+            lateness: 0,
+            fill_gaps: true,
+            late_events: 0,
+            open: BTreeMap::new(),
+            next_open: None,
+            watermark: i64::MIN,
+            last_close: None,
+            current: None,
+            measure: Amount::default(),
+        }
+    }
+
+    /// How long a time candle stays open for trades arriving out of order
+    /// after its end, none by default.
+    pub fn set_allowed_lateness(mut self, lateness: Duration) -> Self {
+        self.lateness = lateness.as_millis() as i64;
+        self
+    }
+
+    /// Whether time intervals without trades get a candle at the previous
+    /// close with no volume, as the exchange does. On by default.
+    pub fn set_fill_gaps(mut self, fill_gaps: bool) -> Self {
+        self.fill_gaps = fill_gaps;
+        self
+    }
+
+    /// Number of trades dropped for arriving after their time candle closed.
+    pub fn late_events(&self) -> u64 {
+        self.late_events
+    }
+
+    /// The latest candle still open.
+    pub fn current(&self) -> Option<&KlineSummary> {
+        match self.bar_type {
+            BarType::Time(_) => self.open.values().next_back(),
+            _ => self.current.as_ref(),
+        }
+        .map(|candle| &candle.kline)
+    }
+
+    /// Adds the trades of `event` if it is a trade or aggregate trade of the
+    /// symbol, and returns the candles that closed.
+    pub fn handle_event(&mut self, event: &WebsocketEvent) -> Vec<KlineSummary> {
+        match event {
+            WebsocketEvent::Trade(trade) if trade.symbol == self.symbol => self.push(
+                trade.trade_order_time,
+                trade.price,
+                trade.qty,
+                trade.is_buyer_maker,
+                1,
+            ),
+            WebsocketEvent::AggrTrades(trade) if trade.symbol == self.symbol => self.push(
+                trade.trade_order_time,
+                trade.price,
+                trade.qty,
+                trade.is_buyer_maker,
+                trade.last_break_trade_id - trade.first_break_trade_id + 1,
+            ),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Adds `trades` trades, at `time` in milliseconds, and returns the
+    /// candles that closed. For trades from other sources than the websockets,
+    /// such as [`historical`](crate::historical) archives.
+    pub fn push(
+        &mut self, time: u64, price: Amount, qty: Amount, is_buyer_maker: bool, trades: u64,
+    ) -> Vec<KlineSummary> {
+        let time = time as i64;
+        match self.bar_type {
+            BarType::Time(_) => {
+                let open_time = self.align(time);
+                if self.next_open.is_some_and(|next_open| open_time < next_open) {
+                    self.late_events += 1;
+                    return Vec::new();
+                }
+                self.next_open.get_or_insert(open_time);
+                let close_time = open_time + self.interval() - 1;
+                let candle = self
+                    .open
+                    .entry(open_time)
+                    .or_insert_with(|| Candle::new(new_kline(open_time, close_time, price)));
+                candle.add_trade(time, price, qty, is_buyer_maker, trades);
+                self.close_until(time - self.lateness)
+            }
+            bar_type => {
+                let candle = self.current.get_or_insert_with(|| Candle::new(new_kline(time, time, price)));
+                candle.add_trade(time, price, qty, is_buyer_maker, trades);
+                candle.kline.open_time = candle.first_trade;
+                candle.kline.close_time = candle.last_trade;
+                let closed = match bar_type {
+                    BarType::Volume(threshold) => {
+                        self.measure += qty;
+                        self.measure >= threshold
+                    }
+                    BarType::Dollar(threshold) => {
+                        self.measure += price * qty;
+                        self.measure >= threshold
+                    }
+                    BarType::Tick(threshold) => candle.kline.number_of_trades as u64 >= threshold,
+                    BarType::Time(_) => unreachable!(),
+                };
+                if !closed {
+                    return Vec::new();
+                }
+                self.measure = Amount::default();
+                self.current.take().map(|candle| candle.kline).into_iter().collect()
+            }
+        }
+    }
+
+    /// Closes the time candles that ended before `time`, in milliseconds,
+    /// less the allowed lateness. Use the clock or the event time of another
+    /// stream so that candles close while no trades come.
+    pub fn advance_to(&mut self, time: u64) -> Vec<KlineSummary> {
+        match self.bar_type {
+            BarType::Time(_) => self.close_until(time as i64 - self.lateness),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Closes every candle still open, for instance at the end of a recording.
+    pub fn flush(&mut self) -> Vec<KlineSummary> {
+        match self.open.keys().next_back() {
+            Some(&last) => {
+                let end = last + self.interval();
+                self.close_until(end)
+            }
+            None => {
+                self.measure = Amount::default();
+                self.current.take().map(|candle| candle.kline).into_iter().collect()
+            }
+        }
+    }
+
+    fn interval(&self) -> i64 {
+        match self.bar_type {
+            BarType::Time(interval) => interval.as_millis() as i64,
+            _ => 0,
+        }
+    }
+
+    fn align(&self, time: i64) -> i64 {
+        time - time.rem_euclid(self.interval())
+    }
+
+    fn close_until(&mut self, watermark: i64) -> Vec<KlineSummary> {
+        self.watermark = self.watermark.max(watermark);
+        let interval = self.interval();
+        let mut closed = Vec::new();
+        while let Some(open_time) = self.next_open {
+            if open_time + interval > self.watermark {
+                break;
+            }
+            let next_open = match self.open.remove(&open_time) {
+                Some(candle) => {
+                    self.last_close = Some(candle.kline.close);
+                    closed.push(candle.kline);
+                    open_time + interval
+                }
+                None if self.fill_gaps => {
+                    // Trades start a candle at their own interval, so there was a close before.
+                    let close = self.last_close.unwrap_or_default();
+                    closed.push(new_kline(open_time, open_time + interval - 1, close));
+                    open_time + interval
+                }
+                // Straight to the next trade, or to the first candle that is still open.
+                None => match self.open.keys().next() {
+                    Some(&next_open) => next_open.min(self.align(self.watermark)),
+                    None => self.align(self.watermark),
+                },
+            };
+            self.next_open = Some(next_open);
+        }
+        closed
+    }
+}
+
+impl Candle {
+    fn new(kline: KlineSummary) -> Self {
+        Candle {
+            kline,
+            first_trade: i64::MAX,
+            last_trade: i64::MIN,
+        }
+    }
+
+    fn add_trade(&mut self, time: i64, price: Amount, qty: Amount, is_buyer_maker: bool, trades: u64) {
+        let kline = &mut self.kline;
+        if kline.number_of_trades == 0 {
+            kline.high = price;
+            kline.low = price;
+        }
+        // Trades out of order only move the open or close if they are the first or the latest.
+        if time < self.first_trade {
+            kline.open = price;
+            self.first_trade = time;
+        }
+        if time >= self.last_trade {
+            kline.close = price;
+            self.last_trade = time;
+        }
+        if price > kline.high {
+            kline.high = price;
+        }
+        if price < kline.low {
+            kline.low = price;
+        }
+        let quote = price * qty;
+        kline.volume += qty;
+        kline.quote_asset_volume += quote;
+        kline.number_of_trades += trades as i64;
+        // The buyer is the taker when the maker is the seller.
+        if !is_buyer_maker {
+            kline.taker_buy_base_asset_volume += qty;
+            kline.taker_buy_quote_asset_volume += quote;
+        }
+    }
+}
+
+fn new_kline(open_time: i64, close_time: i64, price: Amount) -> KlineSummary {
+    KlineSummary {
+        open_time,
+        open: price,
+        high: price,
+        low: price,
+        close: price,
+        volume: Amount::default(),
+        close_time,
+        quote_asset_volume: Amount::default(),
+        number_of_trades: 0,
+        taker_buy_base_asset_volume: Amount::default(),
+        taker_buy_quote_asset_volume: Amount::default(),
+    }
+}
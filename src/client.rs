@@ -1,183 +1,280 @@
-use hex::encode as hex_encode;
-use hmac::{Hmac, Mac, NewMac};
+use crate::config::Config;
+use crate::credentials::{Credentials, Secret};
 use crate::errors::*;
-use reqwest::StatusCode;
-use reqwest::blocking::Response;
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT, CONTENT_TYPE};
-use sha2::Sha256;
+use crate::middleware::{redact, Middleware, RequestInfo, ResponseInfo};
+use crate::signer::{HmacSigner, Signer};
+use crate::transport::{HttpRequest, HttpResponse, ReqwestTransport, Transport};
+use crate::util::lock;
+use reqwest::header::HeaderValue;
 use serde::de::DeserializeOwned;
 use crate::api::API;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::form_urlencoded::byte_serialize;
+
+/// Usage reported in the `X-MBX-USED-WEIGHT-*` and `X-MBX-ORDER-COUNT-*` headers
+/// of the latest response, keyed by interval such as `1m` or `10s`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RateLimitUsage {
+    pub used_weight: BTreeMap<String, u64>,
+    pub order_count: BTreeMap<String, u64>,
+}
+
+impl RateLimitUsage {
+    fn from_headers(headers: &[(String, String)]) -> Self {
+        let mut usage = RateLimitUsage::default();
+        for (name, value) in headers {
+            let value = match value.parse() {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+            let name = name.to_ascii_lowercase();
+            if let Some(interval) = name.strip_prefix("x-mbx-used-weight-") {
+                usage.used_weight.insert(interval.to_string(), value);
+            } else if let Some(interval) = name.strip_prefix("x-mbx-order-count-") {
+                usage.order_count.insert(interval.to_string(), value);
+            }
+        }
+        usage
+    }
+}
 
 #[derive(Clone)]
 pub struct Client {
-    api_key: String,
-    secret_key: String,
+    api_key: Option<Secret>,
+    signer: Arc<dyn Signer>,
     host: String,
-    inner_client: reqwest::blocking::Client,
+    transport: Arc<dyn Transport>,
+    signed_payload_in_body: bool,
+    user_agent: String,
+    rate_limit_usage: Arc<Mutex<RateLimitUsage>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
     timestamp_shift: Option<i64>,
 }
 
 impl Client {
 
-    pub fn new(api_key: Option<String>, secret_key: Option<String>, host: String) -> Self {
-        Client {
-            api_key: api_key.unwrap_or_else(|| "".into()),
-            secret_key: secret_key.unwrap_or_else(|| "".into()),
+    pub fn new(credentials: Credentials, host: String, config: &Config) -> Result<Self> {
+        let signer: Arc<dyn Signer> = match &config.signer {
+            Some(signer) => signer.clone(),
+            None => Arc::new(HmacSigner::new(credentials.secret_key.unwrap_or_else(|| "".into()))),
+        };
+        let transport: Arc<dyn Transport> = match &config.transport {
+            Some(transport) => transport.clone(),
+            None => Arc::new(ReqwestTransport::from_config(config)?),
+        };
+        HeaderValue::from_str(&config.user_agent)?;
+        Ok(Client {
+            api_key: credentials.api_key,
+            signer,
             host,
-            inner_client: reqwest::blocking::Client::builder()
-                .pool_idle_timeout(None)
-                .build()
-                .unwrap(),
+            transport,
+            signed_payload_in_body: config.signed_payload_in_body,
+            user_agent: config.user_agent.clone(),
+            rate_limit_usage: Default::default(),
+            middlewares: config.middlewares.clone(),
             timestamp_shift: None,
+        })
+    }
+
+    // Same credentials and connection pool, against another host. Rate limits are
+    // counted per host, so the usage starts out empty.
+    pub(crate) fn with_host(&self, host: String) -> Self {
+        Client {
+            host,
+            rate_limit_usage: Default::default(),
+            ..self.clone()
         }
     }
 
-    pub fn get_signed<T: DeserializeOwned>(&self, endpoint: API, request: Option<String>) -> Result<T> {
-        let url = self.sign_request(endpoint, request);
-        let client = &self.inner_client;
-        let response = client
-            .get(url.as_str())
-            .headers(self.build_headers(true)?)
-            .send()?;
+    pub fn rate_limit_usage(&self) -> RateLimitUsage {
+        lock(&self.rate_limit_usage).clone()
+    }
 
-        self.handler(response)
+    pub fn get_signed<T: DeserializeOwned>(&self, endpoint: API, request: Option<String>) -> Result<T> {
+        self.send_signed("GET", endpoint, request)
     }
 
     pub fn post_signed<T: DeserializeOwned>(&self, endpoint: API, request: String) -> Result<T> {
-        let url = self.sign_request(endpoint, Some(request));
-        let client = &self.inner_client;
-        let response = client
-            .post(url.as_str())
-            .headers(self.build_headers(true)?)
-            .send()?;
-
-        self.handler(response)
+        self.send_signed("POST", endpoint, Some(request))
     }
 
     pub fn delete_signed<T: DeserializeOwned>(&self, endpoint: API, request: Option<String>) -> Result<T> {
-        let url = self.sign_request(endpoint, request);
-        let client = &self.inner_client;
-        let response = client
-            .delete(url.as_str())
-            .headers(self.build_headers(true)?)
-            .send()?;
-
-        self.handler(response)
+        self.send_signed("DELETE", endpoint, request)
     }
 
     pub fn get<T: DeserializeOwned>(&self, endpoint: API, request: Option<String>) -> Result<T> {
+        let headers = vec![("user-agent".into(), self.user_agent.clone())];
+        self.send_get(endpoint, request, headers)
+    }
+
+    /// Unsigned GET carrying the API key, for the market data endpoints that
+    /// require one such as `historicalTrades`.
+    pub fn get_with_api_key<T: DeserializeOwned>(&self, endpoint: API, request: Option<String>) -> Result<T> {
+        self.send_get(endpoint, request, self.build_headers(false))
+    }
+
+    fn send_get<T: DeserializeOwned>(
+        &self, endpoint: API, request: Option<String>, headers: Vec<(String, String)>,
+    ) -> Result<T> {
+        let request = request.unwrap_or_default();
         let mut url: String = format!("{}{}", self.host, String::from(endpoint));
-        if let Some(request) = request {
-            if !request.is_empty() {
-                url.push_str(format!("?{}", request).as_str());
-            }
+        if !request.is_empty() {
+            url.push_str(format!("?{}", request).as_str());
         }
 
-        let client = &self.inner_client;
-        let response = client.get(url.as_str()).send()?;
-
-        self.handler(response)
+        let http_request = HttpRequest {
+            method: "GET".into(),
+            url,
+            headers,
+            body: String::new(),
+        };
+        self.send(endpoint, &request, false, http_request)
     }
 
     pub fn post<T: DeserializeOwned>(&self, endpoint: API) -> Result<T> {
-        let url: String = format!("{}{}", self.host, String::from(endpoint));
+        let http_request = HttpRequest {
+            method: "POST".into(),
+            url: format!("{}{}", self.host, String::from(endpoint)),
+            headers: self.build_headers(false),
+            body: String::new(),
+        };
+        self.send(endpoint, "", false, http_request)
+    }
 
-        let client = &self.inner_client;
-        let response = client
-            .post(url.as_str())
-            .headers(self.build_headers(false)?)
-            .send()?;
+    pub fn put<T: DeserializeOwned>(&self, endpoint: API, listen_key: &str) -> Result<T> {
+        self.send_listen_key("PUT", endpoint, listen_key)
+    }
 
-        self.handler(response)
+    pub fn delete<T: DeserializeOwned>(&self, endpoint: API, listen_key: &str) -> Result<T> {
+        self.send_listen_key("DELETE", endpoint, listen_key)
     }
 
-    pub fn put<T: DeserializeOwned>(&self, endpoint: API, listen_key: &str) -> Result<T> {
-        let url: String = format!("{}{}", self.host, String::from(endpoint));
+    fn send_listen_key<T: DeserializeOwned>(&self, method: &str, endpoint: API, listen_key: &str) -> Result<T> {
         let data: String = format!("listenKey={}", listen_key);
+        let http_request = HttpRequest {
+            method: method.into(),
+            url: format!("{}{}", self.host, String::from(endpoint)),
+            headers: self.build_headers(false),
+            body: data.clone(),
+        };
+        self.send(endpoint, &data, false, http_request)
+    }
 
-        let client = &self.inner_client;
-        let response = client
-            .put(url.as_str())
-            .headers(self.build_headers(false)?)
-            .body(data)
-            .send()?;
+    // Request must be signed. GET requests always carry the signed parameters in the
+    // query string, other methods put them in the form body if configured to.
+    fn send_signed<T: DeserializeOwned>(
+        &self, method: &str, endpoint: API, request: Option<String>,
+    ) -> Result<T> {
+        let url: String = format!("{}{}", self.host, String::from(endpoint));
+        let request_body = self.sign_payload(request)?;
+
+        let (url, body) = if self.signed_payload_in_body && method != "GET" {
+            (url, request_body.clone())
+        } else {
+            (format!("{}?{}", url, request_body), String::new())
+        };
 
-        self.handler(response)
+        let http_request = HttpRequest {
+            method: method.into(),
+            url,
+            headers: self.build_headers(true),
+            body,
+        };
+        self.send(endpoint, &request_body, true, http_request)
     }
 
-    pub fn delete<T: DeserializeOwned>(&self, endpoint: API, listen_key: &str) -> Result<T> {
-        let url: String = format!("{}{}", self.host, String::from(endpoint));
-        let data: String = format!("listenKey={}", listen_key);
+    // Sends the request, running the middlewares around it. `payload` is only
+    // used to describe the request to them.
+    fn send<T: DeserializeOwned>(
+        &self, endpoint: API, payload: &str, signed: bool, http_request: HttpRequest,
+    ) -> Result<T> {
+        if self.middlewares.is_empty() {
+            let response = self.transport.send(http_request)?;
+            return self.handler(response);
+        }
 
-        let client = &self.inner_client;
-        let response = client
-            .delete(url.as_str())
-            .headers(self.build_headers(false)?)
-            .body(data)
-            .send()?;
-
-        self.handler(response)
-    }
-
-    // Request must be signed
-    fn sign_request(&self, endpoint: API, request: Option<String>) -> String {
-        match request {
-            Some(request) => {
-                let mut signed_key = Hmac::<Sha256>::new_varkey(self.secret_key.as_bytes()).unwrap();
-                signed_key.update(request.as_bytes());
-                let signature = hex_encode(signed_key.finalize().into_bytes());
-                let request_body: String = format!("{}&signature={}", request, signature);
-                format!("{}{}?{}", self.host, String::from(endpoint), request_body)
-            },
-            None => {
-                let signed_key = Hmac::<Sha256>::new_varkey(self.secret_key.as_bytes()).unwrap();
-                let signature = hex_encode(signed_key.finalize().into_bytes());
-                let request_body: String = format!("&signature={}", signature);
-                format!("{}{}?{}", self.host, String::from(endpoint), request_body)
-            }
+        let request = RequestInfo {
+            method: http_request.method.clone(),
+            path: String::from(endpoint),
+            payload: redact(payload),
+            signed,
+            weight: endpoint.weight(),
+        };
+        for middleware in &self.middlewares {
+            middleware.before_send(&request);
+        }
+
+        let start = Instant::now();
+        let result = self.transport.send(http_request);
+        let response = ResponseInfo {
+            status: result.as_ref().ok().map(|r| r.status),
+            latency: start.elapsed(),
+            rate_limit_usage: result
+                .as_ref()
+                .map(|r| RateLimitUsage::from_headers(&r.headers))
+                .unwrap_or_default(),
+        };
+        for middleware in &self.middlewares {
+            middleware.after_receive(&request, &response);
         }
+
+        self.handler(result?)
     }
 
-    fn build_headers(&self, content_type: bool) -> Result<HeaderMap> {
-        let mut custom_headers = HeaderMap::new();
+    fn sign_payload(&self, request: Option<String>) -> Result<String> {
+        let request = request.unwrap_or_default();
+        let signature: String = byte_serialize(self.signer.sign(&request)?.as_bytes()).collect();
+        Ok(format!("{}&signature={}", request, signature))
+    }
 
-        custom_headers.insert(USER_AGENT, HeaderValue::from_static("binance-rs"));
+    fn build_headers(&self, content_type: bool) -> Vec<(String, String)> {
+        let mut custom_headers = vec![("user-agent".to_string(), self.user_agent.clone())];
         if content_type {
-            custom_headers.insert(
-                CONTENT_TYPE,
-                HeaderValue::from_static("application/x-www-form-urlencoded"),
-            );
+            custom_headers.push((
+                "content-type".into(),
+                "application/x-www-form-urlencoded".into(),
+            ));
         }
-        custom_headers.insert(
-            HeaderName::from_static("x-mbx-apikey"),
-            HeaderValue::from_str(self.api_key.as_str())?,
-        );
+        let api_key = self.api_key.as_ref().map(Secret::expose).unwrap_or_default();
+        custom_headers.push(("x-mbx-apikey".into(), api_key.to_string()));
 
-        Ok(custom_headers)
+        custom_headers
     }
 
-    fn handler<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
-        match response.status() {
-            StatusCode::OK => {
-                Ok(response.json::<T>()?)
-            }
-            StatusCode::INTERNAL_SERVER_ERROR => {
-                bail!("Internal Server Error");
-            }
-            StatusCode::SERVICE_UNAVAILABLE => {
-                bail!("Service Unavailable");
-            }
-            StatusCode::UNAUTHORIZED => {
-                bail!("Unauthorized");
-            }
-            StatusCode::BAD_REQUEST => {
-                let error: BinanceContentError = response.json()?;
+    fn record_rate_limit_usage(&self, headers: &[(String, String)]) {
+        let latest = RateLimitUsage::from_headers(headers);
+        let mut usage = lock(&self.rate_limit_usage);
+        usage.used_weight.extend(latest.used_weight);
+        usage.order_count.extend(latest.order_count);
+    }
 
-                Err(ErrorKind::BinanceError(error).into())
-            }
-            s => {
-                bail!(format!("Received response: {:?}", s));
-            }
+    fn handler<T: DeserializeOwned>(&self, response: HttpResponse) -> Result<T> {
+        self.record_rate_limit_usage(&response.headers);
+
+        let status = response.status;
+        if status == 429 || status == 418 {
+            let retry_after = response
+                .header("retry-after")
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs);
+            return Err(BinanceError::RateLimited { retry_after });
+        }
+
+        let body = response.body;
+        if (200..300).contains(&status) {
+            return serde_json::from_str(&body).map_err(|e| BinanceError::decode(e, &body));
+        }
+        match serde_json::from_str::<BinanceContentError>(&body) {
+            Ok(error) => Err(BinanceError::Api {
+                code: error.code,
+                msg: error.msg,
+            }),
+            Err(_) => Err(BinanceError::Http {
+                status,
+                body: snippet(&body),
+            }),
         }
     }
 }
@@ -1,4 +1,20 @@
-#[derive(Clone, Debug, PartialEq)]
+use crate::middleware::Middleware;
+use crate::signer::Signer;
+use crate::transport::Transport;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// TLS implementation used by the REST client.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TlsBackend {
+    /// Whatever reqwest was compiled with, native-tls unless only `rustls-tls` is enabled.
+    Default,
+    NativeTls,
+    /// Requires the `rustls-tls` feature.
+    Rustls,
+}
+
+#[derive(Clone, Debug)]
 pub struct Config {
     pub rest_api_endpoint: String,
     pub ws_endpoint: String,
@@ -6,7 +22,36 @@ pub struct Config {
     pub futures_rest_api_endpoint: String,
     pub futures_ws_endpoint: String,
 
+    /// Archives of historical market data, read by the `historical` module.
+    pub data_endpoint: String,
+
     pub recv_window: u64,
+
+    /// Send the signed parameters of POST and DELETE requests in the body instead of the URL.
+    pub signed_payload_in_body: bool,
+
+    /// Signs requests in place of the HMAC secret key, e.g. for Ed25519 or RSA API keys.
+    pub signer: Option<Arc<dyn Signer>>,
+
+    pub connect_timeout: Option<Duration>,
+    /// Whole request timeout, from connecting until the response body is read.
+    pub timeout: Option<Duration>,
+    /// Proxy URL: `http://`, `https://` or, with the `socks` feature, `socks5://`.
+    pub proxy: Option<String>,
+    pub pool_max_idle_per_host: Option<usize>,
+    pub tcp_nodelay: bool,
+    pub user_agent: String,
+    pub tls_backend: TlsBackend,
+
+    /// Pre-built client used as is; the HTTP options above are then ignored.
+    pub http_client: Option<reqwest::blocking::Client>,
+
+    /// Sends the REST requests in place of reqwest, e.g. to record or replay them.
+    /// The HTTP options above and `http_client` are then ignored.
+    pub transport: Option<Arc<dyn Transport>>,
+
+    /// Called around every REST request, in order.
+    pub middlewares: Vec<Arc<dyn Middleware>>,
 }
 
 impl Config {
@@ -18,7 +63,25 @@ impl Config {
             futures_rest_api_endpoint: "https://fapi.binance.com".into(),
             futures_ws_endpoint: "wss://fstream.binance.com".into(),
 
+            data_endpoint: "https://data.binance.vision".into(),
+
             recv_window: 5000,
+
+            signed_payload_in_body: false,
+            signer: None,
+
+            connect_timeout: Some(Duration::from_secs(10)),
+            timeout: Some(Duration::from_secs(30)),
+            proxy: None,
+            pool_max_idle_per_host: None,
+            tcp_nodelay: true,
+            user_agent: "binance-rs".into(),
+            tls_backend: TlsBackend::Default,
+
+            http_client: None,
+            transport: None,
+
+            middlewares: Vec::new(),
         }
     }
 
@@ -49,8 +112,108 @@ impl Config {
         self
     }
 
+    pub fn set_data_endpoint<T: Into<String>>(mut self, data_endpoint: T) -> Self {
+        self.data_endpoint = data_endpoint.into();
+        self
+    }
+
     pub fn set_recv_window(mut self, recv_window: u64) -> Self {
         self.recv_window = recv_window;
         self
     }
+
+    pub fn set_signed_payload_in_body(mut self, signed_payload_in_body: bool) -> Self {
+        self.signed_payload_in_body = signed_payload_in_body;
+        self
+    }
+
+    pub fn set_signer<S: Signer + 'static>(mut self, signer: S) -> Self {
+        self.signer = Some(Arc::new(signer));
+        self
+    }
+
+    pub fn set_connect_timeout<T: Into<Option<Duration>>>(mut self, connect_timeout: T) -> Self {
+        self.connect_timeout = connect_timeout.into();
+        self
+    }
+
+    pub fn set_timeout<T: Into<Option<Duration>>>(mut self, timeout: T) -> Self {
+        self.timeout = timeout.into();
+        self
+    }
+
+    pub fn set_proxy<T: Into<String>>(mut self, proxy: T) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    pub fn set_pool_max_idle_per_host(mut self, pool_max_idle_per_host: usize) -> Self {
+        self.pool_max_idle_per_host = Some(pool_max_idle_per_host);
+        self
+    }
+
+    pub fn set_tcp_nodelay(mut self, tcp_nodelay: bool) -> Self {
+        self.tcp_nodelay = tcp_nodelay;
+        self
+    }
+
+    pub fn set_user_agent<T: Into<String>>(mut self, user_agent: T) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    pub fn set_tls_backend(mut self, tls_backend: TlsBackend) -> Self {
+        self.tls_backend = tls_backend;
+        self
+    }
+
+    pub fn set_http_client(mut self, http_client: reqwest::blocking::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    pub fn set_transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    pub fn add_middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+}
+
+impl PartialEq for Config {
+    fn eq(&self, other: &Config) -> bool {
+        let same_signer = match (&self.signer, &other.signer) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        let same_transport = match (&self.transport, &other.transport) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        self.rest_api_endpoint == other.rest_api_endpoint
+            && self.ws_endpoint == other.ws_endpoint
+            && self.futures_rest_api_endpoint == other.futures_rest_api_endpoint
+            && self.futures_ws_endpoint == other.futures_ws_endpoint
+            && self.data_endpoint == other.data_endpoint
+            && self.recv_window == other.recv_window
+            && self.signed_payload_in_body == other.signed_payload_in_body
+            && same_signer
+            && self.connect_timeout == other.connect_timeout
+            && self.timeout == other.timeout
+            && self.proxy == other.proxy
+            && self.pool_max_idle_per_host == other.pool_max_idle_per_host
+            && self.tcp_nodelay == other.tcp_nodelay
+            && self.user_agent == other.user_agent
+            && self.tls_backend == other.tls_backend
+            // reqwest clients can't be compared, only their presence is.
+            && self.http_client.is_some() == other.http_client.is_some()
+            && same_transport
+            && self.middlewares.len() == other.middlewares.len()
+            && self.middlewares.iter().zip(&other.middlewares).all(|(a, b)| Arc::ptr_eq(a, b))
+    }
 }
@@ -0,0 +1,164 @@
+//! API keys and where to load them from.
+//!
+//! Keys are held in [`Secret`]s, which are wiped from memory on drop and never
+//! printed through `Debug`. A [`CredentialProvider`] loads them from the
+//! environment, from files, or from any closure returning [`Credentials`]:
+//!
+//!```no_run
+//! use binance::api::Binance;
+//! use binance::account::Account;
+//! use binance::config::Config;
+//! use binance::credentials::EnvCredentials;
+//!
+//! fn main() {
+//!     let provider = EnvCredentials::new("BINANCE_API_KEY", "BINANCE_SECRET_KEY");
+//!     let account: Account = Binance::new_with_provider(&provider, &Config::default()).unwrap();
+//! }
+//! ```
+
+use crate::errors::*;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+/// A key that is zeroized when the last clone is dropped.
+#[derive(Clone)]
+pub struct Secret(Arc<Zeroizing<String>>);
+
+impl Secret {
+    pub fn new<S: Into<String>>(value: S) -> Self {
+        Secret(Arc::new(Zeroizing::new(value.into())))
+    }
+
+    /// The key in clear text, only meant to be used for signing and headers.
+    pub fn expose(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Secret::new(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Secret::new(value)
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(***)")
+    }
+}
+
+/// API key and secret key of an account, both optional for public endpoints.
+#[derive(Clone, Debug, Default)]
+pub struct Credentials {
+    pub api_key: Option<Secret>,
+    pub secret_key: Option<Secret>,
+}
+
+impl Credentials {
+    pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Credentials {
+            api_key: api_key.map(Secret::new),
+            secret_key: secret_key.map(Secret::new),
+        }
+    }
+}
+
+/// Source of the credentials handed to the API clients.
+pub trait CredentialProvider {
+    fn credentials(&self) -> Result<Credentials>;
+}
+
+impl CredentialProvider for Credentials {
+    fn credentials(&self) -> Result<Credentials> {
+        Ok(self.clone())
+    }
+}
+
+/// Any closure returning credentials, e.g. a lookup in a secrets vault.
+impl<F> CredentialProvider for F
+where
+    F: Fn() -> Result<Credentials>,
+{
+    fn credentials(&self) -> Result<Credentials> {
+        self()
+    }
+}
+
+/// Reads both keys from environment variables.
+#[derive(Clone, Debug)]
+pub struct EnvCredentials {
+    api_key_var: String,
+    secret_key_var: String,
+}
+
+impl EnvCredentials {
+    pub fn new<S1, S2>(api_key_var: S1, secret_key_var: S2) -> Self
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        EnvCredentials {
+            api_key_var: api_key_var.into(),
+            secret_key_var: secret_key_var.into(),
+        }
+    }
+}
+
+impl CredentialProvider for EnvCredentials {
+    fn credentials(&self) -> Result<Credentials> {
+        let read = |var: &str| -> Result<Secret> {
+            std::env::var(var)
+                .map(Secret::new)
+                .map_err(|e| format!("Failed to read {}: {}", var, e).into())
+        };
+        Ok(Credentials {
+            api_key: Some(read(&self.api_key_var)?),
+            secret_key: Some(read(&self.secret_key_var)?),
+        })
+    }
+}
+
+/// Reads both keys from files, surrounding whitespace is ignored.
+///
+/// The secret key is used as an HMAC key. Ed25519 and RSA API keys sign with a
+/// private key instead, configured through `Config::set_signer`.
+#[derive(Clone, Debug)]
+pub struct FileCredentials {
+    api_key_path: PathBuf,
+    secret_key_path: PathBuf,
+}
+
+impl FileCredentials {
+    pub fn new<P1, P2>(api_key_path: P1, secret_key_path: P2) -> Self
+    where
+        P1: Into<PathBuf>,
+        P2: Into<PathBuf>,
+    {
+        FileCredentials {
+            api_key_path: api_key_path.into(),
+            secret_key_path: secret_key_path.into(),
+        }
+    }
+}
+
+impl CredentialProvider for FileCredentials {
+    fn credentials(&self) -> Result<Credentials> {
+        let read = |path: &PathBuf| -> Result<Secret> {
+            let content = Zeroizing::new(fs::read_to_string(path)?);
+            Ok(Secret::new(content.trim()))
+        };
+        Ok(Credentials {
+            api_key: Some(read(&self.api_key_path)?),
+            secret_key: Some(read(&self.secret_key_path)?),
+        })
+    }
+}
@@ -1,6 +1,11 @@
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::time::Duration;
+use thiserror::Error;
+
+// Longest part of a response body kept in an error.
+const BODY_SNIPPET_LEN: usize = 512;
 
 #[derive(Debug, Deserialize)]
 pub struct BinanceContentError {
@@ -11,19 +16,108 @@ pub struct BinanceContentError {
     extra: HashMap<String, Value>,
 }
 
-error_chain! {
-    errors {
-        BinanceError(response: BinanceContentError)
-     }
-
-    foreign_links {
-        ReqError(reqwest::Error);
-        InvalidHeaderError(reqwest::header::InvalidHeaderValue);
-        IoError(std::io::Error);
-        ParseFloatError(std::num::ParseFloatError);
-        UrlParserError(url::ParseError);
-        Json(serde_json::Error);
-        Tungstenite(tungstenite::Error);
-        TimestampError(std::time::SystemTimeError);
+#[derive(Debug, Error)]
+pub enum BinanceError {
+    /// Non-success status without a Binance error payload.
+    #[error("Received response with status {status}: {body}")]
+    Http { status: u16, body: String },
+
+    /// Error reported by the exchange, see the Binance error codes.
+    #[error("Binance error {code}: {msg}")]
+    Api { code: i16, msg: String },
+
+    /// HTTP 429, or 418 once the IP is banned for ignoring 429s.
+    #[error("Rate limited, retry after {retry_after:?}")]
+    RateLimited { retry_after: Option<Duration> },
+
+    /// `body` holds the start of the payload that failed to decode, if any.
+    #[error("Failed to decode response: {source}")]
+    Decode {
+        source: serde_json::Error,
+        body: String,
+    },
+
+    #[error("Websocket error: {0}")]
+    Websocket(#[from] tungstenite::Error),
+
+    #[error("Invalid timestamp: {0}")]
+    Timestamp(#[from] std::time::SystemTimeError),
+
+    /// The request failed before a response came back, `reqwest::Error` being
+    /// the source.
+    #[error("Transport error")]
+    Transport(#[from] reqwest::Error),
+
+    #[error(transparent)]
+    InvalidHeader(#[from] reqwest::header::InvalidHeaderValue),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Url(#[from] url::ParseError),
+
+    #[error(transparent)]
+    ParseFloat(#[from] std::num::ParseFloatError),
+
+    #[cfg(feature = "historical")]
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+
+    #[cfg(feature = "historical")]
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+
+    /// Errors raised by the crate itself rather than by the transport or the
+    /// exchange, e.g. a symbol missing from the exchange info, an invalid key
+    /// or a malformed archive row. There is nothing to them but the message.
+    #[error("{0}")]
+    Msg(String),
+}
+
+pub type Error = BinanceError;
+
+pub type Result<T> = std::result::Result<T, BinanceError>;
+
+impl BinanceError {
+    pub(crate) fn decode(source: serde_json::Error, body: &str) -> Self {
+        BinanceError::Decode {
+            source,
+            body: snippet(body),
+        }
+    }
+}
+
+impl From<serde_json::Error> for BinanceError {
+    fn from(source: serde_json::Error) -> Self {
+        BinanceError::Decode {
+            source,
+            body: String::new(),
+        }
+    }
+}
+
+impl From<String> for BinanceError {
+    fn from(msg: String) -> Self {
+        BinanceError::Msg(msg)
     }
 }
+
+impl<'a> From<&'a str> for BinanceError {
+    fn from(msg: &'a str) -> Self {
+        BinanceError::Msg(msg.to_string())
+    }
+}
+
+pub(crate) fn snippet(body: &str) -> String {
+    match body.char_indices().nth(BODY_SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &body[..end]),
+        None => body.to_string(),
+    }
+}
+
+macro_rules! bail {
+    ($e:expr) => {
+        return Err($crate::errors::BinanceError::from($e))
+    };
+}
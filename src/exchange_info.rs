@@ -0,0 +1,242 @@
+//! `exchangeInfo` fetched once and indexed by symbol, base and quote asset.
+//!
+//! The spot endpoint weighs 20 and returns several MB, the cache only downloads
+//! it again once its time to live has passed or when `refresh` is called. One
+//! caller downloads it at a time, the others keep reading the expired copy
+//! meanwhile:
+//!
+//!```no_run
+//! use binance::api::Binance;
+//! use binance::exchange_info::ExchangeInfoCache;
+//! use binance::general::General;
+//! use binance::model::SymbolStatus;
+//! use std::time::Duration;
+//!
+//! fn main() {
+//!     let general: General = Binance::new(None, None).unwrap();
+//!     let cache = ExchangeInfoCache::new(general, Duration::from_secs(3600));
+//!
+//!     let symbol = cache.get_symbol("BTCUSDT").unwrap();
+//!     if symbol.status == SymbolStatus::Trading {
+//!         println!("{} is trading", symbol.symbol);
+//!     }
+//!     let usdt_pairs = cache.symbols_by_quote_asset("USDT").unwrap();
+//!     println!("{} USDT pairs", usdt_pairs.len());
+//! }
+//! ```
+
+use crate::errors::*;
+use crate::futures::general::FuturesGeneral;
+use crate::general::General;
+use crate::model::SymbolStatus;
+use crate::util::lock;
+use crate::{futures, model};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, TryLockError};
+use std::time::{Duration, Instant};
+
+/// Fields of a symbol the cache indexes on.
+pub trait SymbolInfo {
+    fn symbol(&self) -> &str;
+    fn base_asset(&self) -> &str;
+    fn quote_asset(&self) -> &str;
+    fn status(&self) -> &SymbolStatus;
+}
+
+impl SymbolInfo for model::Symbol {
+    fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    fn base_asset(&self) -> &str {
+        &self.base_asset
+    }
+
+    fn quote_asset(&self) -> &str {
+        &self.quote_asset
+    }
+
+    fn status(&self) -> &SymbolStatus {
+        &self.status
+    }
+}
+
+impl SymbolInfo for futures::model::Symbol {
+    fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    fn base_asset(&self) -> &str {
+        &self.base_asset
+    }
+
+    fn quote_asset(&self) -> &str {
+        &self.quote_asset
+    }
+
+    fn status(&self) -> &SymbolStatus {
+        &self.status
+    }
+}
+
+/// Where the symbols come from, `General` for spot and `FuturesGeneral` for futures.
+pub trait ExchangeInfoSource {
+    type Symbol: SymbolInfo;
+
+    fn fetch_symbols(&self) -> Result<Vec<Self::Symbol>>;
+}
+
+impl ExchangeInfoSource for General {
+    type Symbol = model::Symbol;
+
+    fn fetch_symbols(&self) -> Result<Vec<Self::Symbol>> {
+        Ok(self.exchange_info()?.symbols)
+    }
+}
+
+impl ExchangeInfoSource for FuturesGeneral {
+    type Symbol = futures::model::Symbol;
+
+    fn fetch_symbols(&self) -> Result<Vec<Self::Symbol>> {
+        Ok(self.exchange_info()?.symbols)
+    }
+}
+
+struct Snapshot<T> {
+    fetched_at: Instant,
+    by_symbol: HashMap<String, Arc<T>>,
+    by_base_asset: HashMap<String, Vec<Arc<T>>>,
+    by_quote_asset: HashMap<String, Vec<Arc<T>>>,
+}
+
+impl<T: SymbolInfo> Snapshot<T> {
+    fn new(symbols: Vec<T>) -> Self {
+        let mut snapshot = Snapshot {
+            fetched_at: Instant::now(),
+            by_symbol: HashMap::with_capacity(symbols.len()),
+            by_base_asset: HashMap::new(),
+            by_quote_asset: HashMap::new(),
+        };
+        for symbol in symbols {
+            let symbol = Arc::new(symbol);
+            snapshot
+                .by_base_asset
+                .entry(symbol.base_asset().to_uppercase())
+                .or_default()
+                .push(symbol.clone());
+            snapshot
+                .by_quote_asset
+                .entry(symbol.quote_asset().to_uppercase())
+                .or_default()
+                .push(symbol.clone());
+            snapshot.by_symbol.insert(symbol.symbol().to_uppercase(), symbol);
+        }
+        snapshot
+    }
+}
+
+/// Cached symbols of an exchange, safe to share between threads.
+pub struct ExchangeInfoCache<S: ExchangeInfoSource> {
+    source: S,
+    ttl: Duration,
+    snapshot: Mutex<Option<Arc<Snapshot<S::Symbol>>>>,
+    // Held while downloading.
+    fetching: Mutex<()>,
+}
+
+impl<S: ExchangeInfoSource> ExchangeInfoCache<S> {
+    /// Nothing is fetched until the first lookup.
+    pub fn new(source: S, ttl: Duration) -> Self {
+        ExchangeInfoCache {
+            source,
+            ttl,
+            snapshot: Mutex::new(None),
+            fetching: Mutex::new(()),
+        }
+    }
+
+    /// Downloads the exchange info now, whatever its age.
+    pub fn refresh(&self) -> Result<()> {
+        let _fetching = lock(&self.fetching);
+        self.fetch().map(|_| ())
+    }
+
+    // Symbol by name, e.g. BTCUSDT
+    pub fn get_symbol<T>(&self, symbol: T) -> Result<Arc<S::Symbol>>
+    where
+        T: Into<String>,
+    {
+        let upper_symbol = symbol.into().to_uppercase();
+        match self.snapshot()?.by_symbol.get(&upper_symbol) {
+            Some(symbol) => Ok(symbol.clone()),
+            None => bail!("Symbol not found"),
+        }
+    }
+
+    // Symbols traded with `asset` as base asset, e.g. BTC for BTCUSDT
+    pub fn symbols_by_base_asset<T>(&self, asset: T) -> Result<Vec<Arc<S::Symbol>>>
+    where
+        T: Into<String>,
+    {
+        let asset = asset.into().to_uppercase();
+        Ok(self.snapshot()?.by_base_asset.get(&asset).cloned().unwrap_or_default())
+    }
+
+    // Symbols traded with `asset` as quote asset, e.g. USDT for BTCUSDT
+    pub fn symbols_by_quote_asset<T>(&self, asset: T) -> Result<Vec<Arc<S::Symbol>>>
+    where
+        T: Into<String>,
+    {
+        let asset = asset.into().to_uppercase();
+        Ok(self.snapshot()?.by_quote_asset.get(&asset).cloned().unwrap_or_default())
+    }
+
+    // Symbols with the given status, e.g. all symbols currently trading
+    pub fn symbols_with_status(&self, status: &SymbolStatus) -> Result<Vec<Arc<S::Symbol>>> {
+        Ok(self
+            .snapshot()?
+            .by_symbol
+            .values()
+            .filter(|symbol| symbol.status() == status)
+            .cloned()
+            .collect())
+    }
+
+    // Every symbol of the exchange
+    pub fn symbols(&self) -> Result<Vec<Arc<S::Symbol>>> {
+        Ok(self.snapshot()?.by_symbol.values().cloned().collect())
+    }
+
+    fn snapshot(&self) -> Result<Arc<Snapshot<S::Symbol>>> {
+        let current = lock(&self.snapshot).clone();
+        if let Some(snapshot) = current.as_ref().filter(|snapshot| self.is_fresh(snapshot)) {
+            return Ok(snapshot.clone());
+        }
+        // While another caller downloads it, the expired copy is served, or
+        // waited for when there is none yet.
+        let _fetching = match self.fetching.try_lock() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => match current {
+                Some(stale) => return Ok(stale),
+                None => lock(&self.fetching),
+            },
+        };
+        // The previous download may have completed in the meantime.
+        let current = lock(&self.snapshot).clone();
+        match current.filter(|snapshot| self.is_fresh(snapshot)) {
+            Some(snapshot) => Ok(snapshot),
+            None => self.fetch(),
+        }
+    }
+
+    fn is_fresh(&self, snapshot: &Snapshot<S::Symbol>) -> bool {
+        snapshot.fetched_at.elapsed() < self.ttl
+    }
+
+    fn fetch(&self) -> Result<Arc<Snapshot<S::Symbol>>> {
+        let snapshot = Arc::new(Snapshot::new(self.source.fetch_symbols()?));
+        *lock(&self.snapshot) = Some(snapshot.clone());
+        Ok(snapshot)
+    }
+}
@@ -1,12 +1,11 @@
-use std::collections::BTreeMap;
-
 use crate::util::*;
 use crate::errors::*;
 use crate::client::Client;
 use crate::api::{API, Futures};
-use crate::model::Empty;
-use crate::account::{OrderSide, TimeInForce};
-use super::model::{ChangeLeverageResponse, Transaction};
+use crate::model::{Amount, Empty};
+use super::model::{AccountBalance, ChangeLeverageResponse, Order, PositionRisk, Transaction};
+
+pub use crate::model::{OrderSide, OrderType, TimeInForce};
 
 #[derive(Clone)]
 pub struct FuturesAccount {
@@ -51,30 +50,6 @@ impl From<PositionSide> for String {
     }
 }
 
-pub enum OrderType {
-    Limit,
-    Market,
-    Stop,
-    StopMarket,
-    TakeProfit,
-    TakeProfitMarket,
-    TrailingStopMarket,
-}
-
-impl From<OrderType> for String {
-    fn from(item: OrderType) -> Self {
-        match item {
-            OrderType::Limit => String::from("LIMIT"),
-            OrderType::Market => String::from("MARKET"),
-            OrderType::Stop => String::from("STOP"),
-            OrderType::StopMarket => String::from("STOP_MARKET"),
-            OrderType::TakeProfit => String::from("TAKE_PROFIT"),
-            OrderType::TakeProfitMarket => String::from("TAKE_PROFIT_MARKET"),
-            OrderType::TrailingStopMarket => String::from("TRAILING_STOP_MARKET"),
-        }
-    }
-}
-
 pub enum WorkingType {
     MarkPrice,
     ContractPrice,
@@ -95,20 +70,20 @@ struct OrderRequest {
     pub position_side: Option<PositionSide>,
     pub order_type: OrderType,
     pub time_in_force: Option<TimeInForce>,
-    pub qty: Option<f64>,
+    pub qty: Option<Amount>,
     pub reduce_only: Option<bool>,
-    pub price: Option<f64>,
-    pub stop_price: Option<f64>,
+    pub price: Option<Amount>,
+    pub stop_price: Option<Amount>,
     pub close_position: Option<bool>,
-    pub activation_price: Option<f64>,
-    pub callback_rate: Option<f64>,
+    pub activation_price: Option<Amount>,
+    pub callback_rate: Option<Amount>,
     pub working_type: Option<WorkingType>,
     pub price_protect: Option<f64>,
 }
 
 impl FuturesAccount {
     pub fn limit_buy(
-        &self, symbol: impl Into<String>, qty: impl Into<f64>, price: f64,
+        &self, symbol: impl Into<String>, qty: impl Into<Amount>, price: impl Into<Amount>,
         time_in_force: TimeInForce,
     ) -> Result<Transaction> {
         let buy = OrderRequest {
@@ -119,7 +94,7 @@ impl FuturesAccount {
             time_in_force: Some(time_in_force),
             qty: Some(qty.into()),
             reduce_only: None,
-            price: Some(price),
+            price: Some(price.into()),
             stop_price: None,
             close_position: None,
             activation_price: None,
@@ -136,7 +111,7 @@ impl FuturesAccount {
     }
 
     pub fn stop_market_buy(
-        &self, symbol: impl Into<String>, qty: impl Into<f64>, stop_price: f64,
+        &self, symbol: impl Into<String>, qty: impl Into<Amount>, stop_price: impl Into<Amount>,
     ) -> Result<Transaction> {
         let buy = OrderRequest {
             symbol: symbol.into(),
@@ -147,7 +122,7 @@ impl FuturesAccount {
             qty: Some(qty.into()),
             reduce_only: None,
             price: None,
-            stop_price: Some(stop_price),
+            stop_price: Some(stop_price.into()),
             close_position: None,
             activation_price: None,
             callback_rate: None,
@@ -163,7 +138,7 @@ impl FuturesAccount {
     }
 
     pub fn reduce_buy(
-        &self, symbol: impl Into<String>, qty: impl Into<f64>, price: f64,
+        &self, symbol: impl Into<String>, qty: impl Into<Amount>, price: impl Into<Amount>,
         time_in_force: TimeInForce,
     ) -> Result<Transaction> {
         let buy = OrderRequest {
@@ -174,7 +149,7 @@ impl FuturesAccount {
             time_in_force: Some(time_in_force),
             qty: Some(qty.into()),
             reduce_only: Some(true),
-            price: Some(price),
+            price: Some(price.into()),
             stop_price: None,
             close_position: None,
             activation_price: None,
@@ -191,7 +166,7 @@ impl FuturesAccount {
     }
 
     pub fn limit_sell(
-        &self, symbol: impl Into<String>, qty: impl Into<f64>, price: f64,
+        &self, symbol: impl Into<String>, qty: impl Into<Amount>, price: impl Into<Amount>,
         time_in_force: TimeInForce,
     ) -> Result<Transaction> {
         let sell = OrderRequest {
@@ -202,7 +177,7 @@ impl FuturesAccount {
             time_in_force: Some(time_in_force),
             qty: Some(qty.into()),
             reduce_only: None,
-            price: Some(price),
+            price: Some(price.into()),
             stop_price: None,
             close_position: None,
             activation_price: None,
@@ -219,7 +194,7 @@ impl FuturesAccount {
     }
 
     pub fn stop_market_sell(
-        &self, symbol: impl Into<String>, qty: impl Into<f64>, stop_price: f64,
+        &self, symbol: impl Into<String>, qty: impl Into<Amount>, stop_price: impl Into<Amount>,
     ) -> Result<Transaction> {
         let sell = OrderRequest {
             symbol: symbol.into(),
@@ -230,7 +205,7 @@ impl FuturesAccount {
             qty: Some(qty.into()),
             reduce_only: None,
             price: None,
-            stop_price: Some(stop_price),
+            stop_price: Some(stop_price.into()),
             close_position: None,
             activation_price: None,
             callback_rate: None,
@@ -246,7 +221,7 @@ impl FuturesAccount {
     }
 
     pub fn reduce_sell(
-        &self, symbol: impl Into<String>, qty: impl Into<f64>, price: f64,
+        &self, symbol: impl Into<String>, qty: impl Into<Amount>, price: impl Into<Amount>,
         time_in_force: TimeInForce,
     ) -> Result<Transaction> {
         let sell = OrderRequest {
@@ -257,7 +232,7 @@ impl FuturesAccount {
             time_in_force: Some(time_in_force),
             qty: Some(qty.into()),
             reduce_only: Some(true),
-            price: Some(price),
+            price: Some(price.into()),
             stop_price: None,
             close_position: None,
             activation_price: None,
@@ -279,7 +254,7 @@ impl FuturesAccount {
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let buy = OrderRequest {
             symbol: symbol.into(),
@@ -309,7 +284,7 @@ impl FuturesAccount {
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
@@ -333,64 +308,78 @@ impl FuturesAccount {
             .post_signed(API::Futures(Futures::Order), request)
     }
 
-    fn build_order(&self, order: OrderRequest) -> BTreeMap<String, String> {
-        let mut parameters = BTreeMap::new();
-        parameters.insert("symbol".into(), order.symbol);
-        parameters.insert("side".into(), order.side.into());
-        parameters.insert("type".into(), order.order_type.into());
+    fn build_order(&self, order: OrderRequest) -> RequestParams {
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", order.symbol);
+        parameters.insert("side", order.side);
+        parameters.insert("type", order.order_type);
 
         if let Some(position_side) = order.position_side {
-            parameters.insert("positionSide".into(), position_side.into());
+            parameters.insert("positionSide", position_side);
         }
         if let Some(time_in_force) = order.time_in_force {
-            parameters.insert("timeInForce".into(), time_in_force.into());
+            parameters.insert("timeInForce", time_in_force);
         }
         if let Some(qty) = order.qty {
-            parameters.insert("quantity".into(), qty.to_string());
+            parameters.insert("quantity", qty.to_string());
         }
         if let Some(reduce_only) = order.reduce_only {
-            parameters.insert("reduceOnly".into(), reduce_only.to_string().to_uppercase());
+            parameters.insert("reduceOnly", reduce_only.to_string().to_uppercase());
         }
         if let Some(price) = order.price {
-            parameters.insert("price".into(), price.to_string());
+            parameters.insert("price", price.to_string());
         }
         if let Some(stop_price) = order.stop_price {
-            parameters.insert("stopPrice".into(), stop_price.to_string());
+            parameters.insert("stopPrice", stop_price.to_string());
         }
         if let Some(close_position) = order.close_position {
-            parameters.insert(
-                "closePosition".into(),
-                close_position.to_string().to_uppercase(),
-            );
+            parameters.insert("closePosition", close_position.to_string().to_uppercase());
         }
         if let Some(activation_price) = order.activation_price {
-            parameters.insert("activationPrice".into(), activation_price.to_string());
+            parameters.insert("activationPrice", activation_price.to_string());
         }
         if let Some(callback_rate) = order.callback_rate {
-            parameters.insert("callbackRate".into(), callback_rate.to_string());
+            parameters.insert("callbackRate", callback_rate.to_string());
         }
         if let Some(working_type) = order.working_type {
-            parameters.insert("workingType".into(), working_type.into());
+            parameters.insert("workingType", working_type);
         }
         if let Some(price_protect) = order.price_protect {
-            parameters.insert(
-                "priceProtect".into(),
-                price_protect.to_string().to_uppercase(),
-            );
+            parameters.insert("priceProtect", price_protect.to_string().to_uppercase());
         }
 
         parameters
     }
 
+    /// Open orders of every symbol.
+    pub fn get_all_open_orders(&self) -> Result<Vec<Order>> {
+        let request = build_signed_request(RequestParams::new(), self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::OpenOrders), Some(request))
+    }
+
+    /// Position of every symbol, in both sides in hedge mode.
+    pub fn position_information(&self) -> Result<Vec<PositionRisk>> {
+        let request = build_signed_request(RequestParams::new(), self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::PositionRisk), Some(request))
+    }
+
+    pub fn account_balance(&self) -> Result<Vec<AccountBalance>> {
+        let request = build_signed_request(RequestParams::new(), self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::Balance), Some(request))
+    }
+
     pub fn change_initial_leverage<S>(
         &self, symbol: S, leverage: u8,
     ) -> Result<ChangeLeverageResponse>
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("leverage".into(), leverage.to_string());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
+        parameters.insert("leverage", leverage.to_string());
 
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client
@@ -398,9 +387,9 @@ impl FuturesAccount {
     }
 
     pub fn change_position_mode(&self, dual_side_position: bool) -> Result<()> {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        let mut parameters = RequestParams::new();
         let dual_side = if dual_side_position { "true" } else { "false" };
-        parameters.insert("dualSidePosition".into(), dual_side.into());
+        parameters.insert("dualSidePosition", dual_side);
 
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client
@@ -413,27 +402,16 @@ impl FuturesAccount {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        let list = format!(
-            "[{}]",
-            order_ids
-                .iter()
-                .map(u64::to_string)
-                .fold(String::new(), |mut a, b| {
-                    if !a.is_empty() {
-                        a += ",";
-                    }
-                    a + &b
-                })
-        );
-        parameters.insert("orderIdList".into(), list);
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
+        parameters.insert_json("orderIdList", order_ids)?;
 
         let request = self
             .request_builder
             .build_signed_request(parameters, self.recv_window)?;
         self.client
-            .delete_signed(API::Futures(Futures::BatchOrders), Some(request))
+            .delete_signed::<Vec<Empty>>(API::Futures(Futures::BatchOrders), Some(request))
+            .map(|_| ())
     }
 
     /// Cancels the orders
@@ -441,23 +419,24 @@ impl FuturesAccount {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("orderId".into(), order_id.to_string());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
+        parameters.insert("orderId", order_id.to_string());
 
         let request = self
             .request_builder
             .build_signed_request(parameters, self.recv_window)?;
         self.client
-            .delete_signed(API::Futures(Futures::Order), Some(request))
+            .delete_signed::<Empty>(API::Futures(Futures::Order), Some(request))
+            .map(|_| ())
     }
 
     pub fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<()>
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client
             .delete_signed::<Empty>(API::Futures(Futures::AllOpenOrders), Some(request))
@@ -1,6 +1,8 @@
 use crate::futures::model::*;
+use crate::model::Empty;
 use crate::client::*;
 use crate::errors::*;
+use crate::rules::SymbolRules;
 use crate::api::API;
 use crate::api::Futures;
 
@@ -12,7 +14,7 @@ pub struct FuturesGeneral {
 impl FuturesGeneral {
     // Test connectivity
     pub fn ping(&self) -> Result<String> {
-        self.client.get(API::Futures(Futures::Ping), None)?;
+        self.client.get::<Empty>(API::Futures(Futures::Ping), None)?;
         Ok("pong".into())
     }
 
@@ -45,4 +47,13 @@ impl FuturesGeneral {
             Err(e) => Err(e),
         }
     }
+
+    // Get the trading rules of a symbol, to check orders before sending them
+    pub fn get_symbol_rules<S>(&self, symbol: S) -> Result<SymbolRules>
+    where
+        S: Into<String>,
+    {
+        let symbol = self.get_symbol_info(symbol)?;
+        Ok(SymbolRules::from(&symbol))
+    }
 }
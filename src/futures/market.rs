@@ -24,8 +24,8 @@ use crate::util::*;
 use crate::futures::model::*;
 use crate::client::*;
 use crate::errors::*;
-use std::collections::BTreeMap;
-use serde_json::Value;
+use crate::market::{KlineRange, TradeIdRange};
+use crate::model::KlineRow;
 use crate::api::API;
 use crate::api::Futures;
 
@@ -46,9 +46,9 @@ impl FuturesMarket {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        let mut parameters = RequestParams::new();
 
-        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("symbol", symbol);
         let request = build_request(parameters);
 
         self.client.get(API::Futures(Futures::Depth), Some(request))
@@ -58,8 +58,8 @@ impl FuturesMarket {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
         let request = build_request(parameters);
         self.client.get(API::Futures(Futures::Trades), Some(request))
     }
@@ -73,16 +73,16 @@ impl FuturesMarket {
         S2: Into<Option<u64>>,
         S3: Into<Option<u16>>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        let mut parameters = RequestParams::new();
 
-        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("symbol", symbol);
 
         // Add three optional parameters
         if let Some(lt) = limit.into() {
-            parameters.insert("limit".into(), format!("{}", lt));
+            parameters.insert("limit", format!("{}", lt));
         }
         if let Some(fi) = from_id.into() {
-            parameters.insert("fromId".into(), format!("{}", fi));
+            parameters.insert("fromId", format!("{}", fi));
         }
 
         let request = build_signed_request(parameters, self.recv_window)?;
@@ -100,22 +100,22 @@ impl FuturesMarket {
         S4: Into<Option<u64>>,
         S5: Into<Option<u16>>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        let mut parameters = RequestParams::new();
 
-        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("symbol", symbol);
 
         // Add three optional parameters
         if let Some(lt) = limit.into() {
-            parameters.insert("limit".into(), format!("{}", lt));
+            parameters.insert("limit", format!("{}", lt));
         }
         if let Some(st) = start_time.into() {
-            parameters.insert("startTime".into(), format!("{}", st));
+            parameters.insert("startTime", format!("{}", st));
         }
         if let Some(et) = end_time.into() {
-            parameters.insert("endTime".into(), format!("{}", et));
+            parameters.insert("endTime", format!("{}", et));
         }
         if let Some(fi) = from_id.into() {
-            parameters.insert("fromId".into(), format!("{}", fi));
+            parameters.insert("fromId", format!("{}", fi));
         }
 
         let request = build_request(parameters);
@@ -135,56 +135,60 @@ impl FuturesMarket {
         S4: Into<Option<u64>>,
         S5: Into<Option<u64>>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        let mut parameters = RequestParams::new();
 
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("interval".into(), interval.into());
+        parameters.insert("symbol", symbol);
+        parameters.insert("interval", interval);
 
         // Add three optional parameters
         if let Some(lt) = limit.into() {
-            parameters.insert("limit".into(), format!("{}", lt));
+            parameters.insert("limit", format!("{}", lt));
         }
         if let Some(st) = start_time.into() {
-            parameters.insert("startTime".into(), format!("{}", st));
+            parameters.insert("startTime", format!("{}", st));
         }
         if let Some(et) = end_time.into() {
-            parameters.insert("endTime".into(), format!("{}", et));
+            parameters.insert("endTime", format!("{}", et));
         }
 
         let request = build_request(parameters);
 
-        let data: Vec<Vec<Value>> = self
+        let data: Vec<KlineRow> = self
             .client
             .get(API::Futures(Futures::Klines), Some(request))?;
 
         let klines = KlineSummaries::AllKlineSummaries(
-            data.iter()
-                .map(|row| KlineSummary {
-                    open_time: to_i64(&row[0]),
-                    open: to_f64(&row[1]),
-                    high: to_f64(&row[2]),
-                    low: to_f64(&row[3]),
-                    close: to_f64(&row[4]),
-                    volume: to_f64(&row[5]),
-                    close_time: to_i64(&row[6]),
-                    quote_asset_volume: to_f64(&row[7]),
-                    number_of_trades: to_i64(&row[8]),
-                    taker_buy_base_asset_volume: to_f64(&row[9]),
-                    taker_buy_quote_asset_volume: to_f64(&row[10]),
-                })
-                .collect(),
+            data.into_iter().map(KlineSummary::from).collect(),
         );
         Ok(klines)
     }
 
+    // Klines with an open time in [start_time, end_time], fetched page by page as the
+    // iterator is consumed.
+    pub fn klines_range<S>(
+        &self, symbol: S, interval: KlineInterval, start_time: u64, end_time: u64,
+    ) -> KlineRange
+    where
+        S: Into<String>,
+    {
+        KlineRange::new(
+            self.client.clone(),
+            API::Futures(Futures::Klines),
+            symbol.into(),
+            interval,
+            start_time,
+            end_time,
+        )
+    }
+
     // 24hr ticker price change statistics
     pub fn get_24h_price_stats<S>(&self, symbol: S) -> Result<PriceStats>
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        let mut parameters = RequestParams::new();
 
-        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("symbol", symbol);
         let request = build_request(parameters);
 
         self.client.get(API::Futures(Futures::Ticker24hr), Some(request))
@@ -195,9 +199,9 @@ impl FuturesMarket {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        let mut parameters = RequestParams::new();
 
-        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("symbol", symbol);
         let request = build_request(parameters);
 
         self.client.get(API::Futures(Futures::TickerPrice), Some(request))
@@ -214,8 +218,8 @@ impl FuturesMarket {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
         let request = build_request(parameters);
         self.client.get(API::Futures(Futures::BookTicker), Some(request))
     }
@@ -228,12 +232,46 @@ impl FuturesMarket {
         self.client.get(API::Futures(Futures::AllForceOrders), None)
     }
 
+    // Aggregate trades from `from_id` on, up to the latest one, fetched page by page
+    // as the iterator is consumed.
+    pub fn agg_trades_from<S>(&self, symbol: S, from_id: u64) -> TradeIdRange<AggTrade>
+    where
+        S: Into<String>,
+    {
+        TradeIdRange::new(
+            self.client.clone(),
+            API::Futures(Futures::AggTrades),
+            symbol.into(),
+            from_id,
+            1000,
+            false,
+            |trade: &AggTrade| trade.agg_id,
+        )
+    }
+
+    // Trades from `from_id` on, up to the latest one, fetched page by page as the
+    // iterator is consumed. Futures pages hold at most 500 trades.
+    pub fn historical_trades_from<S>(&self, symbol: S, from_id: u64) -> TradeIdRange<Trade>
+    where
+        S: Into<String>,
+    {
+        TradeIdRange::new(
+            self.client.clone(),
+            API::Futures(Futures::HistoricalTrades),
+            symbol.into(),
+            from_id,
+            500,
+            true,
+            |trade: &Trade| trade.id,
+        )
+    }
+
     pub fn open_interest<S>(&self, symbol: S) -> Result<OpenInterest>
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
         let request = build_request(parameters);
         self.client.get(API::Futures(Futures::OpenInterest), Some(request))
     }
@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
-use crate::model::{string_or_float, string_or_float_opt};
+use crate::model::{string_or_float, string_or_float_opt, Amount};
 
 pub use crate::model::{
-    Asks, Bids, BookTickers, Filters, KlineSummaries, KlineSummary, RateLimit, ServerTime,
-    SymbolPrice, Tickers,
+    AggTrade, AggTrades, Asks, Bids, BookTickers, ExecutionType, Filters, KlineInterval,
+    KlineSummaries, KlineSummary, OrderSide, OrderStatus, OrderType, RateLimit, ServerTime,
+    SymbolPrice, SymbolStatus, Tickers, TimeInForce, Trade, Trades,
 };
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -20,9 +21,11 @@ pub struct ExchangeInformation {
 #[serde(rename_all = "camelCase")]
 pub struct Symbol {
     pub symbol: String,
-    pub status: String,
-    pub maint_margin_percent: String,
-    pub required_margin_percent: String,
+    pub status: SymbolStatus,
+    #[serde(with = "string_or_float")]
+    pub maint_margin_percent: Amount,
+    #[serde(with = "string_or_float")]
+    pub required_margin_percent: Amount,
     pub base_asset: String,
     pub quote_asset: String,
     pub price_precision: u16,
@@ -52,73 +55,31 @@ pub struct OrderBook {
 #[serde(rename_all = "camelCase")]
 pub struct PriceStats {
     pub symbol: String,
-    pub price_change: String,
-    pub price_change_percent: String,
-    pub weighted_avg_price: String,
     #[serde(with = "string_or_float")]
-    pub last_price: f64,
+    pub price_change: Amount,
     #[serde(with = "string_or_float")]
-    pub open_price: f64,
+    pub price_change_percent: Amount,
     #[serde(with = "string_or_float")]
-    pub high_price: f64,
+    pub weighted_avg_price: Amount,
     #[serde(with = "string_or_float")]
-    pub low_price: f64,
+    pub last_price: Amount,
     #[serde(with = "string_or_float")]
-    pub volume: f64,
+    pub open_price: Amount,
     #[serde(with = "string_or_float")]
-    pub quote_volume: f64,
+    pub high_price: Amount,
     #[serde(with = "string_or_float")]
-    pub last_qty: f64,
-    pub open_time: u64,
-    pub close_time: u64,
-    pub first_id: u64,
-    pub last_id: u64,
-    pub count: u64,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(untagged)]
-pub enum Trades {
-    AllTrades(Vec<Trade>),
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct Trade {
-    pub id: u64,
-    pub is_buyer_maker: bool,
+    pub low_price: Amount,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub volume: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub quote_volume: Amount,
     #[serde(with = "string_or_float")]
-    pub quote_qty: f64,
-    pub time: u64,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(untagged)]
-pub enum AggTrades {
-    AllAggTrades(Vec<AggTrade>),
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct AggTrade {
-    #[serde(rename = "T")]
-    pub time: u64,
-    #[serde(rename = "a")]
-    pub agg_id: u64,
-    #[serde(rename = "f")]
+    pub last_qty: Amount,
+    pub open_time: u64,
+    pub close_time: u64,
     pub first_id: u64,
-    #[serde(rename = "l")]
     pub last_id: u64,
-    #[serde(rename = "m")]
-    pub maker: bool,
-    #[serde(rename = "p", with = "string_or_float")]
-    pub price: f64,
-    #[serde(rename = "q", with = "string_or_float")]
-    pub qty: f64,
+    pub count: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -132,9 +93,9 @@ pub enum MarkPrices {
 pub struct MarkPrice {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub mark_price: f64,
+    pub mark_price: Amount,
     #[serde(with = "string_or_float")]
-    pub last_funding_rate: f64,
+    pub last_funding_rate: Amount,
     pub next_funding_time: u64,
     pub time: u64,
 }
@@ -149,62 +110,63 @@ pub enum LiquidationOrders {
 #[serde(rename_all = "camelCase")]
 pub struct LiquidationOrder {
     #[serde(with = "string_or_float")]
-    pub average_price: f64,
+    pub average_price: Amount,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub price: f64,
-    pub side: String,
-    pub status: String,
+    pub price: Amount,
+    pub side: OrderSide,
+    pub status: OrderStatus,
     pub symbol: String,
     pub time: u64,
-    pub time_in_force: String,
-    pub r#type: String,
+    pub time_in_force: TimeInForce,
+    pub r#type: OrderType,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenInterest {
     #[serde(with = "string_or_float")]
-    pub open_interest: f64,
+    pub open_interest: Amount,
     pub symbol: String,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-struct Order {
+pub struct Order {
     pub client_order_id: String,
+    // Not sent for open orders.
+    #[serde(with = "string_or_float", default)]
+    pub cum_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub cum_qty: f64,
-    #[serde(with = "string_or_float")]
-    pub cum_quote: f64,
+    pub cum_quote: Amount,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Amount,
     pub order_id: u64,
     #[serde(with = "string_or_float")]
-    pub avg_price: f64,
+    pub avg_price: Amount,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub price: f64,
-    pub side: String,
+    pub price: Amount,
+    pub side: OrderSide,
     pub reduce_only: bool,
     pub position_side: String,
-    pub status: String,
+    pub status: OrderStatus,
     #[serde(with = "string_or_float", default = "default_stop_price")]
-    pub stop_price: f64,
+    pub stop_price: Amount,
     pub close_position: bool,
     pub symbol: String,
-    pub time_in_force: String,
+    pub time_in_force: TimeInForce,
     #[serde(rename = "type")]
-    pub order_type: String,
-    pub orig_type: String,
+    pub order_type: OrderType,
+    pub orig_type: OrderType,
     #[serde(with = "string_or_float", default = "default_activation_price")]
-    pub activation_price: f64,
+    pub activation_price: Amount,
     #[serde(with = "string_or_float", default = "default_price_rate")]
-    pub price_rate: f64,
+    pub price_rate: Amount,
     pub update_time: u64,
     pub working_type: String,
     pub price_protect: bool,
@@ -215,45 +177,86 @@ struct Order {
 pub struct Transaction {
     pub client_order_id: String,
     #[serde(with = "string_or_float")]
-    pub cum_qty: f64,
+    pub cum_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub cum_quote: f64,
+    pub cum_quote: Amount,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Amount,
     pub order_id: u64,
     #[serde(with = "string_or_float")]
-    pub avg_price: f64,
+    pub avg_price: Amount,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Amount,
     pub reduce_only: bool,
-    pub side: String,
+    pub side: OrderSide,
     pub position_side: String,
-    pub status: String,
+    pub status: OrderStatus,
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Amount,
     pub close_position: bool,
     pub symbol: String,
-    pub time_in_force: String,
+    pub time_in_force: TimeInForce,
     #[serde(rename = "type")]
-    pub type_name: String,
-    pub orig_type: String,
+    pub type_name: OrderType,
+    pub orig_type: OrderType,
     #[serde(default)]
     #[serde(with = "string_or_float_opt")]
-    pub activate_price: Option<f64>,
+    pub activate_price: Option<Amount>,
     #[serde(default)]
     #[serde(with = "string_or_float_opt")]
-    pub price_rate: Option<f64>,
+    pub price_rate: Option<Amount>,
     pub update_time: u64,
     pub working_type: String,
     price_protect: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionRisk {
+    pub symbol: String,
+    #[serde(rename = "positionAmt", with = "string_or_float")]
+    pub position_amount: Amount,
+    #[serde(with = "string_or_float")]
+    pub entry_price: Amount,
+    #[serde(with = "string_or_float")]
+    pub mark_price: Amount,
+    #[serde(rename = "unRealizedProfit", with = "string_or_float")]
+    pub unrealized_profit: Amount,
+    #[serde(with = "string_or_float")]
+    pub liquidation_price: Amount,
+    #[serde(with = "string_or_float")]
+    pub leverage: Amount,
+    pub margin_type: String,
+    #[serde(with = "string_or_float")]
+    pub isolated_margin: Amount,
+    pub position_side: String,
+    pub update_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBalance {
+    pub asset: String,
+    /// Wallet balance.
+    #[serde(with = "string_or_float")]
+    pub balance: Amount,
+    #[serde(with = "string_or_float")]
+    pub cross_wallet_balance: Amount,
+    #[serde(rename = "crossUnPnl", with = "string_or_float")]
+    pub cross_unrealized_pnl: Amount,
+    #[serde(with = "string_or_float")]
+    pub available_balance: Amount,
+    #[serde(with = "string_or_float")]
+    pub max_withdraw_amount: Amount,
+    pub update_time: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ChangeLeverageResponse {
     pub leverage: u8,
     #[serde(with = "string_or_float")]
-    pub max_notional_value: f64,
+    pub max_notional_value: Amount,
     pub symbol: String,
 }
 
@@ -309,14 +312,14 @@ pub struct AccountBalanceUpdate {
     #[serde(rename = "a")]
     pub asset: String,
 
-    #[serde(rename = "wb")]
-    pub balance: String,
+    #[serde(rename = "wb", with = "string_or_float")]
+    pub balance: Amount,
 
-    #[serde(rename = "cw")]
-    pub cross_balance: String,
+    #[serde(rename = "cw", with = "string_or_float")]
+    pub cross_balance: Amount,
 
-    #[serde(rename = "bc")]
-    pub balance_change: String,
+    #[serde(rename = "bc", with = "string_or_float")]
+    pub balance_change: Amount,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -324,28 +327,106 @@ pub struct AccountPositionUpdate {
     #[serde(rename = "s")]
     pub symbol: String,
 
-    #[serde(rename = "pa")]
-    pub position_amount: String,
+    #[serde(rename = "pa", with = "string_or_float")]
+    pub position_amount: Amount,
 
-    #[serde(rename = "ep")]
-    pub entry_price: String,
+    #[serde(rename = "ep", with = "string_or_float")]
+    pub entry_price: Amount,
 
-    #[serde(rename = "cr")]
-    pub accumulated_realized: String,
+    #[serde(rename = "cr", with = "string_or_float")]
+    pub accumulated_realized: Amount,
 
-    #[serde(rename = "up")]
-    pub unrealized: String,
+    #[serde(rename = "up", with = "string_or_float")]
+    pub unrealized: Amount,
 
     #[serde(rename = "mt")]
     pub margin_type: String,
 
-    #[serde(rename = "iw")]
-    pub isolated_wallet: String,
+    #[serde(rename = "iw", with = "string_or_float")]
+    pub isolated_wallet: Amount,
 
     #[serde(rename = "ps")]
     pub position_side: String,
 }
 
+/// Sent when positions are close to liquidation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MarginCallEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    /// Only for cross margin.
+    #[serde(rename = "cw", default, with = "string_or_float_opt")]
+    pub cross_wallet_balance: Option<Amount>,
+
+    #[serde(rename = "p")]
+    pub positions: Vec<MarginCallPosition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MarginCallPosition {
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "ps")]
+    pub position_side: String,
+
+    #[serde(rename = "pa", with = "string_or_float")]
+    pub position_amount: Amount,
+
+    #[serde(rename = "mt")]
+    pub margin_type: String,
+
+    #[serde(rename = "iw", with = "string_or_float")]
+    pub isolated_wallet: Amount,
+
+    #[serde(rename = "mp", with = "string_or_float")]
+    pub mark_price: Amount,
+
+    #[serde(rename = "up", with = "string_or_float")]
+    pub unrealized: Amount,
+
+    #[serde(rename = "mm", with = "string_or_float")]
+    pub maintenance_margin: Amount,
+}
+
+/// Leverage of a symbol or multi-assets mode changed, only one of them is set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccountConfigUpdateEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "T")]
+    pub transaction: u64,
+
+    #[serde(rename = "ac", default)]
+    pub leverage: Option<LeverageUpdate>,
+
+    #[serde(rename = "ai", default)]
+    pub multi_assets: Option<MultiAssetsUpdate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LeverageUpdate {
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "l")]
+    pub leverage: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MultiAssetsUpdate {
+    #[serde(rename = "j")]
+    pub multi_assets_mode: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct OrderUpdateEvent {
     #[serde(rename = "e")]
@@ -370,51 +451,51 @@ pub struct OrderUpdateData {
     pub client_order_id: String,
 
     #[serde(rename = "S")]
-    pub side: OrderUpdateSide,
+    pub side: OrderSide,
 
     #[serde(rename = "o")]
-    pub order_type: String,
+    pub order_type: OrderType,
 
     #[serde(rename = "f")]
-    pub time_in_force: String,
+    pub time_in_force: TimeInForce,
 
-    #[serde(rename = "q")]
-    pub original_quantity: String,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub original_quantity: Amount,
 
-    #[serde(rename = "p")]
-    pub original_price: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub original_price: Amount,
 
-    #[serde(rename = "ap")]
-    pub average_price: String,
+    #[serde(rename = "ap", with = "string_or_float")]
+    pub average_price: Amount,
 
-    #[serde(rename = "sp")]
-    pub stop_price: String,
+    #[serde(rename = "sp", with = "string_or_float")]
+    pub stop_price: Amount,
 
     #[serde(rename = "x")]
-    pub execution_type: String,
+    pub execution_type: ExecutionType,
 
     #[serde(rename = "X")]
-    pub status: String,
+    pub status: OrderStatus,
 
     #[serde(rename = "i")]
     pub order_id: u64,
 
-    #[serde(rename = "l")]
-    pub last_filled_quantity: String,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub last_filled_quantity: Amount,
 
-    #[serde(rename = "z")]
-    pub filled_accumulated_quantity: String,
+    #[serde(rename = "z", with = "string_or_float")]
+    pub filled_accumulated_quantity: Amount,
 
-    #[serde(rename = "L")]
-    pub last_filled_price: String,
+    #[serde(rename = "L", with = "string_or_float")]
+    pub last_filled_price: Amount,
 
     #[serde(rename = "N")]
     #[serde(default)]
     pub commission_asset: Option<String>,
 
-    #[serde(rename = "n")]
+    #[serde(rename = "n", with = "string_or_float_opt")]
     #[serde(default)]
-    pub commission: Option<String>,
+    pub commission: Option<Amount>,
 
     #[serde(rename = "T")]
     pub order_trade_time: u64,
@@ -422,11 +503,11 @@ pub struct OrderUpdateData {
     #[serde(rename = "t")]
     pub trade_id: u64,
 
-    #[serde(rename = "b")]
-    pub bids_notional: String,
+    #[serde(rename = "b", with = "string_or_float")]
+    pub bids_notional: Amount,
 
-    #[serde(rename = "a")]
-    pub asks_notional: String,
+    #[serde(rename = "a", with = "string_or_float")]
+    pub asks_notional: Amount,
 
     #[serde(rename = "m")]
     pub trade_maker_side: bool,
@@ -438,7 +519,7 @@ pub struct OrderUpdateData {
     pub stop_price_working_type: String,
 
     #[serde(rename = "ot")]
-    pub original_order_type: String,
+    pub original_order_type: OrderType,
 
     #[serde(rename = "ps")]
     pub position_side: String,
@@ -446,24 +527,20 @@ pub struct OrderUpdateData {
     #[serde(rename = "cp")]
     pub close_all: bool,
 
-    #[serde(rename = "AP")]
+    #[serde(rename = "AP", with = "string_or_float_opt")]
     #[serde(default)]
-    pub activation_price: Option<String>,
+    pub activation_price: Option<Amount>,
 
-    #[serde(rename = "cr")]
+    #[serde(rename = "cr", with = "string_or_float_opt")]
     #[serde(default)]
-    pub callback_rate: Option<String>,
+    pub callback_rate: Option<Amount>,
 
-    #[serde(rename = "rp")]
-    pub realized_profit: String,
+    #[serde(rename = "rp", with = "string_or_float")]
+    pub realized_profit: Amount,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum OrderUpdateSide {
-    Buy,
-    Sell,
-}
+/// Kept for compatibility, order updates use the shared `OrderSide`.
+pub type OrderUpdateSide = OrderSide;
 
 #[test]
 fn deserialize_order_update_event() {
@@ -503,33 +580,33 @@ fn deserialize_order_update_event() {
                 symbol: "BTCUSDT".to_string(),
                 client_order_id: "TEST".to_string(),
                 side: OrderUpdateSide::Sell,
-                order_type: "TRAILING_STOP_MARKET".to_string(),
-                time_in_force: "GTC".to_string(),
-                original_quantity: "0.001".to_string(),
-                original_price: "0".to_string(),
-                average_price: "0".to_string(),
-                stop_price: "7103.04".to_string(),
-                execution_type: "NEW".to_string(),
-                status: "NEW".to_string(),
+                order_type: OrderType::TrailingStopMarket,
+                time_in_force: TimeInForce::GTC,
+                original_quantity: "0.001".parse().unwrap(),
+                original_price: "0".parse().unwrap(),
+                average_price: "0".parse().unwrap(),
+                stop_price: "7103.04".parse().unwrap(),
+                execution_type: ExecutionType::New,
+                status: OrderStatus::New,
                 order_id: 8886774,
-                last_filled_quantity: "0".to_string(),
-                filled_accumulated_quantity: "0".to_string(),
-                last_filled_price: "0".to_string(),
+                last_filled_quantity: "0".parse().unwrap(),
+                filled_accumulated_quantity: "0".parse().unwrap(),
+                last_filled_price: "0".parse().unwrap(),
                 commission_asset: Some("USDT".to_string()),
-                commission: Some("0".to_string()),
+                commission: Some("0".parse().unwrap()),
                 order_trade_time: 1568879465651,
                 trade_id: 0,
-                bids_notional: "0".to_string(),
-                asks_notional: "9.91".to_string(),
+                bids_notional: "0".parse().unwrap(),
+                asks_notional: "9.91".parse().unwrap(),
                 trade_maker_side: false,
                 reduce_only: false,
                 stop_price_working_type: "CONTRACT_PRICE".to_string(),
-                original_order_type: "TRAILING_STOP_MARKET".to_string(),
+                original_order_type: OrderType::TrailingStopMarket,
                 position_side: "LONG".to_string(),
                 close_all: false,
-                activation_price: Some("7476.89".to_string()),
-                callback_rate: Some("5.0".to_string()),
-                realized_profit: "0".to_string()
+                activation_price: Some("7476.89".parse().unwrap()),
+                callback_rate: Some("5.0".parse().unwrap()),
+                realized_profit: "0".parse().unwrap()
             }
         }
     );
@@ -547,33 +624,33 @@ fn deserialize_order_update_event() {
                 symbol: "DOTUSDT".to_string(),
                 client_order_id: "web_2F1nPfgmy53m5J4Hh9of".to_string(),
                 side: OrderUpdateSide::Buy,
-                order_type: "LIMIT".to_string(),
-                time_in_force: "GTC".to_string(),
-                original_quantity: "198.6".to_string(),
-                original_price: "23".to_string(),
-                average_price: "0".to_string(),
-                stop_price: "0".to_string(),
-                execution_type: "CANCELED".to_string(),
-                status: "CANCELED".to_string(),
+                order_type: OrderType::Limit,
+                time_in_force: TimeInForce::GTC,
+                original_quantity: "198.6".parse().unwrap(),
+                original_price: "23".parse().unwrap(),
+                average_price: "0".parse().unwrap(),
+                stop_price: "0".parse().unwrap(),
+                execution_type: ExecutionType::Canceled,
+                status: OrderStatus::Canceled,
                 order_id: 5595810084,
-                last_filled_quantity: "0".to_string(),
-                filled_accumulated_quantity: "0".to_string(),
-                last_filled_price: "0".to_string(),
+                last_filled_quantity: "0".parse().unwrap(),
+                filled_accumulated_quantity: "0".parse().unwrap(),
+                last_filled_price: "0".parse().unwrap(),
                 commission_asset: None,
                 commission: None,
                 order_trade_time: 1622142325683,
                 trade_id: 0,
-                bids_notional: "0".to_string(),
-                asks_notional: "0".to_string(),
+                bids_notional: "0".parse().unwrap(),
+                asks_notional: "0".parse().unwrap(),
                 trade_maker_side: false,
                 reduce_only: false,
                 stop_price_working_type: "CONTRACT_PRICE".to_string(),
-                original_order_type: "LIMIT".to_string(),
+                original_order_type: OrderType::Limit,
                 position_side: "BOTH".to_string(),
                 close_all: false,
                 activation_price: None,
                 callback_rate: None,
-                realized_profit: "0".to_string()
+                realized_profit: "0".parse().unwrap()
             }
         }
     );
@@ -591,33 +668,33 @@ fn deserialize_order_update_event() {
                 symbol: "GRTUSDT".to_string(),
                 client_order_id: "web_zf9zndTx6Ih1tuU0paEB".to_string(),
                 side: OrderUpdateSide::Buy,
-                order_type: "LIMIT".to_string(),
-                time_in_force: "GTC".to_string(),
-                original_quantity: "12".to_string(),
-                original_price: "0.75919".to_string(),
-                average_price: "0".to_string(),
-                stop_price: "0".to_string(),
-                execution_type: "NEW".to_string(),
-                status: "NEW".to_string(),
+                order_type: OrderType::Limit,
+                time_in_force: TimeInForce::GTC,
+                original_quantity: "12".parse().unwrap(),
+                original_price: "0.75919".parse().unwrap(),
+                average_price: "0".parse().unwrap(),
+                stop_price: "0".parse().unwrap(),
+                execution_type: ExecutionType::New,
+                status: OrderStatus::New,
                 order_id: 1697404867,
-                last_filled_quantity: "0".to_string(),
-                filled_accumulated_quantity: "0".to_string(),
-                last_filled_price: "0".to_string(),
+                last_filled_quantity: "0".parse().unwrap(),
+                filled_accumulated_quantity: "0".parse().unwrap(),
+                last_filled_price: "0".parse().unwrap(),
                 commission_asset: None,
                 commission: None,
                 order_trade_time: 1622142670086,
                 trade_id: 0,
-                bids_notional: "9.11028".to_string(),
-                asks_notional: "0".to_string(),
+                bids_notional: "9.11028".parse().unwrap(),
+                asks_notional: "0".parse().unwrap(),
                 trade_maker_side: false,
                 reduce_only: false,
                 stop_price_working_type: "CONTRACT_PRICE".to_string(),
-                original_order_type: "LIMIT".to_string(),
+                original_order_type: OrderType::Limit,
                 position_side: "BOTH".to_string(),
                 close_all: false,
                 activation_price: None,
                 callback_rate: None,
-                realized_profit: "0".to_string()
+                realized_profit: "0".parse().unwrap()
             }
         }
     );
@@ -633,33 +710,33 @@ fn deserialize_order_update_event() {
                 symbol: "GRTUSDT".to_string(),
                 client_order_id: "web_TF7zjup9BmWWFluoSz6M".to_string(),
                 side: OrderUpdateSide::Buy,
-                order_type: "MARKET".to_string(),
-                time_in_force: "GTC".to_string(),
-                original_quantity: "10".to_string(),
-                original_price: "0".to_string(),
-                average_price: "0.76867".to_string(),
-                stop_price: "0".to_string(),
-                execution_type: "TRADE".to_string(),
-                status: "FILLED".to_string(),
+                order_type: OrderType::Market,
+                time_in_force: TimeInForce::GTC,
+                original_quantity: "10".parse().unwrap(),
+                original_price: "0".parse().unwrap(),
+                average_price: "0.76867".parse().unwrap(),
+                stop_price: "0".parse().unwrap(),
+                execution_type: ExecutionType::Trade,
+                status: OrderStatus::Filled,
                 order_id: 1697440152,
-                last_filled_quantity: "10".to_string(),
-                filled_accumulated_quantity: "10".to_string(),
-                last_filled_price: "0.76867".to_string(),
+                last_filled_quantity: "10".parse().unwrap(),
+                filled_accumulated_quantity: "10".parse().unwrap(),
+                last_filled_price: "0.76867".parse().unwrap(),
                 commission_asset: Some("USDT".to_string()),
-                commission: Some("0.00307468".to_string()),
+                commission: Some("0.00307468".parse().unwrap()),
                 order_trade_time: 1622143071600,
                 trade_id: 69281933,
-                bids_notional: "0".to_string(),
-                asks_notional: "0".to_string(),
+                bids_notional: "0".parse().unwrap(),
+                asks_notional: "0".parse().unwrap(),
                 trade_maker_side: false,
                 reduce_only: false,
                 stop_price_working_type: "CONTRACT_PRICE".to_string(),
-                original_order_type: "MARKET".to_string(),
+                original_order_type: OrderType::Market,
                 position_side: "BOTH".to_string(),
                 close_all: false,
                 activation_price: None,
                 callback_rate: None,
-                realized_profit: "0".to_string()
+                realized_profit: "0".parse().unwrap()
             }
         }
     );
@@ -740,46 +817,46 @@ fn deserialize_account_update_event() {
                 balance_updates: vec![
                     AccountBalanceUpdate {
                         asset: "USDT".to_string(),
-                        balance: "122624.12345678".to_string(),
-                        cross_balance: "100.12345678".to_string(),
-                        balance_change: "50.12345678".to_string()
+                        balance: "122624.12345678".parse().unwrap(),
+                        cross_balance: "100.12345678".parse().unwrap(),
+                        balance_change: "50.12345678".parse().unwrap()
                     },
                     AccountBalanceUpdate {
                         asset: "BUSD".to_string(),
-                        balance: "1.00000000".to_string(),
-                        cross_balance: "0.00000000".to_string(),
-                        balance_change: "-49.12345678".to_string()
+                        balance: "1.00000000".parse().unwrap(),
+                        cross_balance: "0.00000000".parse().unwrap(),
+                        balance_change: "-49.12345678".parse().unwrap()
                     }
                 ],
                 position: vec![
                     AccountPositionUpdate {
                         symbol: "BTCUSDT".to_string(),
-                        position_amount: "0".to_string(),
-                        entry_price: "0.00000".to_string(),
-                        accumulated_realized: "200".to_string(),
-                        unrealized: "0".to_string(),
+                        position_amount: "0".parse().unwrap(),
+                        entry_price: "0.00000".parse().unwrap(),
+                        accumulated_realized: "200".parse().unwrap(),
+                        unrealized: "0".parse().unwrap(),
                         margin_type: "isolated".to_string(),
-                        isolated_wallet: "0.00000000".to_string(),
+                        isolated_wallet: "0.00000000".parse().unwrap(),
                         position_side: "BOTH".to_string()
                     },
                     AccountPositionUpdate {
                         symbol: "BTCUSDT".to_string(),
-                        position_amount: "20".to_string(),
-                        entry_price: "6563.66500".to_string(),
-                        accumulated_realized: "0".to_string(),
-                        unrealized: "2850.21200".to_string(),
+                        position_amount: "20".parse().unwrap(),
+                        entry_price: "6563.66500".parse().unwrap(),
+                        accumulated_realized: "0".parse().unwrap(),
+                        unrealized: "2850.21200".parse().unwrap(),
                         margin_type: "isolated".to_string(),
-                        isolated_wallet: "13200.70726908".to_string(),
+                        isolated_wallet: "13200.70726908".parse().unwrap(),
                         position_side: "LONG".to_string()
                     },
                     AccountPositionUpdate {
                         symbol: "BTCUSDT".to_string(),
-                        position_amount: "-10".to_string(),
-                        entry_price: "6563.86000".to_string(),
-                        accumulated_realized: "-45.04000000".to_string(),
-                        unrealized: "-1423.15600".to_string(),
+                        position_amount: "-10".parse().unwrap(),
+                        entry_price: "6563.86000".parse().unwrap(),
+                        accumulated_realized: "-45.04000000".parse().unwrap(),
+                        unrealized: "-1423.15600".parse().unwrap(),
                         margin_type: "isolated".to_string(),
-                        isolated_wallet: "6570.42511771".to_string(),
+                        isolated_wallet: "6570.42511771".parse().unwrap(),
                         position_side: "SHORT".to_string()
                     }
                 ]
@@ -788,12 +865,12 @@ fn deserialize_account_update_event() {
     );
 }
 
-fn default_stop_price() -> f64 {
-    0.0
+fn default_stop_price() -> Amount {
+    Amount::default()
 }
-fn default_activation_price() -> f64 {
-    0.0
+fn default_activation_price() -> Amount {
+    Amount::default()
 }
-fn default_price_rate() -> f64 {
-    0.0
+fn default_price_rate() -> Amount {
+    Amount::default()
 }
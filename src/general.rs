@@ -1,6 +1,7 @@
 use crate::model::*;
 use crate::client::*;
 use crate::errors::*;
+use crate::rules::SymbolRules;
 use crate::api::API;
 use crate::api::Spot;
 
@@ -45,4 +46,13 @@ impl General {
             Err(e) => Err(e),
         }
     }
+
+    // Get the trading rules of a symbol, to check orders before sending them
+    pub fn get_symbol_rules<S>(&self, symbol: S) -> Result<SymbolRules>
+    where
+        S: Into<String>,
+    {
+        let symbol = self.get_symbol_info(symbol)?;
+        Ok(SymbolRules::from(&symbol))
+    }
 }
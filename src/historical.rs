@@ -0,0 +1,395 @@
+//! Historical market data from the public archives of data.binance.vision.
+//! Requires the `historical` feature.
+//!
+//! The archives are zipped CSV files of klines, aggregate trades or trades,
+//! one per day or month, each with a `.CHECKSUM` file holding its SHA-256.
+//! They are parsed into the same models as the REST responses, and the time
+//! since the last archive is filled in from the REST API:
+//!
+//! ```rust,no_run
+//! use binance::api::*;
+//! use binance::config::*;
+//! use binance::historical::*;
+//! use binance::market::*;
+//! use binance::model::*;
+//!
+//! let config = Config::default();
+//! let downloader = Downloader::new(&config).unwrap();
+//! let archive = Archive::monthly(ArchiveMarket::Spot, DataType::AggTrades, "BTCUSDT", 2024, 1);
+//! let path = downloader.download(&archive, "data").unwrap();
+//!
+//! let mut trades: Vec<AggTrade> = load(&path).unwrap();
+//! let market: Market = Binance::new_with_config(None, None, &config).unwrap();
+//! let recent = market.agg_trades_from("BTCUSDT", resume_from(&trades));
+//! merge(&mut trades, recent).unwrap();
+//! ```
+//!
+//! Spot archives from 2025 on have timestamps in microseconds, they are
+//! converted to milliseconds like everywhere else in this crate.
+
+use crate::config::Config;
+use crate::errors::*;
+use crate::model::{AggTrade, Amount, KlineInterval, KlineSummary, Trade};
+use crate::transport::build_http_client;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{self, Read, Seek};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Millisecond timestamps stay below this until the year 5138.
+const MICROSECONDS_FROM: u64 = 100_000_000_000_000;
+
+/// Market of an archive, `Futures` being the USD-M futures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArchiveMarket {
+    Spot,
+    Futures,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataType {
+    Klines(KlineInterval),
+    AggTrades,
+    Trades,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Period {
+    Daily,
+    Monthly,
+}
+
+/// One archive file, e.g. the 1m klines of BTCUSDT for January 2024.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Archive {
+    pub market: ArchiveMarket,
+    pub data_type: DataType,
+    pub symbol: String,
+    pub period: Period,
+    /// `YYYY-MM` for monthly archives, `YYYY-MM-DD` for daily ones.
+    pub date: String,
+}
+
+impl Archive {
+    pub fn monthly<S>(market: ArchiveMarket, data_type: DataType, symbol: S, year: u16, month: u8) -> Self
+    where
+        S: Into<String>,
+    {
+        Archive {
+            market,
+            data_type,
+            symbol: symbol.into(),
+            period: Period::Monthly,
+            date: format!("{:04}-{:02}", year, month),
+        }
+    }
+
+    pub fn daily<S>(
+        market: ArchiveMarket, data_type: DataType, symbol: S, year: u16, month: u8, day: u8,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        Archive {
+            market,
+            data_type,
+            symbol: symbol.into(),
+            period: Period::Daily,
+            date: format!("{:04}-{:02}-{:02}", year, month, day),
+        }
+    }
+
+    /// e.g. `BTCUSDT-1m-2024-01.zip`
+    pub fn file_name(&self) -> String {
+        let data_type = match self.data_type {
+            DataType::Klines(interval) => String::from(interval),
+            DataType::AggTrades => "aggTrades".into(),
+            DataType::Trades => "trades".into(),
+        };
+        format!("{}-{}-{}.zip", self.symbol, data_type, self.date)
+    }
+
+    /// Path of the archive under the data endpoint, e.g.
+    /// `data/spot/monthly/klines/BTCUSDT/1m/BTCUSDT-1m-2024-01.zip`
+    pub fn path(&self) -> String {
+        let market = match self.market {
+            ArchiveMarket::Spot => "spot",
+            ArchiveMarket::Futures => "futures/um",
+        };
+        let period = match self.period {
+            Period::Daily => "daily",
+            Period::Monthly => "monthly",
+        };
+        let data_type = match self.data_type {
+            DataType::Klines(interval) => format!("klines/{}/{}", self.symbol, String::from(interval)),
+            DataType::AggTrades => format!("aggTrades/{}", self.symbol),
+            DataType::Trades => format!("trades/{}", self.symbol),
+        };
+        format!("data/{}/{}/{}/{}", market, period, data_type, self.file_name())
+    }
+}
+
+/// Downloads archives from the data endpoint of the config.
+#[derive(Clone)]
+pub struct Downloader {
+    client: reqwest::blocking::Client,
+    data_endpoint: String,
+}
+
+impl Downloader {
+    pub fn new(config: &Config) -> Result<Self> {
+        Ok(Downloader {
+            client: build_http_client(config)?,
+            data_endpoint: config.data_endpoint.clone(),
+        })
+    }
+
+    /// Saves the archive and its checksum file into `dir` and verifies the
+    /// archive, returning its path. Archives already saved are only verified.
+    pub fn download<P: AsRef<Path>>(&self, archive: &Archive, dir: P) -> Result<PathBuf> {
+        fs::create_dir_all(dir.as_ref())?;
+        let path = dir.as_ref().join(archive.file_name());
+        let checksum_path = checksum_path(&path);
+        let url = format!("{}/{}", self.data_endpoint, archive.path());
+
+        if !checksum_path.exists() {
+            self.fetch(&format!("{}.CHECKSUM", url), &checksum_path)?;
+        }
+        if !path.exists() || verify_checksum(&path).is_err() {
+            self.fetch(&url, &path)?;
+        }
+        verify_checksum(&path)?;
+        Ok(path)
+    }
+
+    fn fetch(&self, url: &str, path: &Path) -> Result<()> {
+        let mut response = self.client.get(url).send()?;
+        if !response.status().is_success() {
+            return Err(BinanceError::Http {
+                status: response.status().as_u16(),
+                body: format!("Failed to download {}", url),
+            });
+        }
+        // Written under another name first, so that an interrupted download
+        // isn't mistaken for an archive.
+        let partial = path.with_extension("part");
+        io::copy(&mut response, &mut File::create(&partial)?)?;
+        fs::rename(&partial, path)?;
+        Ok(())
+    }
+}
+
+/// A row of an archive.
+pub trait ArchiveRecord: Sized {
+    fn from_record(record: &csv::StringRecord) -> Result<Self>;
+
+    /// Open time of a kline, id of a trade: increasing from one row to the next.
+    fn key(&self) -> u64;
+
+    /// Whether the record is final, klines are not until their close time.
+    fn is_closed(&self) -> bool {
+        true
+    }
+}
+
+impl ArchiveRecord for KlineSummary {
+    fn from_record(record: &csv::StringRecord) -> Result<Self> {
+        Ok(KlineSummary {
+            open_time: millis(field(record, 0)?) as i64,
+            open: field(record, 1)?,
+            high: field(record, 2)?,
+            low: field(record, 3)?,
+            close: field(record, 4)?,
+            volume: field(record, 5)?,
+            close_time: millis(field(record, 6)?) as i64,
+            quote_asset_volume: field(record, 7)?,
+            number_of_trades: field(record, 8)?,
+            taker_buy_base_asset_volume: field(record, 9)?,
+            taker_buy_quote_asset_volume: field(record, 10)?,
+        })
+    }
+
+    fn key(&self) -> u64 {
+        self.open_time as u64
+    }
+
+    fn is_closed(&self) -> bool {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_millis());
+        i128::from(self.close_time) < now as i128
+    }
+}
+
+impl ArchiveRecord for AggTrade {
+    fn from_record(record: &csv::StringRecord) -> Result<Self> {
+        Ok(AggTrade {
+            agg_id: field(record, 0)?,
+            price: field::<Amount>(record, 1)?,
+            qty: field::<Amount>(record, 2)?,
+            first_id: field(record, 3)?,
+            last_id: field(record, 4)?,
+            time: millis(field(record, 5)?),
+            maker: boolean(record, 6)?,
+        })
+    }
+
+    fn key(&self) -> u64 {
+        self.agg_id
+    }
+}
+
+impl ArchiveRecord for Trade {
+    fn from_record(record: &csv::StringRecord) -> Result<Self> {
+        Ok(Trade {
+            id: field(record, 0)?,
+            price: field::<Amount>(record, 1)?,
+            qty: field::<Amount>(record, 2)?,
+            quote_qty: field::<Amount>(record, 3)?,
+            time: millis(field(record, 4)?),
+            is_buyer_maker: boolean(record, 5)?,
+        })
+    }
+
+    fn key(&self) -> u64 {
+        self.id
+    }
+}
+
+/// Parses a CSV file of an archive. The header row of the futures archives
+/// is skipped.
+pub fn read_csv<T: ArchiveRecord, R: Read>(reader: R) -> Result<Vec<T>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(reader);
+    let mut records = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let record = record?;
+        // `Option::is_none_or` would need Rust 1.82.
+        #[allow(clippy::unnecessary_map_or)]
+        let is_header = record.get(0).map_or(true, |id| id.parse::<u64>().is_err());
+        if index == 0 && is_header {
+            continue;
+        }
+        records.push(T::from_record(&record)?);
+    }
+    Ok(records)
+}
+
+/// Parses every CSV file of a zip archive, in order.
+pub fn read_zip<T: ArchiveRecord, R: Read + Seek>(reader: R) -> Result<Vec<T>> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+    let mut records = Vec::new();
+    for index in 0..archive.len() {
+        let file = archive.by_index(index)?;
+        if file.name().ends_with(".csv") {
+            records.extend(read_csv(file)?);
+        }
+    }
+    Ok(records)
+}
+
+/// Parses a `.zip` archive or an extracted `.csv` file. The file is first
+/// verified against its `.CHECKSUM` file when there is one next to it.
+pub fn load<T: ArchiveRecord, P: AsRef<Path>>(path: P) -> Result<Vec<T>> {
+    let path = path.as_ref();
+    if checksum_path(path).exists() {
+        verify_checksum(path)?;
+    }
+    let file = File::open(path)?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("zip") => read_zip(file),
+        _ => read_csv(file),
+    }
+}
+
+/// Checks the SHA-256 of the file against its `.CHECKSUM` file, which holds
+/// the hash followed by the file name.
+pub fn verify_checksum<P: AsRef<Path>>(path: P) -> Result<()> {
+    let path = path.as_ref();
+    let checksum = fs::read_to_string(checksum_path(path))?;
+    let expected = checksum.split_whitespace().next().unwrap_or_default();
+    let actual = sha256(File::open(path)?)?;
+    if !expected.eq_ignore_ascii_case(&actual) {
+        bail!(format!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            path.display(),
+            expected,
+            actual
+        ));
+    }
+    Ok(())
+}
+
+/// Key to resume from with the REST API after the archived records: the open
+/// time for `klines_range`, or the id for `agg_trades_from` and
+/// `historical_trades_from`.
+pub fn resume_from<T: ArchiveRecord>(records: &[T]) -> u64 {
+    records.last().map_or(0, |record| record.key() + 1)
+}
+
+/// Appends the records of `recent` that come after the last one of `records`.
+/// The last kline from the REST API may still be open, it is left out until a
+/// later merge gets it closed.
+pub fn merge<T, I>(records: &mut Vec<T>, recent: I) -> Result<()>
+where
+    T: ArchiveRecord,
+    I: IntoIterator<Item = Result<T>>,
+{
+    let mut next_key = resume_from(records);
+    for record in recent {
+        let record = record?;
+        if record.key() >= next_key && record.is_closed() {
+            next_key = record.key() + 1;
+            records.push(record);
+        }
+    }
+    Ok(())
+}
+
+fn checksum_path(path: &Path) -> PathBuf {
+    let mut checksum_path = path.as_os_str().to_owned();
+    checksum_path.push(".CHECKSUM");
+    PathBuf::from(checksum_path)
+}
+
+fn sha256<R: Read>(mut reader: R) -> Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buffer = [0; 64 * 1024];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn field<T: FromStr>(record: &csv::StringRecord, index: usize) -> Result<T>
+where
+    T::Err: std::fmt::Display,
+{
+    let value = record.get(index).unwrap_or_default();
+    value.parse().map_err(|e| {
+        format!("Invalid archive row {:?}, column {}: {}", record, index, e).into()
+    })
+}
+
+// Spot archives write `True` and `False`, futures ones `true` and `false`.
+fn boolean(record: &csv::StringRecord, index: usize) -> Result<bool> {
+    match record.get(index) {
+        Some(value) if value.eq_ignore_ascii_case("true") => Ok(true),
+        Some(value) if value.eq_ignore_ascii_case("false") => Ok(false),
+        _ => bail!(format!("Invalid archive row {:?}, column {}: not a boolean", record, index)),
+    }
+}
+
+fn millis(timestamp: u64) -> u64 {
+    if timestamp >= MICROSECONDS_FROM {
+        timestamp / 1000
+    } else {
+        timestamp
+    }
+}
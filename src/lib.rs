@@ -9,19 +9,35 @@
 #![allow(clippy::needless_doctest_main)]
 
 #[macro_use]
-extern crate error_chain;
+pub mod errors;
 
 mod client;
-pub mod errors;
 pub mod util;
 
 pub mod model;
 
 pub mod account;
+pub mod account_state;
 pub mod api;
+pub mod candles;
 pub mod config;
+pub mod credentials;
+pub mod exchange_info;
 pub mod general;
+#[cfg(feature = "historical")]
+pub mod historical;
 pub mod market;
+pub mod middleware;
+pub mod paper;
+#[cfg(feature = "recorder")]
+pub mod recorder;
+pub mod rules;
+pub mod signer;
+pub mod sub_account;
+#[cfg(feature = "testkit")]
+pub mod testkit;
+pub mod trading;
+pub mod transport;
 pub mod userstream;
 pub mod websockets;
 
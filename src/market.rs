@@ -2,8 +2,8 @@ use crate::util::*;
 use crate::model::*;
 use crate::client::*;
 use crate::errors::*;
-use std::collections::BTreeMap;
-use serde_json::Value;
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
 use crate::api::API;
 use crate::api::Spot;
 
@@ -20,8 +20,8 @@ impl Market {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
         let request = build_request(parameters);
         self.client.get(API::Spot(Spot::Depth), Some(request))
     }
@@ -32,9 +32,9 @@ impl Market {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("limit".into(), depth.to_string());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
+        parameters.insert("limit", depth.to_string());
         let request = build_request(parameters);
         self.client.get(API::Spot(Spot::Depth), Some(request))
     }
@@ -49,8 +49,8 @@ impl Market {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
         let request = build_request(parameters);
         self.client.get(API::Spot(Spot::Price), Some(request))
     }
@@ -60,8 +60,8 @@ impl Market {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
         let request = build_request(parameters);
         self.client.get(API::Spot(Spot::AvgPrice), Some(request))
     }
@@ -77,8 +77,8 @@ impl Market {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
         let request = build_request(parameters);
         self.client.get(API::Spot(Spot::BookTicker), Some(request))
     }
@@ -88,8 +88,8 @@ impl Market {
     where
         S: Into<String>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", symbol);
         let request = build_request(parameters);
         self.client.get(API::Spot(Spot::Ticker24hr), Some(request))
     }
@@ -111,42 +111,227 @@ impl Market {
         S4: Into<Option<u64>>,
         S5: Into<Option<u64>>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        let mut parameters = RequestParams::new();
 
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("interval".into(), interval.into());
+        parameters.insert("symbol", symbol);
+        parameters.insert("interval", interval);
 
         // Add three optional parameters
         if let Some(lt) = limit.into() {
-            parameters.insert("limit".into(), format!("{}", lt));
+            parameters.insert("limit", format!("{}", lt));
         }
         if let Some(st) = start_time.into() {
-            parameters.insert("startTime".into(), format!("{}", st));
+            parameters.insert("startTime", format!("{}", st));
         }
         if let Some(et) = end_time.into() {
-            parameters.insert("endTime".into(), format!("{}", et));
+            parameters.insert("endTime", format!("{}", et));
         }
 
         let request = build_request(parameters);
-        let data: Vec<Vec<Value>> = self.client.get(API::Spot(Spot::Klines), Some(request))?;
+        let data: Vec<KlineRow> = self.client.get(API::Spot(Spot::Klines), Some(request))?;
 
         let klines = KlineSummaries::AllKlineSummaries(
-            data.iter()
-                .map(|row| KlineSummary {
-                    open_time: to_i64(&row[0]),
-                    open: to_f64(&row[1]),
-                    high: to_f64(&row[2]),
-                    low: to_f64(&row[3]),
-                    close: to_f64(&row[4]),
-                    volume: to_f64(&row[5]),
-                    close_time: to_i64(&row[6]),
-                    quote_asset_volume: to_f64(&row[7]),
-                    number_of_trades: to_i64(&row[8]),
-                    taker_buy_base_asset_volume: to_f64(&row[9]),
-                    taker_buy_quote_asset_volume: to_f64(&row[10]),
-                })
-                .collect(),
+            data.into_iter().map(KlineSummary::from).collect(),
         );
         Ok(klines)
     }
+
+    // Klines with an open time in [start_time, end_time], fetched page by page as the
+    // iterator is consumed.
+    pub fn klines_range<S>(
+        &self, symbol: S, interval: KlineInterval, start_time: u64, end_time: u64,
+    ) -> KlineRange
+    where
+        S: Into<String>,
+    {
+        KlineRange::new(
+            self.client.clone(),
+            API::Spot(Spot::Klines),
+            symbol.into(),
+            interval,
+            start_time,
+            end_time,
+        )
+    }
+
+    // Aggregate trades from `from_id` on, up to the latest one, fetched page by page
+    // as the iterator is consumed.
+    pub fn agg_trades_from<S>(&self, symbol: S, from_id: u64) -> TradeIdRange<AggTrade>
+    where
+        S: Into<String>,
+    {
+        TradeIdRange::new(
+            self.client.clone(),
+            API::Spot(Spot::AggTrades),
+            symbol.into(),
+            from_id,
+            1000,
+            false,
+            |trade: &AggTrade| trade.agg_id,
+        )
+    }
+
+    // Trades from `from_id` on, up to the latest one, fetched page by page as the
+    // iterator is consumed.
+    pub fn historical_trades_from<S>(&self, symbol: S, from_id: u64) -> TradeIdRange<Trade>
+    where
+        S: Into<String>,
+    {
+        TradeIdRange::new(
+            self.client.clone(),
+            API::Spot(Spot::HistoricalTrades),
+            symbol.into(),
+            from_id,
+            1000,
+            true,
+            |trade: &Trade| trade.id,
+        )
+    }
+}
+
+/// Iterator over the klines of a time range, requesting the next page once
+/// the current one is drained. Stops after the first error.
+pub struct KlineRange {
+    client: Client,
+    endpoint: API,
+    symbol: String,
+    interval: KlineInterval,
+    next_start: u64,
+    end_time: u64,
+    page: VecDeque<KlineSummary>,
+    done: bool,
+}
+
+impl KlineRange {
+    // Both spot and futures accept up to 1000 klines per request.
+    const PAGE_LIMIT: u16 = 1000;
+
+    pub(crate) fn new(
+        client: Client, endpoint: API, symbol: String, interval: KlineInterval, start_time: u64,
+        end_time: u64,
+    ) -> Self {
+        KlineRange {
+            client,
+            endpoint,
+            symbol,
+            interval,
+            next_start: start_time,
+            end_time,
+            page: VecDeque::new(),
+            done: start_time > end_time,
+        }
+    }
+
+    fn fetch_page(&mut self) -> Result<()> {
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", self.symbol.as_str());
+        parameters.insert("interval", self.interval);
+        parameters.insert("limit", Self::PAGE_LIMIT.to_string());
+        parameters.insert("startTime", self.next_start.to_string());
+        parameters.insert("endTime", self.end_time.to_string());
+
+        let request = build_request(parameters);
+        let data: Vec<KlineRow> = self.client.get(self.endpoint, Some(request))?;
+
+        if data.len() < usize::from(Self::PAGE_LIMIT) {
+            self.done = true;
+        }
+        for kline in data.into_iter().map(KlineSummary::from) {
+            // Open times are strictly increasing, so the next page starts right after
+            // the last kline seen.
+            self.next_start = kline.open_time as u64 + 1;
+            self.page.push_back(kline);
+        }
+        if self.page.is_empty() || self.next_start > self.end_time {
+            self.done = true;
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for KlineRange {
+    type Item = Result<KlineSummary>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.page.is_empty() && !self.done {
+            if let Err(e) = self.fetch_page() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+        self.page.pop_front().map(Ok)
+    }
+}
+
+/// Iterator over trades or aggregate trades from an id on, requesting the next
+/// page once the current one is drained. Ends with the latest trade, or after
+/// the first error.
+pub struct TradeIdRange<T> {
+    client: Client,
+    endpoint: API,
+    symbol: String,
+    next_id: u64,
+    limit: u16,
+    with_api_key: bool,
+    id: fn(&T) -> u64,
+    page: VecDeque<T>,
+    done: bool,
+}
+
+impl<T: DeserializeOwned> TradeIdRange<T> {
+    /// `with_api_key` for the endpoints that need the API key header, such as
+    /// `historicalTrades`.
+    pub(crate) fn new(
+        client: Client, endpoint: API, symbol: String, from_id: u64, limit: u16, with_api_key: bool,
+        id: fn(&T) -> u64,
+    ) -> Self {
+        TradeIdRange {
+            client,
+            endpoint,
+            symbol,
+            next_id: from_id,
+            limit,
+            with_api_key,
+            id,
+            page: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn fetch_page(&mut self) -> Result<()> {
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", self.symbol.as_str());
+        parameters.insert("fromId", self.next_id.to_string());
+        parameters.insert("limit", self.limit.to_string());
+
+        let request = Some(build_request(parameters));
+        let data: Vec<T> = if self.with_api_key {
+            self.client.get_with_api_key(self.endpoint, request)?
+        } else {
+            self.client.get(self.endpoint, request)?
+        };
+
+        if data.len() < usize::from(self.limit) {
+            self.done = true;
+        }
+        for trade in data {
+            self.next_id = (self.id)(&trade) + 1;
+            self.page.push_back(trade);
+        }
+        Ok(())
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for TradeIdRange<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.page.is_empty() && !self.done {
+            if let Err(e) = self.fetch_page() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+        self.page.pop_front().map(Ok)
+    }
 }
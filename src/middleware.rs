@@ -0,0 +1,140 @@
+//! Hooks around every REST request, for logging, metrics or tracing.
+//!
+//! Middlewares only ever see redacted data: the `signature` and `listenKey`
+//! parameters are masked and no headers but the rate-limit ones are passed on.
+//!
+//! ```rust,no_run
+//! use binance::api::*;
+//! use binance::config::*;
+//! use binance::market::*;
+//! use binance::middleware::*;
+//!
+//! #[derive(Debug)]
+//! struct Latency;
+//!
+//! impl Middleware for Latency {
+//!     fn after_receive(&self, request: &RequestInfo, response: &ResponseInfo) {
+//!         println!("{} {} took {:?}", request.method, request.path, response.latency);
+//!     }
+//! }
+//!
+//! let config = Config::default().add_middleware(Latency).add_middleware(LogMiddleware);
+//! let market: Market = Binance::new_with_config(None, None, &config).unwrap();
+//! ```
+
+use crate::client::RateLimitUsage;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+const REDACTED: &str = "<redacted>";
+const SENSITIVE_PARAMETERS: &[&str] = &["signature", "listenKey"];
+
+/// A request about to be sent.
+#[derive(Clone, Debug)]
+pub struct RequestInfo {
+    pub method: String,
+    /// Endpoint path, e.g. `/api/v3/order`.
+    pub path: String,
+    /// Query string or form body, with the sensitive parameters redacted.
+    pub payload: String,
+    pub signed: bool,
+    /// Request weight with the default parameters.
+    pub weight: u32,
+}
+
+/// The outcome of a request, `status` is `None` if no response arrived.
+#[derive(Clone, Debug)]
+pub struct ResponseInfo {
+    pub status: Option<u16>,
+    /// Time to send the request and read the whole response, body included,
+    /// or until the request failed.
+    pub latency: Duration,
+    pub rate_limit_usage: RateLimitUsage,
+}
+
+pub trait Middleware: fmt::Debug + Send + Sync {
+    fn before_send(&self, _request: &RequestInfo) {}
+
+    fn after_receive(&self, _request: &RequestInfo, _response: &ResponseInfo) {}
+}
+
+// Lets the caller keep a handle on a middleware, e.g. to read collected metrics.
+impl<M: Middleware + ?Sized> Middleware for Arc<M> {
+    fn before_send(&self, request: &RequestInfo) {
+        (**self).before_send(request)
+    }
+
+    fn after_receive(&self, request: &RequestInfo, response: &ResponseInfo) {
+        (**self).after_receive(request, response)
+    }
+}
+
+/// Logs requests at debug level and responses at debug, or warn unless successful.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LogMiddleware;
+
+impl Middleware for LogMiddleware {
+    fn before_send(&self, request: &RequestInfo) {
+        log::debug!(
+            "{} {} {} (weight {})",
+            request.method, request.path, request.payload, request.weight
+        );
+    }
+
+    fn after_receive(&self, request: &RequestInfo, response: &ResponseInfo) {
+        let level = match response.status {
+            Some(status) if (200..300).contains(&status) => log::Level::Debug,
+            _ => log::Level::Warn,
+        };
+        log::log!(
+            level,
+            "{} {} -> {:?} in {:?}, used weight {:?}",
+            request.method, request.path, response.status, response.latency,
+            response.rate_limit_usage.used_weight
+        );
+    }
+}
+
+/// Emits a `tracing` event per request and per response.
+#[cfg(feature = "tracing")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TracingMiddleware;
+
+#[cfg(feature = "tracing")]
+impl Middleware for TracingMiddleware {
+    fn before_send(&self, request: &RequestInfo) {
+        tracing::debug!(
+            method = %request.method,
+            path = %request.path,
+            payload = %request.payload,
+            signed = request.signed,
+            weight = request.weight,
+            "binance request"
+        );
+    }
+
+    fn after_receive(&self, request: &RequestInfo, response: &ResponseInfo) {
+        tracing::debug!(
+            method = %request.method,
+            path = %request.path,
+            status = ?response.status,
+            latency_ms = response.latency.as_millis() as u64,
+            used_weight = ?response.rate_limit_usage.used_weight,
+            order_count = ?response.rate_limit_usage.order_count,
+            "binance response"
+        );
+    }
+}
+
+/// Masks the value of the `signature` and `listenKey` parameters.
+pub fn redact(payload: &str) -> String {
+    payload
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _)) if SENSITIVE_PARAMETERS.contains(&key) => format!("{}={}", key, REDACTED),
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
@@ -1,5 +1,15 @@
 use serde::{Deserialize, Serialize};
 
+/// Prices, quantities and balances: `f64`, or `rust_decimal::Decimal` with the
+/// `decimal` feature enabled.
+#[cfg(not(feature = "decimal"))]
+pub type Amount = f64;
+
+/// Prices, quantities and balances: `f64`, or `rust_decimal::Decimal` with the
+/// `decimal` feature enabled.
+#[cfg(feature = "decimal")]
+pub type Amount = rust_decimal::Decimal;
+
 #[derive(Deserialize, Clone)]
 pub struct Empty { }
 
@@ -31,7 +41,7 @@ pub struct RateLimit {
 #[serde(rename_all = "camelCase")]
 pub struct Symbol {
     pub symbol: String,
-    pub status: String,
+    pub status: SymbolStatus,
     pub base_asset: String,
     pub base_asset_precision: u64,
     pub quote_asset: String,
@@ -43,35 +53,110 @@ pub struct Symbol {
     pub filters: Vec<Filters>,
 }
 
+/// Trading status of a symbol, spot and futures share the same values.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum SymbolStatus {
+    PreTrading,
+    Trading,
+    PostTrading,
+    EndOfDay,
+    Halt,
+    AuctionMatch,
+    Break,
+    PendingTrading,
+    PreDelivering,
+    Delivering,
+    Delivered,
+    PreSettle,
+    Settling,
+    Close,
+    Other(String),
+}
+
+impl From<String> for SymbolStatus {
+    fn from(item: String) -> Self {
+        match item.as_str() {
+            "PRE_TRADING" => SymbolStatus::PreTrading,
+            "TRADING" => SymbolStatus::Trading,
+            "POST_TRADING" => SymbolStatus::PostTrading,
+            "END_OF_DAY" => SymbolStatus::EndOfDay,
+            "HALT" => SymbolStatus::Halt,
+            "AUCTION_MATCH" => SymbolStatus::AuctionMatch,
+            "BREAK" => SymbolStatus::Break,
+            "PENDING_TRADING" => SymbolStatus::PendingTrading,
+            "PRE_DELIVERING" => SymbolStatus::PreDelivering,
+            "DELIVERING" => SymbolStatus::Delivering,
+            "DELIVERED" => SymbolStatus::Delivered,
+            "PRE_SETTLE" => SymbolStatus::PreSettle,
+            "SETTLING" => SymbolStatus::Settling,
+            "CLOSE" => SymbolStatus::Close,
+            _ => SymbolStatus::Other(item),
+        }
+    }
+}
+
+impl From<SymbolStatus> for String {
+    fn from(item: SymbolStatus) -> Self {
+        match item {
+            SymbolStatus::PreTrading => String::from("PRE_TRADING"),
+            SymbolStatus::Trading => String::from("TRADING"),
+            SymbolStatus::PostTrading => String::from("POST_TRADING"),
+            SymbolStatus::EndOfDay => String::from("END_OF_DAY"),
+            SymbolStatus::Halt => String::from("HALT"),
+            SymbolStatus::AuctionMatch => String::from("AUCTION_MATCH"),
+            SymbolStatus::Break => String::from("BREAK"),
+            SymbolStatus::PendingTrading => String::from("PENDING_TRADING"),
+            SymbolStatus::PreDelivering => String::from("PRE_DELIVERING"),
+            SymbolStatus::Delivering => String::from("DELIVERING"),
+            SymbolStatus::Delivered => String::from("DELIVERED"),
+            SymbolStatus::PreSettle => String::from("PRE_SETTLE"),
+            SymbolStatus::Settling => String::from("SETTLING"),
+            SymbolStatus::Close => String::from("CLOSE"),
+            SymbolStatus::Other(status) => status,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "filterType")]
 pub enum Filters {
     #[serde(rename = "PRICE_FILTER")]
     #[serde(rename_all = "camelCase")]
     PriceFilter {
-        min_price: String,
-        max_price: String,
-        tick_size: String,
+        #[serde(with = "string_or_float")]
+        min_price: Amount,
+        #[serde(with = "string_or_float")]
+        max_price: Amount,
+        #[serde(with = "string_or_float")]
+        tick_size: Amount,
     },
     #[serde(rename = "PERCENT_PRICE")]
     #[serde(rename_all = "camelCase")]
     PercentPrice {
-        multiplier_up: String,
-        multiplier_down: String,
+        #[serde(with = "string_or_float")]
+        multiplier_up: Amount,
+        #[serde(with = "string_or_float")]
+        multiplier_down: Amount,
         avg_price_mins: Option<f64>,
     },
     #[serde(rename = "LOT_SIZE")]
     #[serde(rename_all = "camelCase")]
     LotSize {
-        min_qty: String,
-        max_qty: String,
-        step_size: String,
+        #[serde(with = "string_or_float")]
+        min_qty: Amount,
+        #[serde(with = "string_or_float")]
+        max_qty: Amount,
+        #[serde(with = "string_or_float")]
+        step_size: Amount,
     },
     #[serde(rename = "MIN_NOTIONAL")]
     #[serde(rename_all = "camelCase")]
     MinNotional {
-        notional: Option<String>,
-        min_notional: Option<String>,
+        #[serde(default, with = "string_or_float_opt")]
+        notional: Option<Amount>,
+        #[serde(default, with = "string_or_float_opt")]
+        min_notional: Option<Amount>,
         apply_to_market: Option<bool>,
         avg_price_mins: Option<f64>,
     },
@@ -89,13 +174,19 @@ pub enum Filters {
     MaxNumIcebergOrders { max_num_iceberg_orders: u16 },
     #[serde(rename = "MAX_POSITION")]
     #[serde(rename_all = "camelCase")]
-    MaxPosition { max_position: String },
+    MaxPosition {
+        #[serde(with = "string_or_float")]
+        max_position: Amount,
+    },
     #[serde(rename = "MARKET_LOT_SIZE")]
     #[serde(rename_all = "camelCase")]
     MarketLotSize {
-        min_qty: String,
-        max_qty: String,
-        step_size: String,
+        #[serde(with = "string_or_float")]
+        min_qty: Amount,
+        #[serde(with = "string_or_float")]
+        max_qty: Amount,
+        #[serde(with = "string_or_float")]
+        step_size: Amount,
     },
 }
 
@@ -116,8 +207,237 @@ pub struct AccountInformation {
 #[serde(rename_all = "camelCase")]
 pub struct Balance {
     pub asset: String,
-    pub free: String,
-    pub locked: String,
+    #[serde(with = "string_or_float")]
+    pub free: Amount,
+    #[serde(with = "string_or_float")]
+    pub locked: Amount,
+}
+
+/// Side of an order, in requests and responses.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum OrderSide {
+    Buy,
+    Sell,
+    Other(String),
+}
+
+impl From<String> for OrderSide {
+    fn from(item: String) -> Self {
+        match item.as_str() {
+            "BUY" => OrderSide::Buy,
+            "SELL" => OrderSide::Sell,
+            _ => OrderSide::Other(item),
+        }
+    }
+}
+
+impl From<OrderSide> for String {
+    fn from(item: OrderSide) -> Self {
+        match item {
+            OrderSide::Buy => String::from("BUY"),
+            OrderSide::Sell => String::from("SELL"),
+            OrderSide::Other(item) => item,
+        }
+    }
+}
+
+/// Type of an order, spot and futures types share the enum.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum OrderType {
+    Limit,
+    Market,
+    StopLoss,
+    StopLossLimit,
+    TakeProfit,
+    TakeProfitLimit,
+    LimitMaker,
+    Stop,
+    StopMarket,
+    TakeProfitMarket,
+    TrailingStopMarket,
+    Liquidation,
+    Other(String),
+}
+
+impl From<String> for OrderType {
+    fn from(item: String) -> Self {
+        match item.as_str() {
+            "LIMIT" => OrderType::Limit,
+            "MARKET" => OrderType::Market,
+            "STOP_LOSS" => OrderType::StopLoss,
+            "STOP_LOSS_LIMIT" => OrderType::StopLossLimit,
+            "TAKE_PROFIT" => OrderType::TakeProfit,
+            "TAKE_PROFIT_LIMIT" => OrderType::TakeProfitLimit,
+            "LIMIT_MAKER" => OrderType::LimitMaker,
+            "STOP" => OrderType::Stop,
+            "STOP_MARKET" => OrderType::StopMarket,
+            "TAKE_PROFIT_MARKET" => OrderType::TakeProfitMarket,
+            "TRAILING_STOP_MARKET" => OrderType::TrailingStopMarket,
+            "LIQUIDATION" => OrderType::Liquidation,
+            _ => OrderType::Other(item),
+        }
+    }
+}
+
+impl From<OrderType> for String {
+    fn from(item: OrderType) -> Self {
+        match item {
+            OrderType::Limit => String::from("LIMIT"),
+            OrderType::Market => String::from("MARKET"),
+            OrderType::StopLoss => String::from("STOP_LOSS"),
+            OrderType::StopLossLimit => String::from("STOP_LOSS_LIMIT"),
+            OrderType::TakeProfit => String::from("TAKE_PROFIT"),
+            OrderType::TakeProfitLimit => String::from("TAKE_PROFIT_LIMIT"),
+            OrderType::LimitMaker => String::from("LIMIT_MAKER"),
+            OrderType::Stop => String::from("STOP"),
+            OrderType::StopMarket => String::from("STOP_MARKET"),
+            OrderType::TakeProfitMarket => String::from("TAKE_PROFIT_MARKET"),
+            OrderType::TrailingStopMarket => String::from("TRAILING_STOP_MARKET"),
+            OrderType::Liquidation => String::from("LIQUIDATION"),
+            OrderType::Other(item) => item,
+        }
+    }
+}
+
+/// How long an order stays on the book. GTX is post-only, futures only.
+#[allow(clippy::all)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum TimeInForce {
+    GTC,
+    IOC,
+    FOK,
+    GTX,
+    Other(String),
+}
+
+impl From<String> for TimeInForce {
+    fn from(item: String) -> Self {
+        match item.as_str() {
+            "GTC" => TimeInForce::GTC,
+            "IOC" => TimeInForce::IOC,
+            "FOK" => TimeInForce::FOK,
+            "GTX" => TimeInForce::GTX,
+            _ => TimeInForce::Other(item),
+        }
+    }
+}
+
+impl From<TimeInForce> for String {
+    fn from(item: TimeInForce) -> Self {
+        match item {
+            TimeInForce::GTC => String::from("GTC"),
+            TimeInForce::IOC => String::from("IOC"),
+            TimeInForce::FOK => String::from("FOK"),
+            TimeInForce::GTX => String::from("GTX"),
+            TimeInForce::Other(item) => item,
+        }
+    }
+}
+
+/// Status of an order.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum OrderStatus {
+    New,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    PendingCancel,
+    Rejected,
+    Expired,
+    ExpiredInMatch,
+    NewInsurance,
+    NewAdl,
+    Other(String),
+}
+
+impl From<String> for OrderStatus {
+    fn from(item: String) -> Self {
+        match item.as_str() {
+            "NEW" => OrderStatus::New,
+            "PARTIALLY_FILLED" => OrderStatus::PartiallyFilled,
+            "FILLED" => OrderStatus::Filled,
+            "CANCELED" => OrderStatus::Canceled,
+            "PENDING_CANCEL" => OrderStatus::PendingCancel,
+            "REJECTED" => OrderStatus::Rejected,
+            "EXPIRED" => OrderStatus::Expired,
+            "EXPIRED_IN_MATCH" => OrderStatus::ExpiredInMatch,
+            "NEW_INSURANCE" => OrderStatus::NewInsurance,
+            "NEW_ADL" => OrderStatus::NewAdl,
+            _ => OrderStatus::Other(item),
+        }
+    }
+}
+
+impl From<OrderStatus> for String {
+    fn from(item: OrderStatus) -> Self {
+        match item {
+            OrderStatus::New => String::from("NEW"),
+            OrderStatus::PartiallyFilled => String::from("PARTIALLY_FILLED"),
+            OrderStatus::Filled => String::from("FILLED"),
+            OrderStatus::Canceled => String::from("CANCELED"),
+            OrderStatus::PendingCancel => String::from("PENDING_CANCEL"),
+            OrderStatus::Rejected => String::from("REJECTED"),
+            OrderStatus::Expired => String::from("EXPIRED"),
+            OrderStatus::ExpiredInMatch => String::from("EXPIRED_IN_MATCH"),
+            OrderStatus::NewInsurance => String::from("NEW_INSURANCE"),
+            OrderStatus::NewAdl => String::from("NEW_ADL"),
+            OrderStatus::Other(item) => item,
+        }
+    }
+}
+
+/// What happened to an order in an execution report.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum ExecutionType {
+    New,
+    Canceled,
+    Replaced,
+    Rejected,
+    Trade,
+    Expired,
+    TradePrevention,
+    Calculated,
+    Amendment,
+    Other(String),
+}
+
+impl From<String> for ExecutionType {
+    fn from(item: String) -> Self {
+        match item.as_str() {
+            "NEW" => ExecutionType::New,
+            "CANCELED" => ExecutionType::Canceled,
+            "REPLACED" => ExecutionType::Replaced,
+            "REJECTED" => ExecutionType::Rejected,
+            "TRADE" => ExecutionType::Trade,
+            "EXPIRED" => ExecutionType::Expired,
+            "TRADE_PREVENTION" => ExecutionType::TradePrevention,
+            "CALCULATED" => ExecutionType::Calculated,
+            "AMENDMENT" => ExecutionType::Amendment,
+            _ => ExecutionType::Other(item),
+        }
+    }
+}
+
+impl From<ExecutionType> for String {
+    fn from(item: ExecutionType) -> Self {
+        match item {
+            ExecutionType::New => String::from("NEW"),
+            ExecutionType::Canceled => String::from("CANCELED"),
+            ExecutionType::Replaced => String::from("REPLACED"),
+            ExecutionType::Rejected => String::from("REJECTED"),
+            ExecutionType::Trade => String::from("TRADE"),
+            ExecutionType::Expired => String::from("EXPIRED"),
+            ExecutionType::TradePrevention => String::from("TRADE_PREVENTION"),
+            ExecutionType::Calculated => String::from("CALCULATED"),
+            ExecutionType::Amendment => String::from("AMENDMENT"),
+            ExecutionType::Other(item) => item,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -128,22 +448,27 @@ pub struct Order {
     pub order_list_id: i64,
     pub client_order_id: String,
     #[serde(with = "string_or_float")]
-    pub price: f64,
-    pub orig_qty: String,
-    pub executed_qty: String,
-    pub cummulative_quote_qty: String,
-    pub status: String,
-    pub time_in_force: String,
+    pub price: Amount,
+    #[serde(with = "string_or_float")]
+    pub orig_qty: Amount,
+    #[serde(with = "string_or_float")]
+    pub executed_qty: Amount,
+    #[serde(with = "string_or_float")]
+    pub cummulative_quote_qty: Amount,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
     #[serde(rename = "type")]
-    pub type_name: String,
-    pub side: String,
+    pub type_name: OrderType,
+    pub side: OrderSide,
+    #[serde(with = "string_or_float")]
+    pub stop_price: Amount,
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
-    pub iceberg_qty: String,
+    pub iceberg_qty: Amount,
     pub time: u64,
     pub update_time: u64,
     pub is_working: bool,
-    pub orig_quote_order_qty: String,
+    #[serde(with = "string_or_float")]
+    pub orig_quote_order_qty: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -164,36 +489,36 @@ pub struct Transaction {
     pub client_order_id: String,
     pub transact_time: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
+    pub cummulative_quote_qty: Amount,
     #[serde(with = "string_or_float", default = "default_stop_price")]
-    pub stop_price: f64,
-    pub status: String,
-    pub time_in_force: String,
+    pub stop_price: Amount,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
     #[serde(rename = "type")]
-    pub type_name: String,
-    pub side: String,
+    pub type_name: OrderType,
+    pub side: OrderSide,
     pub fills: Option<Vec<FillInfo>>,
 }
 
-fn default_stop_price() -> f64 {
-    0.0
+fn default_stop_price() -> Amount {
+    Amount::default()
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct FillInfo {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Amount,
     #[serde(with = "string_or_float")]
-    pub commission: f64,
+    pub commission: Amount,
     pub commission_asset: String,
     pub trade_id: Option<u64>,
 }
@@ -216,13 +541,13 @@ pub struct OrderBook {
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
 pub struct Bids {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Amount,
 }
 
 impl Bids {
-    pub fn new(price: f64, qty: f64) -> Bids {
+    pub fn new(price: Amount, qty: Amount) -> Bids {
         Bids { 
             price, 
             qty,
@@ -233,9 +558,9 @@ impl Bids {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Asks {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -258,14 +583,14 @@ pub enum Prices {
 pub struct SymbolPrice {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AveragePrice {
     pub mins: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -280,18 +605,62 @@ pub enum KlineSummaries {
     AllKlineSummaries(Vec<KlineSummary>),
 }
 
+/// Candlestick interval of a kline request. `OneSecond` is spot only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KlineInterval {
+    OneSecond,
+    OneMinute,
+    ThreeMinutes,
+    FiveMinutes,
+    FifteenMinutes,
+    ThirtyMinutes,
+    OneHour,
+    TwoHours,
+    FourHours,
+    SixHours,
+    EightHours,
+    TwelveHours,
+    OneDay,
+    ThreeDays,
+    OneWeek,
+    OneMonth,
+}
+
+impl From<KlineInterval> for String {
+    fn from(item: KlineInterval) -> Self {
+        match item {
+            KlineInterval::OneSecond => String::from("1s"),
+            KlineInterval::OneMinute => String::from("1m"),
+            KlineInterval::ThreeMinutes => String::from("3m"),
+            KlineInterval::FiveMinutes => String::from("5m"),
+            KlineInterval::FifteenMinutes => String::from("15m"),
+            KlineInterval::ThirtyMinutes => String::from("30m"),
+            KlineInterval::OneHour => String::from("1h"),
+            KlineInterval::TwoHours => String::from("2h"),
+            KlineInterval::FourHours => String::from("4h"),
+            KlineInterval::SixHours => String::from("6h"),
+            KlineInterval::EightHours => String::from("8h"),
+            KlineInterval::TwelveHours => String::from("12h"),
+            KlineInterval::OneDay => String::from("1d"),
+            KlineInterval::ThreeDays => String::from("3d"),
+            KlineInterval::OneWeek => String::from("1w"),
+            KlineInterval::OneMonth => String::from("1M"),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Tickers {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub bid_price: f64,
+    pub bid_price: Amount,
     #[serde(with = "string_or_float")]
-    pub bid_qty: f64,
+    pub bid_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub ask_price: f64,
+    pub ask_price: Amount,
     #[serde(with = "string_or_float")]
-    pub ask_qty: f64,
+    pub ask_qty: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -299,10 +668,11 @@ pub struct Tickers {
 pub struct TradeHistory {
     pub id: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
+    #[serde(with = "string_or_float")]
+    pub qty: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
-    pub commission: String,
+    pub commission: Amount,
     pub commission_asset: String,
     pub time: u64,
     pub is_buyer: bool,
@@ -314,25 +684,28 @@ pub struct TradeHistory {
 #[serde(rename_all = "camelCase")]
 pub struct PriceStats {
     pub symbol: String,
-    pub price_change: String,
-    pub price_change_percent: String,
-    pub weighted_avg_price: String,
     #[serde(with = "string_or_float")]
-    pub prev_close_price: f64,
+    pub price_change: Amount,
+    #[serde(with = "string_or_float")]
+    pub price_change_percent: Amount,
+    #[serde(with = "string_or_float")]
+    pub weighted_avg_price: Amount,
+    #[serde(with = "string_or_float")]
+    pub prev_close_price: Amount,
     #[serde(with = "string_or_float")]
-    pub last_price: f64,
+    pub last_price: Amount,
     #[serde(with = "string_or_float")]
-    pub bid_price: f64,
+    pub bid_price: Amount,
     #[serde(with = "string_or_float")]
-    pub ask_price: f64,
+    pub ask_price: Amount,
     #[serde(with = "string_or_float")]
-    pub open_price: f64,
+    pub open_price: Amount,
     #[serde(with = "string_or_float")]
-    pub high_price: f64,
+    pub high_price: Amount,
     #[serde(with = "string_or_float")]
-    pub low_price: f64,
+    pub low_price: Amount,
     #[serde(with = "string_or_float")]
-    pub volume: f64,
+    pub volume: Amount,
     pub open_time: u64,
     pub close_time: u64,
     pub first_id: i64,
@@ -340,6 +713,157 @@ pub struct PriceStats {
     pub count: u64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountList {
+    pub sub_accounts: Vec<SubAccountInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountInfo {
+    pub email: String,
+    pub is_freeze: bool,
+    pub create_time: u64,
+    #[serde(default)]
+    pub is_managed_sub_account: bool,
+    #[serde(default)]
+    pub is_asset_management_sub_account: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountAssets {
+    pub balances: Vec<SubAccountBalance>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountBalance {
+    pub asset: String,
+    #[serde(with = "string_or_float")]
+    pub free: Amount,
+    #[serde(with = "string_or_float")]
+    pub locked: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountFuturesAccount {
+    pub email: String,
+    pub asset: String,
+    pub assets: Vec<SubAccountFuturesAsset>,
+    pub can_deposit: bool,
+    pub can_trade: bool,
+    pub can_withdraw: bool,
+    pub fee_tier: u8,
+    #[serde(with = "string_or_float")]
+    pub max_withdraw_amount: Amount,
+    #[serde(with = "string_or_float")]
+    pub total_initial_margin: Amount,
+    #[serde(with = "string_or_float")]
+    pub total_maintenance_margin: Amount,
+    #[serde(with = "string_or_float")]
+    pub total_margin_balance: Amount,
+    #[serde(with = "string_or_float")]
+    pub total_open_order_initial_margin: Amount,
+    #[serde(with = "string_or_float")]
+    pub total_position_initial_margin: Amount,
+    #[serde(with = "string_or_float")]
+    pub total_unrealized_profit: Amount,
+    #[serde(with = "string_or_float")]
+    pub total_wallet_balance: Amount,
+    pub update_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountFuturesAsset {
+    pub asset: String,
+    #[serde(with = "string_or_float")]
+    pub initial_margin: Amount,
+    #[serde(with = "string_or_float")]
+    pub maintenance_margin: Amount,
+    #[serde(with = "string_or_float")]
+    pub margin_balance: Amount,
+    #[serde(with = "string_or_float")]
+    pub max_withdraw_amount: Amount,
+    #[serde(with = "string_or_float")]
+    pub open_order_initial_margin: Amount,
+    #[serde(with = "string_or_float")]
+    pub position_initial_margin: Amount,
+    #[serde(with = "string_or_float")]
+    pub unrealized_profit: Amount,
+    #[serde(with = "string_or_float")]
+    pub wallet_balance: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountFuturesPosition {
+    pub symbol: String,
+    #[serde(with = "string_or_float")]
+    pub entry_price: Amount,
+    #[serde(with = "string_or_float")]
+    pub leverage: f64,
+    #[serde(with = "string_or_float")]
+    pub max_notional: Amount,
+    #[serde(with = "string_or_float")]
+    pub liquidation_price: Amount,
+    #[serde(with = "string_or_float")]
+    pub mark_price: Amount,
+    #[serde(with = "string_or_float")]
+    pub position_amount: Amount,
+    #[serde(with = "string_or_float")]
+    pub unrealized_profit: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountTransferResponse {
+    pub tran_id: u64,
+    pub client_tran_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountTransferHistory {
+    pub result: Vec<SubAccountTransfer>,
+    pub total_count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountTransfer {
+    pub tran_id: u64,
+    pub from_email: String,
+    pub to_email: String,
+    pub asset: String,
+    #[serde(with = "string_or_float")]
+    pub amount: Amount,
+    pub create_time_stamp: u64,
+    pub from_account_type: String,
+    pub to_account_type: String,
+    pub status: String,
+    pub client_tran_id: Option<String>,
+}
+
+/// Sent on a user data stream once its listen key expired, nothing more is
+/// sent on it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenKeyExpiredEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    // Not sent by every market.
+    #[serde(default)]
+    pub listen_key: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountUpdateEvent {
@@ -370,10 +894,98 @@ pub struct AccountUpdateEvent {
 pub struct EventBalance {
     #[serde(rename = "a")]
     pub asset: String,
-    #[serde(rename = "f")]
-    pub free: String,
+    #[serde(rename = "f", with = "string_or_float")]
+    pub free: Amount,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub locked: Amount,
+}
+
+/// Balances changed by an account update, only the assets that changed.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountPositionEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "u")]
+    pub last_update_time: u64,
+
+    #[serde(rename = "B")]
+    pub balances: Vec<EventBalance>,
+}
+
+/// Deposit, withdrawal or transfer between accounts.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BalanceUpdateEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "a")]
+    pub asset: String,
+
+    #[serde(rename = "d", with = "string_or_float")]
+    pub balance_delta: Amount,
+
+    #[serde(rename = "T")]
+    pub clear_time: u64,
+}
+
+/// Status of an order list, such as an OCO, sent along with the execution
+/// reports of its orders.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListStatusEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "g")]
+    pub order_list_id: i64,
+
+    /// `OCO` or `OTO`.
+    #[serde(rename = "c")]
+    pub contingency_type: String,
+
+    /// `RESPONSE`, `EXEC_STARTED`, `UPDATED` or `ALL_DONE`.
     #[serde(rename = "l")]
-    pub locked: String,
+    pub list_status_type: String,
+
+    /// `EXECUTING`, `ALL_DONE` or `REJECT`.
+    #[serde(rename = "L")]
+    pub list_order_status: String,
+
+    #[serde(rename = "r")]
+    pub list_reject_reason: String,
+
+    #[serde(rename = "C")]
+    pub list_client_order_id: String,
+
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+
+    #[serde(rename = "O")]
+    pub orders: Vec<ListStatusOrder>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListStatusOrder {
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "i")]
+    pub order_id: u64,
+
+    #[serde(rename = "c")]
+    pub client_order_id: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -392,19 +1004,19 @@ pub struct OrderTradeEvent {
     pub new_client_order_id: String,
 
     #[serde(rename = "S")]
-    pub side: String,
+    pub side: OrderSide,
 
     #[serde(rename = "o")]
-    pub order_type: String,
+    pub order_type: OrderType,
 
     #[serde(rename = "f")]
-    pub time_in_force: String,
+    pub time_in_force: TimeInForce,
 
-    #[serde(rename = "q")]
-    pub qty: String,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub qty: Amount,
 
-    #[serde(rename = "p")]
-    pub price: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: Amount,
 
     #[serde(skip, rename = "P")]
     pub p_ignore: String,
@@ -419,10 +1031,10 @@ pub struct OrderTradeEvent {
     pub c_ignore: Option<String>,
 
     #[serde(rename = "x")]
-    pub execution_type: String,
+    pub execution_type: ExecutionType,
 
     #[serde(rename = "X")]
-    pub order_status: String,
+    pub order_status: OrderStatus,
 
     #[serde(rename = "r")]
     pub order_reject_reason: String,
@@ -430,17 +1042,17 @@ pub struct OrderTradeEvent {
     #[serde(rename = "i")]
     pub order_id: u64,
 
-    #[serde(rename = "l")]
-    pub qty_last_filled_trade: String,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub qty_last_filled_trade: Amount,
 
-    #[serde(rename = "z")]
-    pub accumulated_qty_filled_trades: String,
+    #[serde(rename = "z", with = "string_or_float")]
+    pub accumulated_qty_filled_trades: Amount,
 
-    #[serde(rename = "L")]
-    pub price_last_filled_trade: String,
+    #[serde(rename = "L", with = "string_or_float")]
+    pub price_last_filled_trade: Amount,
 
-    #[serde(rename = "n")]
-    pub commission: String,
+    #[serde(rename = "n", with = "string_or_float")]
+    pub commission: Amount,
 
     #[serde(skip, rename = "N")]
     pub asset_commisioned: Option<String>,
@@ -486,11 +1098,11 @@ pub struct AggrTradesEvent {
     #[serde(rename = "a")]
     pub aggregated_trade_id: u64,
 
-    #[serde(rename = "p")]
-    pub price: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: Amount,
 
-    #[serde(rename = "q")]
-    pub qty: String,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub qty: Amount,
 
     #[serde(rename = "f")]
     pub first_break_trade_id: u64,
@@ -530,11 +1142,11 @@ pub struct TradeEvent {
     #[serde(rename = "t")]
     pub trade_id: u64,
 
-    #[serde(rename = "p")]
-    pub price: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: Amount,
 
-    #[serde(rename = "q")]
-    pub qty: String,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub qty: Amount,
 
     #[serde(rename = "b")]
     pub buyer_order_id: u64,
@@ -561,17 +1173,17 @@ pub struct BookTickerEvent {
     #[serde(rename = "s")]
     pub symbol: String,
 
-    #[serde(rename = "b")]
-    pub best_bid: String,
+    #[serde(rename = "b", with = "string_or_float")]
+    pub best_bid: Amount,
 
-    #[serde(rename = "B")]
-    pub best_bid_qty: String,
+    #[serde(rename = "B", with = "string_or_float")]
+    pub best_bid_qty: Amount,
 
-    #[serde(rename = "a")]
-    pub best_ask: String,
+    #[serde(rename = "a", with = "string_or_float")]
+    pub best_ask: Amount,
 
-    #[serde(rename = "A")]
-    pub best_ask_qty: String,
+    #[serde(rename = "A", with = "string_or_float")]
+    pub best_ask_qty: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -586,50 +1198,50 @@ pub struct DayTickerEvent {
     #[serde(rename = "s")]
     pub symbol: String,
 
-    #[serde(rename = "p")]
-    pub price_change: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price_change: Amount,
 
-    #[serde(rename = "P")]
-    pub price_change_percent: String,
+    #[serde(rename = "P", with = "string_or_float")]
+    pub price_change_percent: Amount,
 
-    #[serde(rename = "w")]
-    pub average_price: String,
+    #[serde(rename = "w", with = "string_or_float")]
+    pub average_price: Amount,
 
-    #[serde(rename = "x")]
-    pub prev_close: String,
+    #[serde(rename = "x", with = "string_or_float")]
+    pub prev_close: Amount,
 
-    #[serde(rename = "c")]
-    pub current_close: String,
+    #[serde(rename = "c", with = "string_or_float")]
+    pub current_close: Amount,
 
-    #[serde(rename = "Q")]
-    pub current_close_qty: String,
+    #[serde(rename = "Q", with = "string_or_float")]
+    pub current_close_qty: Amount,
 
-    #[serde(rename = "b")]
-    pub best_bid: String,
+    #[serde(rename = "b", with = "string_or_float")]
+    pub best_bid: Amount,
 
-    #[serde(rename = "B")]
-    pub best_bid_qty: String,
+    #[serde(rename = "B", with = "string_or_float")]
+    pub best_bid_qty: Amount,
 
-    #[serde(rename = "a")]
-    pub best_ask: String,
+    #[serde(rename = "a", with = "string_or_float")]
+    pub best_ask: Amount,
 
-    #[serde(rename = "A")]
-    pub best_ask_qty: String,
+    #[serde(rename = "A", with = "string_or_float")]
+    pub best_ask_qty: Amount,
 
-    #[serde(rename = "o")]
-    pub open: String,
+    #[serde(rename = "o", with = "string_or_float")]
+    pub open: Amount,
 
-    #[serde(rename = "h")]
-    pub high: String,
+    #[serde(rename = "h", with = "string_or_float")]
+    pub high: Amount,
 
-    #[serde(rename = "l")]
-    pub low: String,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub low: Amount,
 
-    #[serde(rename = "v")]
-    pub volume: String,
+    #[serde(rename = "v", with = "string_or_float")]
+    pub volume: Amount,
 
-    #[serde(rename = "q")]
-    pub quote_volume: String,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub quote_volume: Amount,
 
     #[serde(rename = "O")]
     pub open_time: u64,
@@ -667,25 +1279,105 @@ pub struct KlineEvent {
 pub struct KlineSummary {
     pub open_time: i64,
 
-    pub open: f64,
+    pub open: Amount,
 
-    pub high: f64,
+    pub high: Amount,
 
-    pub low: f64,
+    pub low: Amount,
 
-    pub close: f64,
+    pub close: Amount,
 
-    pub volume: f64,
+    pub volume: Amount,
 
     pub close_time: i64,
 
-    pub quote_asset_volume: f64,
+    pub quote_asset_volume: Amount,
 
     pub number_of_trades: i64,
 
-    pub taker_buy_base_asset_volume: f64,
+    pub taker_buy_base_asset_volume: Amount,
 
-    pub taker_buy_quote_asset_volume: f64,
+    pub taker_buy_quote_asset_volume: Amount,
+}
+
+// A kline row as returned by the REST API: a JSON array rather than an object.
+#[derive(Deserialize)]
+pub(crate) struct KlineRow(
+    i64,
+    #[serde(with = "string_or_float")] Amount,
+    #[serde(with = "string_or_float")] Amount,
+    #[serde(with = "string_or_float")] Amount,
+    #[serde(with = "string_or_float")] Amount,
+    #[serde(with = "string_or_float")] Amount,
+    i64,
+    #[serde(with = "string_or_float")] Amount,
+    i64,
+    #[serde(with = "string_or_float")] Amount,
+    #[serde(with = "string_or_float")] Amount,
+    serde::de::IgnoredAny,
+);
+
+impl From<KlineRow> for KlineSummary {
+    fn from(row: KlineRow) -> Self {
+        KlineSummary {
+            open_time: row.0,
+            open: row.1,
+            high: row.2,
+            low: row.3,
+            close: row.4,
+            volume: row.5,
+            close_time: row.6,
+            quote_asset_volume: row.7,
+            number_of_trades: row.8,
+            taker_buy_base_asset_volume: row.9,
+            taker_buy_quote_asset_volume: row.10,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Trades {
+    AllTrades(Vec<Trade>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Trade {
+    pub id: u64,
+    pub is_buyer_maker: bool,
+    #[serde(with = "string_or_float")]
+    pub price: Amount,
+    #[serde(with = "string_or_float")]
+    pub qty: Amount,
+    #[serde(with = "string_or_float")]
+    pub quote_qty: Amount,
+    pub time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum AggTrades {
+    AllAggTrades(Vec<AggTrade>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AggTrade {
+    #[serde(rename = "T")]
+    pub time: u64,
+    #[serde(rename = "a")]
+    pub agg_id: u64,
+    #[serde(rename = "f")]
+    pub first_id: u64,
+    #[serde(rename = "l")]
+    pub last_id: u64,
+    #[serde(rename = "m")]
+    pub maker: bool,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: Amount,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub qty: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -709,20 +1401,20 @@ pub struct Kline {
     #[serde(rename = "L")]
     pub last_trade_id: i32,
 
-    #[serde(rename = "o")]
-    pub open: String,
+    #[serde(rename = "o", with = "string_or_float")]
+    pub open: Amount,
 
-    #[serde(rename = "c")]
-    pub close: String,
+    #[serde(rename = "c", with = "string_or_float")]
+    pub close: Amount,
 
-    #[serde(rename = "h")]
-    pub high: String,
+    #[serde(rename = "h", with = "string_or_float")]
+    pub high: Amount,
 
-    #[serde(rename = "l")]
-    pub low: String,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub low: Amount,
 
-    #[serde(rename = "v")]
-    pub volume: String,
+    #[serde(rename = "v", with = "string_or_float")]
+    pub volume: Amount,
 
     #[serde(rename = "n")]
     pub number_of_trades: i32,
@@ -730,14 +1422,14 @@ pub struct Kline {
     #[serde(rename = "x")]
     pub is_final_bar: bool,
 
-    #[serde(rename = "q")]
-    pub quote_volume: String,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub quote_volume: Amount,
 
-    #[serde(rename = "V")]
-    pub active_buy_volume: String,
+    #[serde(rename = "V", with = "string_or_float")]
+    pub active_buy_volume: Amount,
 
-    #[serde(rename = "Q")]
-    pub active_volume_buy_quote: String,
+    #[serde(rename = "Q", with = "string_or_float")]
+    pub active_volume_buy_quote: Amount,
 
     #[serde(skip, rename = "B")]
     pub ignore_me: String,
@@ -773,7 +1465,9 @@ pub struct DepthOrderBookEvent {
 }
 
 pub(crate) mod string_or_float {
+    use std::convert::TryFrom;
     use std::fmt;
+    use std::str::FromStr;
 
     use serde::{de, Serializer, Deserialize, Deserializer};
 
@@ -785,9 +1479,13 @@ pub(crate) mod string_or_float {
         serializer.collect_str(value)
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<f64, D::Error>
+    // Generic so the same helper fills both `f64` and `Amount` fields.
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
     where
         D: Deserializer<'de>,
+        T: FromStr + TryFrom<f64>,
+        T::Err: fmt::Display,
+        <T as TryFrom<f64>>::Error: fmt::Display,
     {
         #[derive(Deserialize)]
         #[serde(untagged)]
@@ -798,15 +1496,17 @@ pub(crate) mod string_or_float {
 
         match StringOrFloat::deserialize(deserializer)? {
             StringOrFloat::String(s) => s.parse().map_err(de::Error::custom),
-            StringOrFloat::Float(i) => Ok(i),
+            StringOrFloat::Float(i) => T::try_from(i).map_err(de::Error::custom),
         }
     }
 }
 
 pub(crate) mod string_or_float_opt {
+    use std::convert::TryFrom;
     use std::fmt;
+    use std::str::FromStr;
 
-    use serde::{Serializer, Deserialize, Deserializer};
+    use serde::{Serializer, Deserializer};
 
     pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -819,17 +1519,13 @@ pub(crate) mod string_or_float_opt {
         }
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
     where
         D: Deserializer<'de>,
+        T: FromStr + TryFrom<f64>,
+        T::Err: fmt::Display,
+        <T as TryFrom<f64>>::Error: fmt::Display,
     {
-        #[derive(Deserialize)]
-        #[serde(untagged)]
-        enum StringOrFloat {
-            String(String),
-            Float(f64),
-        }
-
         Ok(Some(crate::model::string_or_float::deserialize(deserializer)?))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,569 @@
+//! Paper trading: orders are filled against market data instead of being
+//! sent to the exchange.
+//!
+//! A [`PaperAccount`] implements [`Trading`], so a strategy switches between
+//! paper and live trading by the account it is given. Market data comes from
+//! the events of a [`WebSockets`](crate::websockets::WebSockets) connection,
+//! live or replayed:
+//!
+//! ```rust,no_run
+//! use binance::account::*;
+//! use binance::api::*;
+//! use binance::model::Amount;
+//! use binance::paper::*;
+//! use binance::trading::*;
+//! use binance::websockets::*;
+//! use std::sync::atomic::AtomicBool;
+//!
+//! let account: Account = Binance::new(Some("key".into()), Some("secret".into())).unwrap();
+//! let paper = PaperAccount::spot(&account.get_account().unwrap());
+//! paper.add_symbol("BTCUSDT", "BTC", "USDT");
+//!
+//! let max_ask: Amount = "19000".parse().unwrap();
+//! let qty: Amount = "0.01".parse().unwrap();
+//! let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+//!     paper.handle_event(&event);
+//!     if let WebsocketEvent::BookTicker(ticker) = event {
+//!         if ticker.best_ask < max_ask {
+//!             paper.market_buy("BTCUSDT", qty)?;
+//!         }
+//!     }
+//!     Ok(())
+//! });
+//! web_socket.connect("btcusdt@bookTicker").unwrap();
+//! web_socket.event_loop(&AtomicBool::new(true)).unwrap();
+//! ```
+//!
+//! Market orders take the best bid or ask, or the last trade price until a
+//! book ticker was received. Limit orders that cross the book fill at once as
+//! taker, the others rest until the opposite side of the book reaches their
+//! price or a trade prints through it, and then fill at their price as maker.
+//! Orders always fill in full, the depth of the book isn't simulated.
+//!
+//! Spot orders lock their funds until filled or canceled, buy commissions are
+//! paid in the base asset and sell commissions in the quote asset. Futures
+//! fills update a net position, and the realized profit and commissions go to
+//! the balance of the quote asset, without margin checks or liquidations.
+
+use crate::errors::*;
+use crate::model::{AccountInformation, Amount, Balance, OrderSide, OrderStatus, OrderType};
+use crate::trading::{OrderReport, Trading};
+use crate::util::lock;
+use crate::websockets::WebsocketEvent;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+/// Net futures position in one symbol.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PaperPosition {
+    pub symbol: String,
+    /// Negative when short.
+    pub amount: Amount,
+    pub entry_price: Amount,
+    /// Profit of the closed part of the position, before commissions.
+    pub realized_pnl: Amount,
+}
+
+impl PaperPosition {
+    // Adds a fill of `qty` at `price`, negative when selling, and returns the
+    // realized profit.
+    fn apply(&mut self, qty: Amount, price: Amount) -> Amount {
+        let zero = Amount::default();
+        let mut realized = zero;
+        if self.amount == zero || (self.amount > zero) == (qty > zero) {
+            let amount = self.amount + qty;
+            self.entry_price = (self.entry_price * self.amount + price * qty) / amount;
+            self.amount = amount;
+        } else {
+            let closed = min(self.amount.abs(), qty.abs());
+            realized = if self.amount > zero {
+                (price - self.entry_price) * closed
+            } else {
+                (self.entry_price - price) * closed
+            };
+            self.amount += qty;
+            if self.amount == zero {
+                self.entry_price = zero;
+            } else if (self.amount > zero) == (qty > zero) {
+                // Flipped sides, what is left was opened at this price.
+                self.entry_price = price;
+            }
+        }
+        self.realized_pnl += realized;
+        realized
+    }
+}
+
+/// One fill of a paper order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaperTrade {
+    pub symbol: String,
+    pub order_id: u64,
+    pub side: OrderSide,
+    pub price: Amount,
+    pub qty: Amount,
+    pub commission: Amount,
+    pub commission_asset: String,
+    pub is_maker: bool,
+}
+
+/// A simulated spot or futures account, clones share the same account.
+#[derive(Debug, Clone)]
+pub struct PaperAccount {
+    state: Arc<Mutex<State>>,
+}
+
+impl PaperAccount {
+    /// Simulates a spot account, starting from the balances and the maker and
+    /// taker commissions of `account`.
+    pub fn spot(account: &AccountInformation) -> Self {
+        PaperAccount::new(Market::Spot, account)
+    }
+
+    /// Simulates a futures account, the balances of `account` are the wallet
+    /// balances of the margin assets.
+    pub fn futures(account: &AccountInformation) -> Self {
+        PaperAccount::new(Market::Futures, account)
+    }
+
+    fn new(market: Market, account: &AccountInformation) -> Self {
+        let balances = account
+            .balances
+            .iter()
+            .map(|balance| (balance.asset.clone(), balance.clone()))
+            .collect();
+        let state = State {
+            market,
+            maker_rate: rate(account.maker_commission),
+            taker_rate: rate(account.taker_commission),
+            symbols: HashMap::new(),
+            balances,
+            positions: BTreeMap::new(),
+            quotes: HashMap::new(),
+            orders: BTreeMap::new(),
+            trades: Vec::new(),
+            next_order_id: 1,
+        };
+        PaperAccount {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Makes `symbol` tradable, orders on unknown symbols are rejected.
+    pub fn add_symbol(&self, symbol: &str, base_asset: &str, quote_asset: &str) {
+        lock(&self.state)
+            .symbols
+            .insert(symbol.into(), (base_asset.into(), quote_asset.into()));
+    }
+
+    /// Updates the market with book tickers, day tickers, trades, aggregated
+    /// trades and klines, and fills the resting orders they reach. Other
+    /// events are ignored.
+    pub fn handle_event(&self, event: &WebsocketEvent) {
+        let mut state = lock(&self.state);
+        match event {
+            WebsocketEvent::BookTicker(ticker) => {
+                state.update_book(&ticker.symbol, ticker.best_bid, ticker.best_ask);
+            }
+            WebsocketEvent::DayTicker(ticker) => {
+                state.update_book(&ticker.symbol, ticker.best_bid, ticker.best_ask);
+            }
+            WebsocketEvent::DayTickerAll(tickers) => {
+                for ticker in tickers {
+                    state.update_book(&ticker.symbol, ticker.best_bid, ticker.best_ask);
+                }
+            }
+            WebsocketEvent::Trade(trade) => state.update_trade(&trade.symbol, trade.price),
+            WebsocketEvent::AggrTrades(trade) => state.update_trade(&trade.symbol, trade.price),
+            WebsocketEvent::Kline(kline) => state.update_trade(&kline.symbol, kline.kline.close),
+            _ => (),
+        }
+    }
+
+    /// Free and locked amount of `asset`, zero if the account never held it.
+    /// On futures, the free amount is the wallet balance.
+    pub fn balance(&self, asset: &str) -> Balance {
+        lock(&self.state)
+            .balances
+            .get(asset)
+            .cloned()
+            .unwrap_or_else(|| empty_balance(asset))
+    }
+
+    pub fn balances(&self) -> Vec<Balance> {
+        lock(&self.state).balances.values().cloned().collect()
+    }
+
+    /// Futures position in `symbol`, flat if it never traded.
+    pub fn position(&self, symbol: &str) -> PaperPosition {
+        lock(&self.state)
+            .positions
+            .get(symbol)
+            .cloned()
+            .unwrap_or_else(|| flat_position(symbol))
+    }
+
+    pub fn open_orders(&self, symbol: &str) -> Vec<OrderReport> {
+        lock(&self.state)
+            .orders
+            .values()
+            .filter(|order| order.is_open() && order.report.symbol == symbol)
+            .map(|order| order.report.clone())
+            .collect()
+    }
+
+    /// Latest state of an order, open or not.
+    pub fn order_status(&self, order_id: u64) -> Option<OrderReport> {
+        lock(&self.state)
+            .orders
+            .get(&order_id)
+            .map(|order| order.report.clone())
+    }
+
+    /// Every fill, oldest first.
+    pub fn trades(&self) -> Vec<PaperTrade> {
+        lock(&self.state).trades.clone()
+    }
+}
+
+impl Trading for PaperAccount {
+    fn limit_buy(&self, symbol: &str, qty: Amount, price: Amount) -> Result<OrderReport> {
+        lock(&self.state).place(symbol, OrderSide::Buy, qty, Some(price))
+    }
+
+    fn limit_sell(&self, symbol: &str, qty: Amount, price: Amount) -> Result<OrderReport> {
+        lock(&self.state).place(symbol, OrderSide::Sell, qty, Some(price))
+    }
+
+    fn market_buy(&self, symbol: &str, qty: Amount) -> Result<OrderReport> {
+        lock(&self.state).place(symbol, OrderSide::Buy, qty, None)
+    }
+
+    fn market_sell(&self, symbol: &str, qty: Amount) -> Result<OrderReport> {
+        lock(&self.state).place(symbol, OrderSide::Sell, qty, None)
+    }
+
+    fn cancel_order(&self, symbol: &str, order_id: u64) -> Result<()> {
+        let mut state = lock(&self.state);
+        match state.orders.get(&order_id) {
+            Some(order) if order.is_open() && order.report.symbol == symbol => {
+                state.cancel(order_id);
+                Ok(())
+            }
+            _ => Err(BinanceError::Api {
+                code: -2011,
+                msg: "Unknown order sent.".into(),
+            }),
+        }
+    }
+
+    fn cancel_all_open_orders(&self, symbol: &str) -> Result<()> {
+        let mut state = lock(&self.state);
+        let open: Vec<u64> = state
+            .orders
+            .values()
+            .filter(|order| order.is_open() && order.report.symbol == symbol)
+            .map(|order| order.report.order_id)
+            .collect();
+        for order_id in open {
+            state.cancel(order_id);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Market {
+    Spot,
+    Futures,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Quote {
+    bid: Option<Amount>,
+    ask: Option<Amount>,
+    last: Option<Amount>,
+}
+
+impl Quote {
+    // The last trade stands in for the book until a ticker arrived.
+    fn bid(&self) -> Option<Amount> {
+        self.bid.or(self.last)
+    }
+
+    fn ask(&self) -> Option<Amount> {
+        self.ask.or(self.last)
+    }
+}
+
+#[derive(Debug)]
+struct PaperOrder {
+    report: OrderReport,
+    price: Amount,
+    // Funds locked by a spot order until it fills or is canceled.
+    reserved: Amount,
+}
+
+impl PaperOrder {
+    fn is_open(&self) -> bool {
+        self.report.status == OrderStatus::New
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    market: Market,
+    maker_rate: Amount,
+    taker_rate: Amount,
+    // Base and quote asset of each symbol.
+    symbols: HashMap<String, (String, String)>,
+    balances: BTreeMap<String, Balance>,
+    positions: BTreeMap<String, PaperPosition>,
+    quotes: HashMap<String, Quote>,
+    orders: BTreeMap<u64, PaperOrder>,
+    trades: Vec<PaperTrade>,
+    next_order_id: u64,
+}
+
+impl State {
+    fn place(
+        &mut self, symbol: &str, side: OrderSide, qty: Amount, price: Option<Amount>,
+    ) -> Result<OrderReport> {
+        let zero = Amount::default();
+        let (base, quote_asset) = match self.symbols.get(symbol) {
+            Some(assets) => assets.clone(),
+            None => return Err(api_error(-1121, "Invalid symbol.")),
+        };
+        if qty <= zero {
+            return Err(api_error(-1013, "Invalid quantity."));
+        }
+        if price.is_some_and(|price| price <= zero) {
+            return Err(api_error(-1013, "Invalid price."));
+        }
+
+        let quote = self.quotes.get(symbol).cloned().unwrap_or_default();
+        let best = match side {
+            OrderSide::Buy => quote.ask(),
+            _ => quote.bid(),
+        };
+        let taker_price = match price {
+            None if best.is_none() => bail!(format!("No market data for {}", symbol)),
+            None => best,
+            Some(price) => best.filter(|&best| crosses(&side, price, best)),
+        };
+
+        let mut reserved = zero;
+        if self.market == Market::Spot {
+            let (asset, amount) = match side {
+                OrderSide::Buy => (quote_asset, qty * price.or(taker_price).unwrap_or(zero)),
+                _ => (base, qty),
+            };
+            let balance = self.balance_mut(&asset);
+            if balance.free < amount {
+                return Err(api_error(
+                    -2010,
+                    "Account has insufficient balance for requested action.",
+                ));
+            }
+            balance.free -= amount;
+            balance.locked += amount;
+            reserved = amount;
+        }
+
+        let order_id = self.next_order_id;
+        self.next_order_id += 1;
+        let order = PaperOrder {
+            report: OrderReport {
+                symbol: symbol.into(),
+                order_id,
+                client_order_id: format!("paper-{}", order_id),
+                side,
+                order_type: if price.is_some() { OrderType::Limit } else { OrderType::Market },
+                status: OrderStatus::New,
+                orig_qty: qty,
+                executed_qty: zero,
+                cumulative_quote_qty: zero,
+            },
+            price: price.unwrap_or(zero),
+            reserved,
+        };
+        self.orders.insert(order_id, order);
+
+        if let Some(taker_price) = taker_price {
+            self.fill(order_id, taker_price, false);
+        }
+        Ok(self.orders[&order_id].report.clone())
+    }
+
+    fn update_book(&mut self, symbol: &str, bid: Amount, ask: Amount) {
+        let quote = self.quotes.entry(symbol.into()).or_default();
+        quote.bid = Some(bid);
+        quote.ask = Some(ask);
+        self.fill_resting(symbol, |side, price| match side {
+            OrderSide::Buy => crosses(side, price, ask),
+            _ => crosses(side, price, bid),
+        });
+    }
+
+    fn update_trade(&mut self, symbol: &str, trade_price: Amount) {
+        self.quotes.entry(symbol.into()).or_default().last = Some(trade_price);
+        // A trade at the price of an order may have been ahead of it in the
+        // queue, only trades through the price fill it.
+        self.fill_resting(symbol, |side, price| match side {
+            OrderSide::Buy => trade_price < price,
+            _ => trade_price > price,
+        });
+    }
+
+    fn fill_resting<F>(&mut self, symbol: &str, reached: F)
+    where
+        F: Fn(&OrderSide, Amount) -> bool,
+    {
+        let reached: Vec<(u64, Amount)> = self
+            .orders
+            .values()
+            .filter(|order| order.is_open() && order.report.symbol == symbol)
+            .filter(|order| order.report.order_type == OrderType::Limit)
+            .filter(|order| reached(&order.report.side, order.price))
+            .map(|order| (order.report.order_id, order.price))
+            .collect();
+        for (order_id, price) in reached {
+            self.fill(order_id, price, true);
+        }
+    }
+
+    fn fill(&mut self, order_id: u64, price: Amount, is_maker: bool) {
+        let rate = if is_maker { self.maker_rate } else { self.taker_rate };
+        let order = match self.orders.get_mut(&order_id) {
+            Some(order) => order,
+            None => return,
+        };
+        let qty = order.report.orig_qty;
+        let notional = qty * price;
+        order.report.status = OrderStatus::Filled;
+        order.report.executed_qty = qty;
+        order.report.cumulative_quote_qty = notional;
+        let reserved = std::mem::take(&mut order.reserved);
+        let symbol = order.report.symbol.clone();
+        let side = order.report.side.clone();
+        let (base, quote) = self.symbols[&symbol].clone();
+
+        let (commission, commission_asset) = match (self.market, &side) {
+            (Market::Spot, OrderSide::Buy) => {
+                let commission = qty * rate;
+                self.release(&quote, reserved);
+                self.credit(&quote, -notional);
+                self.credit(&base, qty - commission);
+                (commission, base)
+            }
+            (Market::Spot, _) => {
+                let commission = notional * rate;
+                self.release(&base, reserved);
+                self.credit(&base, -qty);
+                self.credit(&quote, notional - commission);
+                (commission, quote)
+            }
+            (Market::Futures, _) => {
+                let commission = notional * rate;
+                let signed_qty = if side == OrderSide::Buy { qty } else { -qty };
+                let realized = self.position_mut(&symbol).apply(signed_qty, price);
+                self.credit(&quote, realized - commission);
+                (commission, quote)
+            }
+        };
+
+        self.trades.push(PaperTrade {
+            symbol,
+            order_id,
+            side,
+            price,
+            qty,
+            commission,
+            commission_asset,
+            is_maker,
+        });
+    }
+
+    fn cancel(&mut self, order_id: u64) {
+        let (symbol, side, reserved) = match self.orders.get_mut(&order_id) {
+            Some(order) => {
+                order.report.status = OrderStatus::Canceled;
+                let reserved = std::mem::take(&mut order.reserved);
+                (order.report.symbol.clone(), order.report.side.clone(), reserved)
+            }
+            None => return,
+        };
+        if self.market == Market::Spot {
+            let (base, quote) = self.symbols[&symbol].clone();
+            let asset = if side == OrderSide::Buy { quote } else { base };
+            self.release(&asset, reserved);
+        }
+    }
+
+    fn release(&mut self, asset: &str, amount: Amount) {
+        let balance = self.balance_mut(asset);
+        balance.locked -= amount;
+        balance.free += amount;
+    }
+
+    fn credit(&mut self, asset: &str, amount: Amount) {
+        let balance = self.balance_mut(asset);
+        balance.free += amount;
+    }
+
+    fn balance_mut(&mut self, asset: &str) -> &mut Balance {
+        self.balances
+            .entry(asset.into())
+            .or_insert_with(|| empty_balance(asset))
+    }
+
+    fn position_mut(&mut self, symbol: &str) -> &mut PaperPosition {
+        self.positions
+            .entry(symbol.into())
+            .or_insert_with(|| flat_position(symbol))
+    }
+}
+
+// Whether a limit order at `price` trades with the best opposite price.
+fn crosses(side: &OrderSide, price: Amount, best: Amount) -> bool {
+    match side {
+        OrderSide::Buy => best <= price,
+        _ => best >= price,
+    }
+}
+
+fn empty_balance(asset: &str) -> Balance {
+    Balance {
+        asset: asset.into(),
+        free: Amount::default(),
+        locked: Amount::default(),
+    }
+}
+
+fn flat_position(symbol: &str) -> PaperPosition {
+    PaperPosition {
+        symbol: symbol.into(),
+        ..PaperPosition::default()
+    }
+}
+
+fn min(a: Amount, b: Amount) -> Amount {
+    if a < b {
+        a
+    } else {
+        b
+    }
+}
+
+// Commissions are given in basis points.
+fn rate(bps: f32) -> Amount {
+    let bps: Amount = bps.to_string().parse().unwrap_or_default();
+    bps / Amount::from(10_000u32)
+}
+
+fn api_error(code: i16, msg: &str) -> BinanceError {
+    BinanceError::Api {
+        code,
+        msg: msg.into(),
+    }
+}
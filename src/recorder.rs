@@ -0,0 +1,234 @@
+//! Recording of websocket streams to disk, for research and backtests.
+//! Requires the `recorder` feature.
+//!
+//! A [`Recorder`] keeps every text frame as received, with the time it was
+//! received, in zstd-compressed newline-delimited JSON. A new file is started
+//! every hour by default:
+//!
+//! ```rust,no_run
+//! use binance::recorder::*;
+//! use binance::websockets::*;
+//! use std::sync::atomic::AtomicBool;
+//!
+//! let recorder = Recorder::new("data/btcusdt").unwrap();
+//! let mut web_socket = WebSockets::new(|_event: WebsocketEvent| Ok(()));
+//! web_socket.record_to(recorder);
+//! web_socket
+//!     .connect_multiple_streams(&["btcusdt@trade".into(), "btcusdt@bookTicker".into()])
+//!     .unwrap();
+//! web_socket.event_loop(&AtomicBool::new(true)).unwrap();
+//! ```
+//!
+//! The recording is then handled like the live stream:
+//!
+//! ```rust,no_run
+//! # use binance::websockets::*;
+//! # use std::sync::atomic::AtomicBool;
+//! let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+//!     println!("{:?}", event);
+//!     Ok(())
+//! });
+//! web_socket.connect_recording("data/btcusdt").unwrap();
+//! web_socket.event_loop(&AtomicBool::new(true)).unwrap();
+//! ```
+
+use crate::errors::*;
+use crate::transport::FrameSink;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use zstd::stream::read::Decoder;
+use zstd::stream::write::Encoder;
+
+const EXTENSION: &str = ".ndjson.zst";
+
+// Flushing ends a zstd block, doing it for every frame would cost most of the compression.
+const FLUSH_INTERVAL_MS: u64 = 1000;
+
+/// One line of a recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// Milliseconds since the epoch.
+    pub received_at: u64,
+    /// The frame as received.
+    pub frame: String,
+}
+
+/// Writes frames to `<prefix>-<first received_at>.ndjson.zst` files in a
+/// directory, starting a new file once the rotation period has passed.
+///
+/// Frames are flushed about once a second. A file is only a complete zstd
+/// stream once rotated or once the recorder is dropped, [`read_recording`]
+/// reads the frames flushed to a file cut short.
+pub struct Recorder {
+    dir: PathBuf,
+    prefix: String,
+    rotation: Duration,
+    compression_level: i32,
+    file: Option<RecordingFile>,
+}
+
+struct RecordingFile {
+    encoder: Encoder<'static, File>,
+    started_at: u64,
+    flushed_at: u64,
+}
+
+impl Recorder {
+    /// Records to `dir`, created if missing.
+    pub fn new<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(Recorder {
+            dir: dir.as_ref().to_path_buf(),
+            prefix: "frames".into(),
+            rotation: Duration::from_secs(3600),
+            compression_level: zstd::DEFAULT_COMPRESSION_LEVEL,
+            file: None,
+        })
+    }
+
+    pub fn set_prefix<T: Into<String>>(mut self, prefix: T) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    pub fn set_rotation(mut self, rotation: Duration) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// zstd level, from 1 to 22.
+    pub fn set_compression_level(mut self, compression_level: i32) -> Self {
+        self.compression_level = compression_level;
+        self
+    }
+
+    /// Records `frame` as received at `received_at`, in milliseconds since the epoch.
+    pub fn record_at(&mut self, received_at: u64, frame: &str) -> Result<()> {
+        let rotation = self.rotation.as_millis() as u64;
+        if self
+            .file
+            .as_ref()
+            .is_some_and(|file| received_at >= file.started_at + rotation)
+        {
+            self.finish()?;
+        }
+        if self.file.is_none() {
+            let path = self.dir.join(format!("{}-{}{}", self.prefix, received_at, EXTENSION));
+            self.file = Some(RecordingFile {
+                encoder: Encoder::new(File::create(path)?, self.compression_level)?,
+                started_at: received_at,
+                flushed_at: received_at,
+            });
+        }
+
+        let file = self.file.as_mut().unwrap();
+        let line = serde_json::to_string(&RecordedFrame {
+            received_at,
+            frame: frame.to_string(),
+        })?;
+        file.encoder.write_all(line.as_bytes())?;
+        file.encoder.write_all(b"\n")?;
+        if received_at >= file.flushed_at + FLUSH_INTERVAL_MS {
+            file.encoder.flush()?;
+            file.flushed_at = received_at;
+        }
+        Ok(())
+    }
+
+    /// Completes the current file, the next frame starts a new one.
+    pub fn finish(&mut self) -> Result<()> {
+        if let Some(file) = self.file.take() {
+            file.encoder.finish()?.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+impl FrameSink for Recorder {
+    fn record(&mut self, frame: &str) -> Result<()> {
+        let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        self.record_at(since_epoch.as_millis() as u64, frame)
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+/// Reads the frames of a recording file, or of all the recording files in a
+/// directory in the order they were written.
+pub fn read_recording<P: AsRef<Path>>(path: P) -> Result<RecordingReader> {
+    let path = path.as_ref();
+    let mut files = Vec::new();
+    if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            let file = entry?.path();
+            if file.to_string_lossy().ends_with(EXTENSION) {
+                files.push(file);
+            }
+        }
+        // The timestamps in the names have the same number of digits until 2286.
+        files.sort();
+    } else {
+        files.push(path.to_path_buf());
+    }
+    files.reverse();
+
+    Ok(RecordingReader { files, lines: None })
+}
+
+/// Iterator over the frames of a recording, see [`read_recording`].
+pub struct RecordingReader {
+    // Files left to read, last first.
+    files: Vec<PathBuf>,
+    lines: Option<io::Lines<BufReader<Decoder<'static, BufReader<File>>>>>,
+}
+
+impl RecordingReader {
+    fn open_next(&mut self) -> Result<bool> {
+        match self.files.pop() {
+            Some(path) => {
+                self.lines = Some(BufReader::new(Decoder::new(File::open(path)?)?).lines());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+impl Iterator for RecordingReader {
+    type Item = Result<RecordedFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(lines) = self.lines.as_mut() {
+                match lines.next() {
+                    Some(Ok(line)) if line.trim().is_empty() => continue,
+                    Some(Ok(line)) => {
+                        return Some(
+                            serde_json::from_str(&line).map_err(|e| BinanceError::decode(e, &line)),
+                        )
+                    }
+                    // The recorder did not finish the file, the frames flushed are all there is.
+                    Some(Err(e)) if e.kind() == io::ErrorKind::UnexpectedEof => {}
+                    Some(Err(e)) => {
+                        self.lines = None;
+                        return Some(Err(e.into()));
+                    }
+                    None => {}
+                }
+                self.lines = None;
+            }
+            match self.open_next() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
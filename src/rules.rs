@@ -0,0 +1,351 @@
+//! Client-side checks of orders against the filters of a symbol.
+//!
+//! The filters come with `exchangeInfo`, checking them before sending an order
+//! avoids the round trip that ends in a `-1013` rejection:
+//!
+//!```no_run
+//! use binance::api::Binance;
+//! use binance::general::General;
+//! use binance::model::Amount;
+//!
+//! fn main() {
+//!     let general: General = Binance::new(None, None).unwrap();
+//!     let rules = general.get_symbol_rules("BTCUSDT").unwrap();
+//!
+//!     let price = rules.round_price("20000.123".parse::<Amount>().unwrap());
+//!     let qty = rules.round_qty("0.0012345".parse::<Amount>().unwrap());
+//!     if let Err(violation) = rules.validate_limit(price, qty) {
+//!         println!("Order rejected: {}", violation);
+//!     }
+//! }
+//! ```
+
+use crate::futures;
+use crate::model::{Amount, Filters, Symbol};
+use std::error;
+use std::fmt;
+
+/// Filters of one symbol, a zero bound means the bound is disabled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolRules {
+    pub symbol: String,
+    pub price: Option<PriceRule>,
+    pub percent_price: Option<PercentPriceRule>,
+    pub lot_size: Option<QuantityRule>,
+    pub market_lot_size: Option<QuantityRule>,
+    pub min_notional: Option<NotionalRule>,
+    pub max_num_orders: Option<u16>,
+}
+
+/// PRICE_FILTER
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceRule {
+    pub min_price: Amount,
+    pub max_price: Amount,
+    pub tick_size: Amount,
+}
+
+/// PERCENT_PRICE, bounds relative to the average price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PercentPriceRule {
+    pub multiplier_up: Amount,
+    pub multiplier_down: Amount,
+}
+
+/// LOT_SIZE and MARKET_LOT_SIZE
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantityRule {
+    pub min_qty: Amount,
+    pub max_qty: Amount,
+    pub step_size: Amount,
+}
+
+/// MIN_NOTIONAL, minimum of `price * quantity`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotionalRule {
+    pub min_notional: Amount,
+    pub apply_to_market: bool,
+}
+
+/// The filter an order breaks, with the offending value and the bound.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleViolation {
+    PriceTooLow { price: Amount, min_price: Amount },
+    PriceTooHigh { price: Amount, max_price: Amount },
+    PriceNotOnTick { price: Amount, tick_size: Amount },
+    PriceOutOfPercentRange { price: Amount, min_price: Amount, max_price: Amount },
+    QuantityTooLow { qty: Amount, min_qty: Amount },
+    QuantityTooHigh { qty: Amount, max_qty: Amount },
+    QuantityNotOnStep { qty: Amount, step_size: Amount },
+    NotionalTooLow { notional: Amount, min_notional: Amount },
+    TooManyOrders { max_num_orders: u16 },
+}
+
+impl fmt::Display for RuleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleViolation::PriceTooLow { price, min_price } => {
+                write!(f, "price {} is below the minimum price {}", price, min_price)
+            }
+            RuleViolation::PriceTooHigh { price, max_price } => {
+                write!(f, "price {} is above the maximum price {}", price, max_price)
+            }
+            RuleViolation::PriceNotOnTick { price, tick_size } => {
+                write!(f, "price {} is not on a tick of {} from the minimum price", price, tick_size)
+            }
+            RuleViolation::PriceOutOfPercentRange { price, min_price, max_price } => write!(
+                f,
+                "price {} is outside of the range {} - {} around the average price",
+                price, min_price, max_price
+            ),
+            RuleViolation::QuantityTooLow { qty, min_qty } => {
+                write!(f, "quantity {} is below the minimum quantity {}", qty, min_qty)
+            }
+            RuleViolation::QuantityTooHigh { qty, max_qty } => {
+                write!(f, "quantity {} is above the maximum quantity {}", qty, max_qty)
+            }
+            RuleViolation::QuantityNotOnStep { qty, step_size } => {
+                write!(f, "quantity {} is not on a step of {} from the minimum quantity", qty, step_size)
+            }
+            RuleViolation::NotionalTooLow { notional, min_notional } => {
+                write!(f, "notional {} is below the minimum notional {}", notional, min_notional)
+            }
+            RuleViolation::TooManyOrders { max_num_orders } => {
+                write!(f, "the symbol allows at most {} open orders", max_num_orders)
+            }
+        }
+    }
+}
+
+impl error::Error for RuleViolation {}
+
+impl SymbolRules {
+    pub fn from_filters<S: Into<String>>(symbol: S, filters: &[Filters]) -> Self {
+        let mut rules = SymbolRules {
+            symbol: symbol.into(),
+            price: None,
+            percent_price: None,
+            lot_size: None,
+            market_lot_size: None,
+            min_notional: None,
+            max_num_orders: None,
+        };
+
+        for filter in filters {
+            match filter {
+                Filters::PriceFilter { min_price, max_price, tick_size } => {
+                    rules.price = Some(PriceRule {
+                        min_price: *min_price,
+                        max_price: *max_price,
+                        tick_size: *tick_size,
+                    });
+                }
+                Filters::PercentPrice { multiplier_up, multiplier_down, .. } => {
+                    rules.percent_price = Some(PercentPriceRule {
+                        multiplier_up: *multiplier_up,
+                        multiplier_down: *multiplier_down,
+                    });
+                }
+                Filters::LotSize { min_qty, max_qty, step_size } => {
+                    rules.lot_size = Some(QuantityRule {
+                        min_qty: *min_qty,
+                        max_qty: *max_qty,
+                        step_size: *step_size,
+                    });
+                }
+                Filters::MarketLotSize { min_qty, max_qty, step_size } => {
+                    rules.market_lot_size = Some(QuantityRule {
+                        min_qty: *min_qty,
+                        max_qty: *max_qty,
+                        step_size: *step_size,
+                    });
+                }
+                Filters::MinNotional { notional, min_notional, apply_to_market, .. } => {
+                    // Spot sends `minNotional`, futures send `notional`
+                    if let Some(min_notional) = min_notional.or(*notional) {
+                        rules.min_notional = Some(NotionalRule {
+                            min_notional,
+                            apply_to_market: apply_to_market.unwrap_or(true),
+                        });
+                    }
+                }
+                Filters::MaxNumOrders { max_num_orders } => {
+                    rules.max_num_orders = *max_num_orders;
+                }
+                _ => {}
+            }
+        }
+
+        rules
+    }
+
+    /// Rounds the price to the nearest tick, ticks being counted from the
+    /// minimum price as the exchange does.
+    pub fn round_price(&self, price: Amount) -> Amount {
+        match &self.price {
+            Some(rule) if !is_zero(rule.tick_size) => to_step(price, rule.min_price, rule.tick_size, true),
+            _ => price,
+        }
+    }
+
+    /// Rounds the quantity down to a step, counted from the minimum quantity,
+    /// so it never exceeds what was asked for.
+    pub fn round_qty(&self, qty: Amount) -> Amount {
+        match &self.lot_size {
+            Some(rule) if !is_zero(rule.step_size) => to_step(qty, rule.min_qty, rule.step_size, false),
+            _ => qty,
+        }
+    }
+
+    /// Rounds the quantity of a market order down to a step.
+    pub fn round_market_qty(&self, qty: Amount) -> Amount {
+        match self.market_lot_size.as_ref().or(self.lot_size.as_ref()) {
+            Some(rule) if !is_zero(rule.step_size) => to_step(qty, rule.min_qty, rule.step_size, false),
+            _ => qty,
+        }
+    }
+
+    /// Checks the price, quantity and notional of a limit order.
+    pub fn validate_limit(&self, price: Amount, qty: Amount) -> Result<(), RuleViolation> {
+        self.validate_price(price)?;
+        if let Some(rule) = &self.lot_size {
+            validate_qty(rule, qty)?;
+        }
+        if let Some(rule) = &self.min_notional {
+            validate_notional(rule, price * qty)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the quantity of a market order, and its notional at
+    /// `reference_price` when one is given, e.g. the average price.
+    pub fn validate_market(
+        &self, qty: Amount, reference_price: Option<Amount>,
+    ) -> Result<(), RuleViolation> {
+        if let Some(rule) = &self.lot_size {
+            validate_qty(rule, qty)?;
+        }
+        if let Some(rule) = &self.market_lot_size {
+            validate_qty(rule, qty)?;
+        }
+        if let (Some(rule), Some(price)) = (&self.min_notional, reference_price) {
+            if rule.apply_to_market {
+                validate_notional(rule, price * qty)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the price against the PRICE_FILTER.
+    pub fn validate_price(&self, price: Amount) -> Result<(), RuleViolation> {
+        let rule = match &self.price {
+            Some(rule) => rule,
+            None => return Ok(()),
+        };
+        if !is_zero(rule.min_price) && price < rule.min_price {
+            return Err(RuleViolation::PriceTooLow { price, min_price: rule.min_price });
+        }
+        if !is_zero(rule.max_price) && price > rule.max_price {
+            return Err(RuleViolation::PriceTooHigh { price, max_price: rule.max_price });
+        }
+        if !is_zero(rule.tick_size) && to_step(price, rule.min_price, rule.tick_size, true) != price {
+            return Err(RuleViolation::PriceNotOnTick { price, tick_size: rule.tick_size });
+        }
+        Ok(())
+    }
+
+    /// Checks the price against the PERCENT_PRICE range around `average_price`.
+    pub fn validate_percent_price(
+        &self, price: Amount, average_price: Amount,
+    ) -> Result<(), RuleViolation> {
+        if let Some(rule) = &self.percent_price {
+            let min_price = average_price * rule.multiplier_down;
+            let max_price = average_price * rule.multiplier_up;
+            if price < min_price || price > max_price {
+                return Err(RuleViolation::PriceOutOfPercentRange { price, min_price, max_price });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that one more order fits under MAX_NUM_ORDERS.
+    pub fn validate_open_orders(&self, open_orders: usize) -> Result<(), RuleViolation> {
+        match self.max_num_orders {
+            Some(max_num_orders) if open_orders >= usize::from(max_num_orders) => {
+                Err(RuleViolation::TooManyOrders { max_num_orders })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<&Symbol> for SymbolRules {
+    fn from(symbol: &Symbol) -> Self {
+        SymbolRules::from_filters(symbol.symbol.clone(), &symbol.filters)
+    }
+}
+
+impl From<&futures::model::Symbol> for SymbolRules {
+    fn from(symbol: &futures::model::Symbol) -> Self {
+        SymbolRules::from_filters(symbol.symbol.clone(), &symbol.filters)
+    }
+}
+
+fn validate_qty(rule: &QuantityRule, qty: Amount) -> Result<(), RuleViolation> {
+    if !is_zero(rule.min_qty) && qty < rule.min_qty {
+        return Err(RuleViolation::QuantityTooLow { qty, min_qty: rule.min_qty });
+    }
+    if !is_zero(rule.max_qty) && qty > rule.max_qty {
+        return Err(RuleViolation::QuantityTooHigh { qty, max_qty: rule.max_qty });
+    }
+    if !is_zero(rule.step_size) && to_step(qty, rule.min_qty, rule.step_size, false) != qty {
+        return Err(RuleViolation::QuantityNotOnStep { qty, step_size: rule.step_size });
+    }
+    Ok(())
+}
+
+fn validate_notional(rule: &NotionalRule, notional: Amount) -> Result<(), RuleViolation> {
+    if notional < rule.min_notional {
+        return Err(RuleViolation::NotionalTooLow { notional, min_notional: rule.min_notional });
+    }
+    Ok(())
+}
+
+fn is_zero(value: Amount) -> bool {
+    value == Amount::default()
+}
+
+// Rounds to `min` plus a multiple of `step`, to the nearest one or down. The
+// exchange counts the steps from the minimum, which needs not be a multiple
+// of the step.
+#[cfg(not(feature = "decimal"))]
+fn to_step(value: Amount, min: Amount, step: Amount, nearest: bool) -> Amount {
+    let (offset, step) = (to_units(value) - to_units(min), to_units(step));
+    let mut steps = offset.div_euclid(step);
+    if nearest && offset.rem_euclid(step) * 2 >= step {
+        steps += 1;
+    }
+    (to_units(min) + steps * step) as f64 / UNITS
+}
+
+// Filters have at most 8 decimals, so they are exact in units of 1e-8.
+#[cfg(not(feature = "decimal"))]
+const UNITS: f64 = 1e8;
+
+#[cfg(not(feature = "decimal"))]
+fn to_units(value: Amount) -> i128 {
+    (value * UNITS).round() as i128
+}
+
+#[cfg(feature = "decimal")]
+fn to_step(value: Amount, min: Amount, step: Amount, nearest: bool) -> Amount {
+    use rust_decimal::RoundingStrategy;
+
+    let steps = (value - min) / step;
+    let steps = if nearest {
+        steps.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+    } else {
+        steps.floor()
+    };
+    min + steps * step
+}
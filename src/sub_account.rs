@@ -0,0 +1,250 @@
+use crate::util::*;
+use crate::model::*;
+use crate::client::*;
+use crate::errors::*;
+use crate::api::API;
+use crate::api::Sapi;
+
+/// Sub-account management for a master account.
+///
+/// All endpoints must be called with the master account's API key.
+#[derive(Clone)]
+pub struct SubAccount {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+/// Wallet of an account taking part in a universal transfer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccountType {
+    Spot,
+    UsdtFuture,
+    CoinFuture,
+    Margin,
+    IsolatedMargin,
+}
+
+impl From<AccountType> for String {
+    fn from(item: AccountType) -> Self {
+        match item {
+            AccountType::Spot => String::from("SPOT"),
+            AccountType::UsdtFuture => String::from("USDT_FUTURE"),
+            AccountType::CoinFuture => String::from("COIN_FUTURE"),
+            AccountType::Margin => String::from("MARGIN"),
+            AccountType::IsolatedMargin => String::from("ISOLATED_MARGIN"),
+        }
+    }
+}
+
+struct TransferRequest {
+    pub from_email: Option<String>,
+    pub to_email: Option<String>,
+    pub from_account_type: AccountType,
+    pub to_account_type: AccountType,
+    pub asset: String,
+    pub amount: Amount,
+}
+
+impl SubAccount {
+    /// Sub-accounts of the master account, `limit` defaults to 1 and is capped at 200.
+    pub fn get_sub_account_list<S1, S2>(&self, page: S1, limit: S2) -> Result<SubAccountList>
+    where
+        S1: Into<Option<u32>>,
+        S2: Into<Option<u16>>,
+    {
+        let mut parameters = RequestParams::new();
+
+        if let Some(page) = page.into() {
+            parameters.insert("page", page.to_string());
+        }
+        if let Some(limit) = limit.into() {
+            parameters.insert("limit", limit.to_string());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Sapi(Sapi::SubAccountList), Some(request))
+    }
+
+    /// Spot balances of ONE sub-account.
+    pub fn get_assets<S>(&self, email: S) -> Result<SubAccountAssets>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = RequestParams::new();
+        parameters.insert("email", email);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Sapi(Sapi::SubAccountAssets), Some(request))
+    }
+
+    /// USDT-M futures balances and margins of ONE sub-account.
+    pub fn get_futures_account<S>(&self, email: S) -> Result<SubAccountFuturesAccount>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = RequestParams::new();
+        parameters.insert("email", email);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Sapi(Sapi::SubAccountFuturesAccount), Some(request))
+    }
+
+    /// USDT-M futures positions of ONE sub-account.
+    pub fn get_futures_positions<S>(&self, email: S) -> Result<Vec<SubAccountFuturesPosition>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = RequestParams::new();
+        parameters.insert("email", email);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Sapi(Sapi::SubAccountFuturesPositionRisk), Some(request))
+    }
+
+    /// Move an asset from the master spot wallet to a sub-account spot wallet.
+    pub fn transfer_to_sub_account<S1, S2, F>(
+        &self, to_email: S1, asset: S2, amount: F,
+    ) -> Result<SubAccountTransferResponse>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        F: Into<Amount>,
+    {
+        let transfer = TransferRequest {
+            from_email: None,
+            to_email: Some(to_email.into()),
+            from_account_type: AccountType::Spot,
+            to_account_type: AccountType::Spot,
+            asset: asset.into(),
+            amount: amount.into(),
+        };
+        self.transfer(transfer)
+    }
+
+    /// Move an asset from a sub-account spot wallet back to the master spot wallet.
+    pub fn transfer_from_sub_account<S1, S2, F>(
+        &self, from_email: S1, asset: S2, amount: F,
+    ) -> Result<SubAccountTransferResponse>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        F: Into<Amount>,
+    {
+        let transfer = TransferRequest {
+            from_email: Some(from_email.into()),
+            to_email: None,
+            from_account_type: AccountType::Spot,
+            to_account_type: AccountType::Spot,
+            asset: asset.into(),
+            amount: amount.into(),
+        };
+        self.transfer(transfer)
+    }
+
+    /// Move an asset between the spot wallets of two sub-accounts.
+    pub fn transfer_between_sub_accounts<S1, S2, S3, F>(
+        &self, from_email: S1, to_email: S2, asset: S3, amount: F,
+    ) -> Result<SubAccountTransferResponse>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+        F: Into<Amount>,
+    {
+        let transfer = TransferRequest {
+            from_email: Some(from_email.into()),
+            to_email: Some(to_email.into()),
+            from_account_type: AccountType::Spot,
+            to_account_type: AccountType::Spot,
+            asset: asset.into(),
+            amount: amount.into(),
+        };
+        self.transfer(transfer)
+    }
+
+    /// Universal transfer between any wallets of the account tree.
+    ///
+    /// A missing email stands for the master account.
+    ///
+    ///```no_run
+    /// use binance::api::Binance;
+    /// use binance::sub_account::*;
+    ///
+    /// fn main() {
+    ///     let api_key = Some("api_key".into());
+    ///     let secret_key = Some("secret_key".into());
+    ///     let sub_account: SubAccount = Binance::new(api_key, secret_key).unwrap();
+    ///     let result = sub_account.universal_transfer(
+    ///         None,
+    ///         Some("alice@example.com".into()),
+    ///         AccountType::Spot,
+    ///         AccountType::UsdtFuture,
+    ///         "USDT",
+    ///         100,
+    ///     );
+    /// }
+    /// ```
+    pub fn universal_transfer<S, F>(
+        &self, from_email: Option<String>, to_email: Option<String>,
+        from_account_type: AccountType, to_account_type: AccountType, asset: S, amount: F,
+    ) -> Result<SubAccountTransferResponse>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let transfer = TransferRequest {
+            from_email,
+            to_email,
+            from_account_type,
+            to_account_type,
+            asset: asset.into(),
+            amount: amount.into(),
+        };
+        self.transfer(transfer)
+    }
+
+    /// Universal transfer history, optionally filtered by sender or receiver.
+    pub fn get_universal_transfer_history<S1, S2>(
+        &self, from_email: S1, to_email: S2,
+    ) -> Result<SubAccountTransferHistory>
+    where
+        S1: Into<Option<String>>,
+        S2: Into<Option<String>>,
+    {
+        let mut parameters = RequestParams::new();
+
+        if let Some(from_email) = from_email.into() {
+            parameters.insert("fromEmail", from_email);
+        }
+        if let Some(to_email) = to_email.into() {
+            parameters.insert("toEmail", to_email);
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Sapi(Sapi::SubAccountUniversalTransfer), Some(request))
+    }
+
+    fn transfer(&self, transfer: TransferRequest) -> Result<SubAccountTransferResponse> {
+        let mut parameters = RequestParams::new();
+
+        if let Some(from_email) = transfer.from_email {
+            parameters.insert("fromEmail", from_email);
+        }
+        if let Some(to_email) = transfer.to_email {
+            parameters.insert("toEmail", to_email);
+        }
+        parameters.insert("fromAccountType", transfer.from_account_type);
+        parameters.insert("toAccountType", transfer.to_account_type);
+        parameters.insert("asset", transfer.asset);
+        parameters.insert("amount", transfer.amount.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .post_signed(API::Sapi(Sapi::SubAccountUniversalTransfer), request)
+    }
+}
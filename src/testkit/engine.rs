@@ -0,0 +1,1313 @@
+// Exchange state and matching engine. Amounts are kept as integer multiples of
+// 1e-8, the precision of the Binance API, so fills and balances are exact.
+
+use super::http::{Request, Response};
+use super::Venue;
+use crate::model::{ExecutionType, OrderSide, OrderStatus, OrderType, TimeInForce};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::mpsc::Sender;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) const UNIT: i64 = 100_000_000;
+
+pub(crate) fn to_units(value: f64) -> i64 {
+    (value * UNIT as f64).round() as i64
+}
+
+pub(crate) fn from_units(units: i64) -> f64 {
+    units as f64 / UNIT as f64
+}
+
+fn format_units(units: i64) -> String {
+    let sign = if units < 0 { "-" } else { "" };
+    let abs = units.unsigned_abs();
+    format!("{}{}.{:08}", sign, abs / UNIT as u64, abs % UNIT as u64)
+}
+
+fn notional(price: i64, qty: i64) -> i64 {
+    (price as i128 * qty as i128 / UNIT as i128) as i64
+}
+
+fn fee(amount: i64, bps: i64) -> i64 {
+    (amount as i128 * bps as i128 / 10_000) as i64
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+pub(crate) struct ApiError {
+    status: u16,
+    code: i16,
+    msg: String,
+}
+
+type ApiResult = std::result::Result<Value, ApiError>;
+
+fn reject<T>(code: i16, msg: impl Into<String>) -> std::result::Result<T, ApiError> {
+    Err(ApiError {
+        status: 400,
+        code,
+        msg: msg.into(),
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Topic {
+    UserData(Venue),
+    Trades(Venue, String),
+}
+
+struct Subscriber {
+    topic: Topic,
+    listen_key: Option<String>,
+    sender: Sender<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Owner {
+    User,
+    // Resting orders seeded by the test, without balances behind them.
+    Liquidity,
+}
+
+#[derive(Clone)]
+struct Order {
+    venue: Venue,
+    owner: Owner,
+    id: u64,
+    symbol: String,
+    client_order_id: String,
+    side: OrderSide,
+    order_type: OrderType,
+    time_in_force: TimeInForce,
+    price: i64,
+    orig_qty: i64,
+    orig_quote_qty: i64,
+    executed_qty: i64,
+    cum_quote: i64,
+    status: OrderStatus,
+    reduce_only: bool,
+    time: u64,
+    update_time: u64,
+    // Spot funds set aside for the rest of the order, in `reserved_asset`.
+    reserved: i64,
+    reserved_asset: String,
+}
+
+impl Order {
+    fn remaining(&self) -> i64 {
+        self.orig_qty - self.executed_qty
+    }
+
+    fn is_open(&self) -> bool {
+        matches!(self.status, OrderStatus::New | OrderStatus::PartiallyFilled)
+    }
+}
+
+struct Fill {
+    trade_id: u64,
+    price: i64,
+    qty: i64,
+    commission: i64,
+    commission_asset: String,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Balance {
+    free: i64,
+    locked: i64,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Position {
+    amount: i64,
+    entry_price: i64,
+    realized: i64,
+}
+
+struct UserTrade {
+    symbol: String,
+    id: u64,
+    order_id: u64,
+    price: i64,
+    qty: i64,
+    commission: i64,
+    commission_asset: String,
+    time: u64,
+    is_buyer: bool,
+    is_maker: bool,
+}
+
+#[derive(Default)]
+struct Book {
+    // Order ids by priority: best price first, then oldest first.
+    bids: Vec<u64>,
+    asks: Vec<u64>,
+}
+
+pub(crate) struct Exchange {
+    api_key: Option<String>,
+    maker_bps: i64,
+    taker_bps: i64,
+    // Symbol to (base asset, quote asset).
+    symbols: BTreeMap<String, (String, String)>,
+    books: BTreeMap<(Venue, String), Book>,
+    orders: BTreeMap<u64, Order>,
+    balances: BTreeMap<String, Balance>,
+    futures_balances: BTreeMap<String, i64>,
+    positions: BTreeMap<String, Position>,
+    leverage: BTreeMap<String, u8>,
+    trades: Vec<UserTrade>,
+    listen_keys: BTreeMap<String, Venue>,
+    subscribers: Vec<Subscriber>,
+    next_order_id: u64,
+    next_trade_id: u64,
+    next_listen_key: u64,
+    update_id: u64,
+}
+
+impl Exchange {
+    pub fn new() -> Self {
+        Exchange {
+            api_key: None,
+            maker_bps: 10,
+            taker_bps: 10,
+            symbols: BTreeMap::new(),
+            books: BTreeMap::new(),
+            orders: BTreeMap::new(),
+            balances: BTreeMap::new(),
+            futures_balances: BTreeMap::new(),
+            positions: BTreeMap::new(),
+            leverage: BTreeMap::new(),
+            trades: Vec::new(),
+            listen_keys: BTreeMap::new(),
+            subscribers: Vec::new(),
+            next_order_id: 1,
+            next_trade_id: 1,
+            next_listen_key: 1,
+            update_id: 1,
+        }
+    }
+
+    pub fn set_api_key(&mut self, api_key: String) {
+        self.api_key = Some(api_key);
+    }
+
+    pub fn set_commission(&mut self, maker_bps: i64, taker_bps: i64) {
+        self.maker_bps = maker_bps;
+        self.taker_bps = taker_bps;
+    }
+
+    pub fn add_symbol(&mut self, symbol: &str, base: &str, quote: &str) {
+        self.symbols.insert(symbol.into(), (base.into(), quote.into()));
+    }
+
+    pub fn set_balance(&mut self, asset: &str, free: i64) {
+        self.balances.entry(asset.into()).or_default().free = free;
+    }
+
+    pub fn balance(&self, asset: &str) -> (i64, i64) {
+        let balance = self.balances.get(asset).copied().unwrap_or_default();
+        (balance.free, balance.locked)
+    }
+
+    pub fn set_futures_balance(&mut self, asset: &str, balance: i64) {
+        self.futures_balances.insert(asset.into(), balance);
+    }
+
+    pub fn futures_balance(&self, asset: &str) -> i64 {
+        self.futures_balances.get(asset).copied().unwrap_or_default()
+    }
+
+    pub fn position(&self, symbol: &str) -> (i64, i64) {
+        let position = self.positions.get(symbol).copied().unwrap_or_default();
+        (position.amount, position.entry_price)
+    }
+
+    pub fn open_order_count(&self, venue: Venue) -> usize {
+        self.orders
+            .values()
+            .filter(|o| o.venue == venue && o.owner == Owner::User && o.is_open())
+            .count()
+    }
+
+    pub fn subscribe(&mut self, stream: &str, venue: Venue, sender: Sender<String>) -> bool {
+        let (topic, listen_key) = match self.listen_keys.get(stream) {
+            Some(venue) => (Topic::UserData(*venue), Some(stream.to_string())),
+            None => match stream.strip_suffix("@trade") {
+                Some(symbol) => (Topic::Trades(venue, symbol.to_uppercase()), None),
+                None => return false,
+            },
+        };
+        self.subscribers.push(Subscriber {
+            topic,
+            listen_key,
+            sender,
+        });
+        true
+    }
+
+    pub fn close_streams(&mut self) {
+        self.subscribers.clear();
+    }
+
+    fn publish(&mut self, topic: Topic, event: &Value) {
+        let frame = event.to_string();
+        self.subscribers
+            .retain(|s| s.topic != topic || s.sender.send(frame.clone()).is_ok());
+    }
+
+    /// Seeds a resting order that user orders can trade against.
+    pub fn add_liquidity(&mut self, venue: Venue, symbol: &str, side: OrderSide, price: i64, qty: i64) {
+        let mut params = BTreeMap::new();
+        params.insert("symbol".to_string(), symbol.to_string());
+        params.insert("side".to_string(), String::from(side));
+        params.insert("type".to_string(), "LIMIT".to_string());
+        params.insert("timeInForce".to_string(), "GTC".to_string());
+        params.insert("price".to_string(), format_units(price));
+        params.insert("quantity".to_string(), format_units(qty));
+        if let Err(e) = self.new_order(venue, Owner::Liquidity, &params) {
+            panic!("Invalid liquidity for {}: {}", symbol, e.msg);
+        }
+    }
+
+    pub fn handle(&mut self, request: Request) -> Response {
+        let result = self.route(&request);
+        match result {
+            Ok(body) => Response {
+                status: 200,
+                body: body.to_string(),
+            },
+            Err(e) => Response {
+                status: e.status,
+                body: json!({ "code": e.code, "msg": e.msg }).to_string(),
+            },
+        }
+    }
+
+    fn route(&mut self, request: &Request) -> ApiResult {
+        let params = &request.params;
+        let route = (request.method.as_str(), request.path.as_str());
+
+        let keyed = matches!(
+            route.1,
+            "/api/v3/userDataStream" | "/fapi/v1/listenKey"
+        );
+        let signed = !keyed
+            && !matches!(
+                route,
+                (_, "/api/v3/ping")
+                    | (_, "/api/v3/time")
+                    | (_, "/api/v3/depth")
+                    | (_, "/api/v3/ticker/price")
+                    | (_, "/fapi/v1/ping")
+                    | (_, "/fapi/v1/time")
+                    | (_, "/fapi/v1/depth")
+                    | (_, "/fapi/v1/ticker/price")
+            );
+        if keyed || signed {
+            self.authenticate(request, signed)?;
+        }
+
+        match route {
+            ("GET", "/api/v3/ping") | ("GET", "/fapi/v1/ping") => Ok(json!({})),
+            ("GET", "/api/v3/time") | ("GET", "/fapi/v1/time") => Ok(json!({ "serverTime": now() })),
+            ("GET", "/api/v3/depth") => self.depth(Venue::Spot, params),
+            ("GET", "/fapi/v1/depth") => self.depth(Venue::Futures, params),
+            ("GET", "/api/v3/ticker/price") => self.price(Venue::Spot, params),
+            ("GET", "/fapi/v1/ticker/price") => self.price(Venue::Futures, params),
+
+            ("POST", "/api/v3/order") => self.new_order(Venue::Spot, Owner::User, params),
+            ("GET", "/api/v3/order") => self.query_order(Venue::Spot, params),
+            ("DELETE", "/api/v3/order") => self.cancel_order(Venue::Spot, params),
+            (_, "/api/v3/order/test") => {
+                if request.method == "POST" {
+                    self.validate_order(Venue::Spot, params)?;
+                }
+                Ok(json!({}))
+            }
+            ("GET", "/api/v3/openOrders") => self.open_orders(Venue::Spot, params),
+            ("DELETE", "/api/v3/openOrders") => self.cancel_open_orders(Venue::Spot, params),
+            ("GET", "/api/v3/allOrders") => self.all_orders(params),
+            ("GET", "/api/v3/account") => Ok(self.account()),
+            ("GET", "/api/v3/myTrades") => self.my_trades(params),
+            ("POST", "/api/v3/userDataStream") => Ok(self.new_listen_key(Venue::Spot)),
+            ("PUT", "/api/v3/userDataStream") => self.keep_alive(params),
+            ("DELETE", "/api/v3/userDataStream") => self.close_listen_key(params),
+
+            ("POST", "/fapi/v1/order") => self.new_order(Venue::Futures, Owner::User, params),
+            ("GET", "/fapi/v1/order") => self.query_order(Venue::Futures, params),
+            ("DELETE", "/fapi/v1/order") => self.cancel_order(Venue::Futures, params),
+            ("DELETE", "/fapi/v1/batchOrders") => self.cancel_batch(params),
+            ("DELETE", "/fapi/v1/allOpenOrders") => {
+                self.cancel_open_orders(Venue::Futures, params)?;
+                Ok(json!({ "code": 200, "msg": "The operation of cancel all open order is done." }))
+            }
+            ("POST", "/fapi/v1/leverage") => self.change_leverage(params),
+            ("POST", "/fapi/v1/positionSide/dual") => Ok(json!({ "code": 200, "msg": "success" })),
+            ("POST", "/fapi/v1/listenKey") => Ok(self.new_listen_key(Venue::Futures)),
+            ("PUT", "/fapi/v1/listenKey") => self.keep_alive(params),
+            ("DELETE", "/fapi/v1/listenKey") => self.close_listen_key(params),
+
+            (method, path) => Err(ApiError {
+                status: 404,
+                code: -1000,
+                msg: format!("Path {}, Method {} is not supported by the mock exchange", path, method),
+            }),
+        }
+    }
+
+    fn authenticate(&self, request: &Request, signed: bool) -> std::result::Result<(), ApiError> {
+        let api_key = request.headers.get("x-mbx-apikey").map(String::as_str).unwrap_or_default();
+        let valid = match &self.api_key {
+            Some(expected) => api_key == expected,
+            None => !api_key.is_empty(),
+        };
+        if !valid {
+            return Err(ApiError {
+                status: 401,
+                code: -2014,
+                msg: "API-key format invalid.".into(),
+            });
+        }
+        if signed && !(request.params.contains_key("timestamp") && request.params.contains_key("signature")) {
+            return reject(-1102, "Mandatory parameter 'signature' was not sent, was empty/null, or malformed.");
+        }
+        Ok(())
+    }
+
+    fn symbol<'a>(&self, params: &'a BTreeMap<String, String>) -> std::result::Result<&'a str, ApiError> {
+        let symbol = required(params, "symbol")?;
+        if !self.symbols.contains_key(symbol) {
+            return reject(-1121, "Invalid symbol.");
+        }
+        Ok(symbol)
+    }
+
+    fn depth(&self, venue: Venue, params: &BTreeMap<String, String>) -> ApiResult {
+        let symbol = self.symbol(params)?;
+        let limit = params.get("limit").and_then(|l| l.parse().ok()).unwrap_or(100);
+        let book = self.books.get(&(venue, symbol.to_string()));
+        let levels = |ids: Option<&Vec<u64>>| -> Vec<Value> {
+            let mut levels: Vec<(i64, i64)> = Vec::new();
+            for id in ids.into_iter().flatten() {
+                let order = &self.orders[id];
+                match levels.last_mut() {
+                    Some(level) if level.0 == order.price => level.1 += order.remaining(),
+                    _ => levels.push((order.price, order.remaining())),
+                }
+            }
+            levels
+                .into_iter()
+                .take(limit)
+                .map(|(price, qty)| json!([format_units(price), format_units(qty)]))
+                .collect()
+        };
+        let mut depth = json!({
+            "lastUpdateId": self.update_id,
+            "bids": levels(book.map(|b| &b.bids)),
+            "asks": levels(book.map(|b| &b.asks)),
+        });
+        if venue == Venue::Futures {
+            depth["E"] = json!(now());
+            depth["T"] = json!(now());
+        }
+        Ok(depth)
+    }
+
+    fn price(&self, venue: Venue, params: &BTreeMap<String, String>) -> ApiResult {
+        let symbol = self.symbol(params)?.to_string();
+        let book = self.books.get(&(venue, symbol.clone()));
+        let best = |ids: Option<&Vec<u64>>| ids.and_then(|ids| ids.first()).map(|id| self.orders[id].price);
+        let price = match (best(book.map(|b| &b.bids)), best(book.map(|b| &b.asks))) {
+            (Some(bid), Some(ask)) => (bid + ask) / 2,
+            (Some(price), None) | (None, Some(price)) => price,
+            (None, None) => 0,
+        };
+        Ok(json!({ "symbol": symbol, "price": format_units(price) }))
+    }
+
+    fn validate_order(&self, venue: Venue, params: &BTreeMap<String, String>) -> std::result::Result<Order, ApiError> {
+        let symbol = self.symbol(params)?.to_string();
+        let side = match OrderSide::from(required(params, "side")?.to_string()) {
+            OrderSide::Other(_) => return reject(-1117, "Invalid side."),
+            side => side,
+        };
+        let order_type = OrderType::from(required(params, "type")?.to_string());
+        let supported = match venue {
+            Venue::Spot => matches!(order_type, OrderType::Limit | OrderType::Market | OrderType::LimitMaker),
+            Venue::Futures => matches!(order_type, OrderType::Limit | OrderType::Market),
+        };
+        if !supported {
+            return reject(-1116, "Invalid orderType.");
+        }
+
+        let time_in_force = match order_type {
+            OrderType::Limit => TimeInForce::from(required(params, "timeInForce")?.to_string()),
+            _ => TimeInForce::GTC,
+        };
+        let valid_time_in_force = match venue {
+            Venue::Spot => matches!(time_in_force, TimeInForce::GTC | TimeInForce::IOC | TimeInForce::FOK),
+            Venue::Futures => !matches!(time_in_force, TimeInForce::Other(_)),
+        };
+        if !valid_time_in_force {
+            return reject(-1115, "Invalid timeInForce.");
+        }
+
+        let price = match order_type {
+            OrderType::Market => 0,
+            _ => match amount(params, "price")? {
+                Some(price) if price > 0 => price,
+                _ => return reject(-1013, "Invalid price."),
+            },
+        };
+        let quote_qty = match (venue, &order_type) {
+            (Venue::Spot, OrderType::Market) => amount(params, "quoteOrderQty")?,
+            _ => None,
+        };
+        let qty = match (amount(params, "quantity")?, quote_qty) {
+            (Some(qty), None) if qty > 0 => qty,
+            (None, Some(quote_qty)) if quote_qty > 0 => 0,
+            _ => return reject(-1013, "Invalid quantity."),
+        };
+
+        let id = self.next_order_id;
+        Ok(Order {
+            venue,
+            owner: Owner::User,
+            id,
+            client_order_id: params
+                .get("newClientOrderId")
+                .cloned()
+                .unwrap_or_else(|| format!("mock-{}", id)),
+            symbol,
+            side,
+            order_type,
+            time_in_force,
+            price,
+            orig_qty: qty,
+            orig_quote_qty: quote_qty.unwrap_or_default(),
+            executed_qty: 0,
+            cum_quote: 0,
+            status: OrderStatus::New,
+            reduce_only: params.get("reduceOnly").map(|r| r.eq_ignore_ascii_case("true")).unwrap_or(false),
+            time: now(),
+            update_time: now(),
+            reserved: 0,
+            reserved_asset: String::new(),
+        })
+    }
+
+    fn new_order(&mut self, venue: Venue, owner: Owner, params: &BTreeMap<String, String>) -> ApiResult {
+        let mut order = self.validate_order(venue, params)?;
+        order.owner = owner;
+        let crosses = self.crossing_qty(&order) > 0;
+
+        if order.order_type == OrderType::LimitMaker && crosses {
+            return reject(-2010, "Order would immediately match and take.");
+        }
+        if venue == Venue::Futures && owner == Owner::User && order.reduce_only {
+            let position = self.positions.get(&order.symbol).copied().unwrap_or_default().amount;
+            let reduces = match order.side {
+                OrderSide::Buy => position < 0 && order.orig_qty <= -position,
+                _ => position > 0 && order.orig_qty <= position,
+            };
+            if !reduces {
+                return reject(-2022, "ReduceOnly Order is rejected.");
+            }
+        }
+        if venue == Venue::Spot && owner == Owner::User {
+            self.reserve(&mut order)?;
+        }
+
+        self.next_order_id += 1;
+        self.update_id += 1;
+        let mut events = Vec::new();
+
+        let expire = match order.time_in_force {
+            TimeInForce::FOK => self.crossing_qty(&order) < order.orig_qty,
+            TimeInForce::GTX => crosses,
+            _ => false,
+        };
+        self.orders.insert(order.id, order.clone());
+        events.extend(self.order_events(order.id, ExecutionType::New, None));
+
+        let mut fills = Vec::new();
+        if !expire {
+            self.match_order(order.id, &mut fills, &mut events);
+        }
+
+        let order = &self.orders[&order.id];
+        let rests = order.order_type != OrderType::Market
+            && order.time_in_force == TimeInForce::GTC
+            && order.remaining() > 0;
+        let id = order.id;
+        if rests {
+            self.insert_in_book(id);
+        } else if self.orders[&id].is_open() {
+            self.finish(id, OrderStatus::Expired);
+            events.extend(self.order_events(id, ExecutionType::Expired, None));
+        }
+
+        self.publish_all(events);
+        Ok(self.transaction(&self.orders[&id], &fills))
+    }
+
+    // Sets aside the funds for the whole order, released as it fills or ends.
+    fn reserve(&mut self, order: &mut Order) -> std::result::Result<(), ApiError> {
+        let (base, quote) = self.symbols[&order.symbol].clone();
+        let (asset, amount) = match (&order.side, &order.order_type) {
+            (OrderSide::Buy, OrderType::Market) if order.orig_qty == 0 => (quote, order.orig_quote_qty),
+            (OrderSide::Buy, OrderType::Market) => {
+                let cost = self.market_cost(order);
+                (quote, cost)
+            }
+            (OrderSide::Buy, _) => (quote, notional(order.price, order.orig_qty)),
+            _ => (base, order.orig_qty),
+        };
+        let balance = self.balances.entry(asset.clone()).or_default();
+        if balance.free < amount {
+            return reject(-2010, "Account has insufficient balance for requested action.");
+        }
+        balance.free -= amount;
+        balance.locked += amount;
+        order.reserved = amount;
+        order.reserved_asset = asset;
+        Ok(())
+    }
+
+    // Cost of a market buy against the current book.
+    fn market_cost(&self, order: &Order) -> i64 {
+        let mut remaining = order.orig_qty;
+        let mut cost = 0;
+        for id in self.opposite(order) {
+            let maker = &self.orders[id];
+            let qty = remaining.min(maker.remaining());
+            cost += notional(maker.price, qty);
+            remaining -= qty;
+            if remaining == 0 {
+                break;
+            }
+        }
+        cost
+    }
+
+    fn opposite(&self, order: &Order) -> &[u64] {
+        match self.books.get(&(order.venue, order.symbol.clone())) {
+            Some(book) if order.side == OrderSide::Buy => &book.asks,
+            Some(book) => &book.bids,
+            None => &[],
+        }
+    }
+
+    fn crosses(taker: &Order, maker: &Order) -> bool {
+        match (&taker.order_type, &taker.side) {
+            (OrderType::Market, _) => true,
+            (_, OrderSide::Buy) => maker.price <= taker.price,
+            _ => maker.price >= taker.price,
+        }
+    }
+
+    // Quantity available at prices the order would take.
+    fn crossing_qty(&self, order: &Order) -> i64 {
+        self.opposite(order)
+            .iter()
+            .map(|id| &self.orders[id])
+            .take_while(|maker| Self::crosses(order, maker))
+            .map(Order::remaining)
+            .sum()
+    }
+
+    fn match_order(
+        &mut self, taker_id: u64, fills: &mut Vec<Fill>, events: &mut Vec<(Topic, Value)>,
+    ) {
+        loop {
+            let taker = self.orders[&taker_id].clone();
+            let maker_id = match self.opposite(&taker).first() {
+                Some(id) => *id,
+                None => break,
+            };
+            let maker = self.orders[&maker_id].clone();
+            if !Self::crosses(&taker, &maker) {
+                break;
+            }
+
+            let mut qty = maker.remaining();
+            if taker.orig_qty == 0 {
+                // Market buy by quote quantity.
+                let quote_left = taker.orig_quote_qty - taker.cum_quote;
+                qty = qty.min((quote_left as i128 * UNIT as i128 / maker.price as i128) as i64);
+            } else {
+                qty = qty.min(taker.remaining());
+            }
+            if qty <= 0 {
+                if taker.status == OrderStatus::PartiallyFilled && taker.orig_qty == 0 {
+                    // Less than the smallest quantity is left to spend.
+                    if let Some(order) = self.orders.get_mut(&taker_id) {
+                        order.status = OrderStatus::Filled;
+                    }
+                    self.release_rest(taker_id);
+                }
+                break;
+            }
+
+            let trade_id = self.next_trade_id;
+            self.next_trade_id += 1;
+            let price = maker.price;
+            let time = now();
+
+            self.fill(maker_id, price, qty, true, trade_id, time);
+            let taker_fill = self.fill(taker_id, price, qty, false, trade_id, time);
+            if self.orders[&maker_id].remaining() == 0 {
+                self.remove_from_book(maker_id);
+            }
+            events.extend(self.order_events(maker_id, ExecutionType::Trade, Some(trade_id)));
+            events.extend(self.order_events(taker_id, ExecutionType::Trade, Some(trade_id)));
+            if let Some(fill) = taker_fill {
+                fills.push(fill);
+            }
+
+            let (buyer, seller) = match taker.side {
+                OrderSide::Buy => (taker_id, maker_id),
+                _ => (maker_id, taker_id),
+            };
+            let trade = json!({
+                "e": "trade",
+                "E": time,
+                "s": taker.symbol,
+                "t": trade_id,
+                "p": format_units(price),
+                "q": format_units(qty),
+                "b": buyer,
+                "a": seller,
+                "T": time,
+                "m": taker.side != OrderSide::Buy,
+                "M": true,
+            });
+            self.publish(Topic::Trades(taker.venue, taker.symbol.clone()), &trade);
+        }
+    }
+
+    // Books one side of a trade, returns the fill if the order is the user's.
+    fn fill(&mut self, id: u64, price: i64, qty: i64, is_maker: bool, trade_id: u64, time: u64) -> Option<Fill> {
+        let quote = notional(price, qty);
+        let bps = if is_maker { self.maker_bps } else { self.taker_bps };
+
+        let order = self.orders.get_mut(&id)?;
+        order.executed_qty += qty;
+        order.cum_quote += quote;
+        order.update_time = time;
+        let done = if order.orig_qty == 0 {
+            order.cum_quote >= order.orig_quote_qty
+        } else {
+            order.remaining() == 0
+        };
+        order.status = if done { OrderStatus::Filled } else { OrderStatus::PartiallyFilled };
+        if order.owner != Owner::User {
+            return None;
+        }
+        let order = order.clone();
+
+        let (base, quote_asset) = self.symbols[&order.symbol].clone();
+        let is_buyer = order.side == OrderSide::Buy;
+        let (commission, commission_asset) = match order.venue {
+            Venue::Spot => {
+                let (spent, received, received_asset) = if is_buyer {
+                    (quote, qty, base)
+                } else {
+                    (qty, quote, quote_asset)
+                };
+                let commission = fee(received, bps);
+                self.release(id, spent);
+                self.balances.entry(received_asset.clone()).or_default().free += received - commission;
+                (commission, received_asset)
+            }
+            Venue::Futures => {
+                let commission = fee(quote, bps);
+                let signed_qty = if is_buyer { qty } else { -qty };
+                let realized = self.apply_position(&order.symbol, signed_qty, price);
+                *self.futures_balances.entry(quote_asset.clone()).or_default() += realized - commission;
+                (commission, quote_asset)
+            }
+        };
+        if done {
+            self.release_rest(id);
+        }
+
+        self.trades.push(UserTrade {
+            symbol: order.symbol.clone(),
+            id: trade_id,
+            order_id: id,
+            price,
+            qty,
+            commission,
+            commission_asset: commission_asset.clone(),
+            time,
+            is_buyer,
+            is_maker,
+        });
+        Some(Fill {
+            trade_id,
+            price,
+            qty,
+            commission,
+            commission_asset,
+        })
+    }
+
+    // Spends reserved funds.
+    fn release(&mut self, id: u64, spent: i64) {
+        let order = match self.orders.get_mut(&id) {
+            Some(order) => order,
+            None => return,
+        };
+        let spent = spent.min(order.reserved);
+        order.reserved -= spent;
+        let asset = order.reserved_asset.clone();
+        self.balances.entry(asset).or_default().locked -= spent;
+    }
+
+    // Returns whatever the order no longer needs to the free balance.
+    fn release_rest(&mut self, id: u64) {
+        let order = match self.orders.get_mut(&id) {
+            Some(order) => order,
+            None => return,
+        };
+        let rest = order.reserved;
+        order.reserved = 0;
+        let asset = order.reserved_asset.clone();
+        if rest != 0 {
+            let balance = self.balances.entry(asset).or_default();
+            balance.locked -= rest;
+            balance.free += rest;
+        }
+    }
+
+    // Updates the position, returns the realized profit.
+    fn apply_position(&mut self, symbol: &str, signed_qty: i64, price: i64) -> i64 {
+        let position = self.positions.entry(symbol.to_string()).or_default();
+        let mut realized = 0;
+        if position.amount == 0 || (position.amount > 0) == (signed_qty > 0) {
+            let total = position.amount.abs() + signed_qty.abs();
+            let value = position.entry_price as i128 * position.amount.abs() as i128
+                + price as i128 * signed_qty.abs() as i128;
+            position.entry_price = (value / total as i128) as i64;
+            position.amount += signed_qty;
+        } else {
+            let closed = position.amount.abs().min(signed_qty.abs());
+            let direction = position.amount.signum();
+            realized = notional(price - position.entry_price, closed) * direction;
+            position.amount += signed_qty;
+            if position.amount == 0 {
+                position.entry_price = 0;
+            } else if position.amount.signum() != direction {
+                position.entry_price = price;
+            }
+        }
+        position.realized += realized;
+        realized
+    }
+
+    fn insert_in_book(&mut self, id: u64) {
+        let order = &self.orders[&id];
+        let (price, side) = (order.price, order.side.clone());
+        let key = (order.venue, order.symbol.clone());
+        let orders = &self.orders;
+        let book = self.books.entry(key).or_default();
+        let ids = if side == OrderSide::Buy { &mut book.bids } else { &mut book.asks };
+        let position = ids
+            .iter()
+            .position(|other| {
+                let other = orders[other].price;
+                if side == OrderSide::Buy { other < price } else { other > price }
+            })
+            .unwrap_or(ids.len());
+        ids.insert(position, id);
+    }
+
+    fn remove_from_book(&mut self, id: u64) {
+        let order = &self.orders[&id];
+        if let Some(book) = self.books.get_mut(&(order.venue, order.symbol.clone())) {
+            book.bids.retain(|other| *other != id);
+            book.asks.retain(|other| *other != id);
+        }
+    }
+
+    fn finish(&mut self, id: u64, status: OrderStatus) {
+        self.remove_from_book(id);
+        self.release_rest(id);
+        if let Some(order) = self.orders.get_mut(&id) {
+            order.status = status;
+            order.update_time = now();
+        }
+        self.update_id += 1;
+    }
+
+    fn find_order(&self, venue: Venue, params: &BTreeMap<String, String>) -> std::result::Result<u64, ApiError> {
+        let symbol = self.symbol(params)?;
+        let order_id = params.get("orderId").and_then(|id| id.parse().ok());
+        let client_order_id = params.get("origClientOrderId");
+        self.orders
+            .values()
+            .find(|o| {
+                o.venue == venue
+                    && o.owner == Owner::User
+                    && o.symbol == symbol
+                    && (Some(o.id) == order_id || Some(&o.client_order_id) == client_order_id)
+            })
+            .map(|o| o.id)
+            .ok_or(ApiError {
+                status: 400,
+                code: -2013,
+                msg: "Order does not exist.".into(),
+            })
+    }
+
+    fn query_order(&self, venue: Venue, params: &BTreeMap<String, String>) -> ApiResult {
+        let id = self.find_order(venue, params)?;
+        Ok(self.order_json(&self.orders[&id]))
+    }
+
+    fn cancel_order(&mut self, venue: Venue, params: &BTreeMap<String, String>) -> ApiResult {
+        let id = match self.find_order(venue, params) {
+            Ok(id) if self.orders[&id].is_open() => id,
+            _ => return reject(-2011, "Unknown order sent."),
+        };
+        self.finish(id, OrderStatus::Canceled);
+        let events = self.order_events(id, ExecutionType::Canceled, None);
+        self.publish_all(events);
+        Ok(self.canceled_json(id))
+    }
+
+    fn cancel_open_orders(&mut self, venue: Venue, params: &BTreeMap<String, String>) -> ApiResult {
+        let symbol = self.symbol(params)?;
+        let ids: Vec<u64> = self
+            .orders
+            .values()
+            .filter(|o| o.venue == venue && o.owner == Owner::User && o.symbol == symbol && o.is_open())
+            .map(|o| o.id)
+            .collect();
+        let mut canceled = Vec::new();
+        for id in ids {
+            self.finish(id, OrderStatus::Canceled);
+            let events = self.order_events(id, ExecutionType::Canceled, None);
+            self.publish_all(events);
+            canceled.push(self.canceled_json(id));
+        }
+        Ok(Value::Array(canceled))
+    }
+
+    fn cancel_batch(&mut self, params: &BTreeMap<String, String>) -> ApiResult {
+        let symbol = self.symbol(params)?.to_string();
+        let ids: Vec<u64> = match params.get("orderIdList").map(|ids| serde_json::from_str(ids)) {
+            Some(Ok(ids)) => ids,
+            _ => return reject(-1102, "Mandatory parameter 'orderIdList' was not sent, was empty/null, or malformed."),
+        };
+        let mut results = Vec::new();
+        for id in ids {
+            let mut params = BTreeMap::new();
+            params.insert("symbol".to_string(), symbol.clone());
+            params.insert("orderId".to_string(), id.to_string());
+            results.push(match self.cancel_order(Venue::Futures, &params) {
+                Ok(canceled) => canceled,
+                Err(e) => json!({ "code": e.code, "msg": e.msg }),
+            });
+        }
+        Ok(Value::Array(results))
+    }
+
+    // `Option::is_none_or` would need Rust 1.82.
+    #[allow(clippy::unnecessary_map_or)]
+    fn open_orders(&self, venue: Venue, params: &BTreeMap<String, String>) -> ApiResult {
+        let symbol = match params.get("symbol") {
+            Some(_) => Some(self.symbol(params)?),
+            None => None,
+        };
+        Ok(Value::Array(
+            self.orders
+                .values()
+                .filter(|o| o.venue == venue && o.owner == Owner::User && o.is_open())
+                .filter(|o| symbol.map_or(true, |symbol| o.symbol == symbol))
+                .map(|o| self.order_json(o))
+                .collect(),
+        ))
+    }
+
+    fn all_orders(&self, params: &BTreeMap<String, String>) -> ApiResult {
+        let symbol = self.symbol(params)?;
+        Ok(Value::Array(
+            self.orders
+                .values()
+                .filter(|o| o.venue == Venue::Spot && o.owner == Owner::User && o.symbol == symbol)
+                .map(|o| self.order_json(o))
+                .collect(),
+        ))
+    }
+
+    fn my_trades(&self, params: &BTreeMap<String, String>) -> ApiResult {
+        let symbol = self.symbol(params)?;
+        Ok(Value::Array(
+            self.trades
+                .iter()
+                .filter(|t| t.symbol == symbol && self.orders[&t.order_id].venue == Venue::Spot)
+                .map(|t| {
+                    json!({
+                        "symbol": t.symbol,
+                        "id": t.id,
+                        "orderId": t.order_id,
+                        "orderListId": -1,
+                        "price": format_units(t.price),
+                        "qty": format_units(t.qty),
+                        "quoteQty": format_units(notional(t.price, t.qty)),
+                        "commission": format_units(t.commission),
+                        "commissionAsset": t.commission_asset,
+                        "time": t.time,
+                        "isBuyer": t.is_buyer,
+                        "isMaker": t.is_maker,
+                        "isBestMatch": true,
+                    })
+                })
+                .collect(),
+        ))
+    }
+
+    fn account(&self) -> Value {
+        json!({
+            "makerCommission": self.maker_bps,
+            "takerCommission": self.taker_bps,
+            "buyerCommission": 0,
+            "sellerCommission": 0,
+            "canTrade": true,
+            "canWithdraw": true,
+            "canDeposit": true,
+            "updateTime": now(),
+            "accountType": "SPOT",
+            "balances": self.balances_json("asset", "free", "locked"),
+            "permissions": ["SPOT"],
+        })
+    }
+
+    fn balances_json(&self, asset: &str, free: &str, locked: &str) -> Vec<Value> {
+        self.balances
+            .iter()
+            .map(|(name, balance)| {
+                json!({
+                    asset: name,
+                    free: format_units(balance.free),
+                    locked: format_units(balance.locked),
+                })
+            })
+            .collect()
+    }
+
+    fn change_leverage(&mut self, params: &BTreeMap<String, String>) -> ApiResult {
+        let symbol = self.symbol(params)?.to_string();
+        let leverage = match params.get("leverage").and_then(|l| l.parse::<u8>().ok()) {
+            Some(leverage) if (1..=125).contains(&leverage) => leverage,
+            _ => return reject(-4028, "Leverage is not valid"),
+        };
+        self.leverage.insert(symbol.clone(), leverage);
+        Ok(json!({
+            "leverage": leverage,
+            "maxNotionalValue": "1000000",
+            "symbol": symbol,
+        }))
+    }
+
+    fn new_listen_key(&mut self, venue: Venue) -> Value {
+        let listen_key = format!("mockListenKey{:051}", self.next_listen_key);
+        self.next_listen_key += 1;
+        self.listen_keys.insert(listen_key.clone(), venue);
+        json!({ "listenKey": listen_key })
+    }
+
+    fn keep_alive(&mut self, params: &BTreeMap<String, String>) -> ApiResult {
+        match params.get("listenKey") {
+            Some(listen_key) if self.listen_keys.contains_key(listen_key) => Ok(json!({})),
+            _ => reject(-1125, "This listenKey does not exist."),
+        }
+    }
+
+    fn close_listen_key(&mut self, params: &BTreeMap<String, String>) -> ApiResult {
+        let listen_key = match params.get("listenKey") {
+            Some(listen_key) if self.listen_keys.contains_key(listen_key) => listen_key,
+            _ => return reject(-1125, "This listenKey does not exist."),
+        };
+        self.listen_keys.remove(listen_key);
+        // Dropping the senders closes the connections.
+        self.subscribers
+            .retain(|s| s.listen_key.as_ref() != Some(listen_key));
+        Ok(json!({}))
+    }
+
+    // Renders the user-data events for the order as it is now.
+    fn order_events(&self, id: u64, execution_type: ExecutionType, trade_id: Option<u64>) -> Vec<(Topic, Value)> {
+        let mut events = Vec::new();
+        let order = &self.orders[&id];
+        if order.owner != Owner::User {
+            return events;
+        }
+        let trade = trade_id
+            .and_then(|trade_id| self.trades.iter().find(|t| t.id == trade_id && t.order_id == id));
+        let traded = trade.is_some();
+        let (last_qty, last_price, commission, commission_asset, is_maker) = match trade {
+            Some(t) => (t.qty, t.price, t.commission, Some(t.commission_asset.clone()), t.is_maker),
+            None => (0, 0, 0, None, false),
+        };
+        let time = now();
+
+        match order.venue {
+            Venue::Spot => {
+                let report = json!({
+                    "e": "executionReport",
+                    "E": time,
+                    "s": order.symbol,
+                    "c": order.client_order_id,
+                    "S": order.side,
+                    "o": order.order_type,
+                    "f": order.time_in_force,
+                    "q": format_units(order.orig_qty),
+                    "p": format_units(order.price),
+                    "P": "0.00000000",
+                    "F": "0.00000000",
+                    "g": -1,
+                    "C": "",
+                    "x": execution_type,
+                    "X": order.status,
+                    "r": "NONE",
+                    "i": order.id,
+                    "l": format_units(last_qty),
+                    "z": format_units(order.executed_qty),
+                    "L": format_units(last_price),
+                    "n": format_units(commission),
+                    "N": commission_asset,
+                    "T": order.update_time,
+                    "t": trade_id.map(|id| id as i64).unwrap_or(-1),
+                    "I": 0,
+                    "w": order.is_open(),
+                    "m": is_maker,
+                    "M": false,
+                    "O": order.time,
+                    "Z": format_units(order.cum_quote),
+                    "Y": format_units(notional(last_price, last_qty)),
+                    "Q": format_units(order.orig_quote_qty),
+                });
+                events.push((Topic::UserData(Venue::Spot), report));
+                if execution_type != ExecutionType::New || order.reserved > 0 {
+                    let account = json!({
+                        "e": "outboundAccountInfo",
+                        "E": time,
+                        "m": self.maker_bps,
+                        "t": self.taker_bps,
+                        "b": 0,
+                        "s": 0,
+                        "T": true,
+                        "W": true,
+                        "D": true,
+                        "u": time,
+                        "B": self.balances_json("a", "f", "l"),
+                    });
+                    events.push((Topic::UserData(Venue::Spot), account));
+                }
+            }
+            Venue::Futures => {
+                let average_price = if order.executed_qty > 0 {
+                    (order.cum_quote as i128 * UNIT as i128 / order.executed_qty as i128) as i64
+                } else {
+                    0
+                };
+                let mut update = json!({
+                    "e": "ORDER_TRADE_UPDATE",
+                    "E": time,
+                    "T": time,
+                    "o": {
+                        "s": order.symbol,
+                        "c": order.client_order_id,
+                        "S": order.side,
+                        "o": order.order_type,
+                        "f": order.time_in_force,
+                        "q": format_units(order.orig_qty),
+                        "p": format_units(order.price),
+                        "ap": format_units(average_price),
+                        "sp": "0",
+                        "x": execution_type,
+                        "X": order.status,
+                        "i": order.id,
+                        "l": format_units(last_qty),
+                        "z": format_units(order.executed_qty),
+                        "L": format_units(last_price),
+                        "T": order.update_time,
+                        "t": trade_id.unwrap_or(0),
+                        "b": "0",
+                        "a": "0",
+                        "m": is_maker,
+                        "R": order.reduce_only,
+                        "wt": "CONTRACT_PRICE",
+                        "ot": order.order_type,
+                        "ps": "BOTH",
+                        "cp": false,
+                        "rp": "0",
+                    }
+                });
+                // Only present once the order traded.
+                if let Some(commission_asset) = commission_asset {
+                    update["o"]["N"] = json!(commission_asset);
+                    update["o"]["n"] = json!(format_units(commission));
+                }
+                events.push((Topic::UserData(Venue::Futures), update));
+                if traded {
+                    let (_, quote) = self.symbols[&order.symbol].clone();
+                    let position = self.positions.get(&order.symbol).copied().unwrap_or_default();
+                    let balance = self.futures_balance(&quote);
+                    let account = json!({
+                        "e": "ACCOUNT_UPDATE",
+                        "E": time,
+                        "T": time,
+                        "a": {
+                            "m": "ORDER",
+                            "B": [{
+                                "a": quote,
+                                "wb": format_units(balance),
+                                "cw": format_units(balance),
+                                "bc": "0",
+                            }],
+                            "P": [{
+                                "s": order.symbol,
+                                "pa": format_units(position.amount),
+                                "ep": format_units(position.entry_price),
+                                "cr": format_units(position.realized),
+                                "up": "0",
+                                "mt": "cross",
+                                "iw": "0",
+                                "ps": "BOTH",
+                            }],
+                        }
+                    });
+                    events.push((Topic::UserData(Venue::Futures), account));
+                }
+            }
+        }
+        events
+    }
+
+    fn publish_all(&mut self, events: Vec<(Topic, Value)>) {
+        for (topic, event) in events {
+            self.publish(topic, &event);
+        }
+    }
+
+    fn transaction(&self, order: &Order, fills: &[Fill]) -> Value {
+        match order.venue {
+            Venue::Spot => json!({
+                "symbol": order.symbol,
+                "orderId": order.id,
+                "orderListId": -1,
+                "clientOrderId": order.client_order_id,
+                "transactTime": order.update_time,
+                "price": format_units(order.price),
+                "origQty": format_units(order.orig_qty),
+                "executedQty": format_units(order.executed_qty),
+                "cummulativeQuoteQty": format_units(order.cum_quote),
+                "status": order.status,
+                "timeInForce": order.time_in_force,
+                "type": order.order_type,
+                "side": order.side,
+                "fills": fills.iter().map(|fill| json!({
+                    "price": format_units(fill.price),
+                    "qty": format_units(fill.qty),
+                    "commission": format_units(fill.commission),
+                    "commissionAsset": fill.commission_asset,
+                    "tradeId": fill.trade_id,
+                })).collect::<Vec<_>>(),
+            }),
+            Venue::Futures => self.order_json(order),
+        }
+    }
+
+    fn order_json(&self, order: &Order) -> Value {
+        match order.venue {
+            Venue::Spot => json!({
+                "symbol": order.symbol,
+                "orderId": order.id,
+                "orderListId": -1,
+                "clientOrderId": order.client_order_id,
+                "price": format_units(order.price),
+                "origQty": format_units(order.orig_qty),
+                "executedQty": format_units(order.executed_qty),
+                "cummulativeQuoteQty": format_units(order.cum_quote),
+                "status": order.status,
+                "timeInForce": order.time_in_force,
+                "type": order.order_type,
+                "side": order.side,
+                "stopPrice": "0.00000000",
+                "icebergQty": "0.00000000",
+                "time": order.time,
+                "updateTime": order.update_time,
+                "isWorking": true,
+                "origQuoteOrderQty": format_units(order.orig_quote_qty),
+            }),
+            Venue::Futures => {
+                let average_price = if order.executed_qty > 0 {
+                    (order.cum_quote as i128 * UNIT as i128 / order.executed_qty as i128) as i64
+                } else {
+                    0
+                };
+                json!({
+                    "clientOrderId": order.client_order_id,
+                    "cumQty": format_units(order.executed_qty),
+                    "cumQuote": format_units(order.cum_quote),
+                    "executedQty": format_units(order.executed_qty),
+                    "orderId": order.id,
+                    "avgPrice": format_units(average_price),
+                    "origQty": format_units(order.orig_qty),
+                    "price": format_units(order.price),
+                    "reduceOnly": order.reduce_only,
+                    "side": order.side,
+                    "positionSide": "BOTH",
+                    "status": order.status,
+                    "stopPrice": "0",
+                    "closePosition": false,
+                    "symbol": order.symbol,
+                    "timeInForce": order.time_in_force,
+                    "type": order.order_type,
+                    "origType": order.order_type,
+                    "updateTime": order.update_time,
+                    "workingType": "CONTRACT_PRICE",
+                    "priceProtect": false,
+                })
+            }
+        }
+    }
+
+    fn canceled_json(&self, id: u64) -> Value {
+        let order = &self.orders[&id];
+        let mut canceled = self.order_json(order);
+        canceled["origClientOrderId"] = json!(order.client_order_id);
+        canceled
+    }
+}
+
+fn required<'a>(params: &'a BTreeMap<String, String>, name: &str) -> std::result::Result<&'a str, ApiError> {
+    match params.get(name) {
+        Some(value) if !value.is_empty() => Ok(value),
+        _ => reject(
+            -1102,
+            format!("Mandatory parameter '{}' was not sent, was empty/null, or malformed.", name),
+        ),
+    }
+}
+
+fn amount(params: &BTreeMap<String, String>, name: &str) -> std::result::Result<Option<i64>, ApiError> {
+    match params.get(name) {
+        None => Ok(None),
+        Some(value) => match value.parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(Some(to_units(value))),
+            _ => reject(-1100, format!("Illegal characters found in parameter '{}'.", name)),
+        },
+    }
+}
@@ -0,0 +1,102 @@
+// Just enough HTTP/1.1 for the REST client: keep-alive connections and
+// `Content-Length` bodies, no chunked encoding.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+pub(crate) struct Request {
+    pub method: String,
+    pub path: String,
+    /// Query and form body parameters, the body wins on duplicates.
+    pub params: BTreeMap<String, String>,
+    /// Lowercase header names.
+    pub headers: BTreeMap<String, String>,
+}
+
+pub(crate) struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Serves requests on `stream` until the client hangs up.
+pub(crate) fn serve<F>(stream: TcpStream, mut handle: F) -> io::Result<()>
+where
+    F: FnMut(Request) -> Response,
+{
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    while let Some(request) = read_request(&mut reader)? {
+        let response = handle(request);
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json;charset=UTF-8\r\nContent-Length: {}\r\n\r\n{}",
+            response.status,
+            reason(response.status),
+            response.body.len(),
+            response.body
+        )?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> io::Result<Option<Request>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => (method.to_string(), target.to_string()),
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "Malformed request line")),
+    };
+
+    let mut headers = BTreeMap::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length = headers
+        .get("content-length")
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), query.to_string()),
+        None => (target, String::new()),
+    };
+    let mut params = BTreeMap::new();
+    for source in [query.as_bytes(), body.as_slice()] {
+        params.extend(url::form_urlencoded::parse(source).into_owned());
+    }
+
+    Ok(Some(Request {
+        method,
+        path,
+        params,
+        headers,
+    }))
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        _ => "Unknown",
+    }
+}
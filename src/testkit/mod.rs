@@ -0,0 +1,231 @@
+//! A local stand-in for the exchange, for testing order lifecycles without
+//! the testnet. Requires the `testkit` feature.
+//!
+//! [`MockExchange`] serves the spot and futures REST endpoints used by this
+//! crate, with order books and a matching engine, and user-data and trade
+//! streams over websockets.
+//!
+//! ```rust,no_run
+//! use binance::account::*;
+//! use binance::api::*;
+//! use binance::model::Amount;
+//! use binance::testkit::*;
+//!
+//! let exchange = MockExchange::start().unwrap();
+//! exchange.add_symbol("BTCUSDT", "BTC", "USDT");
+//! exchange.set_balance("USDT", 10_000.0);
+//! exchange.add_liquidity(Venue::Spot, "BTCUSDT", OrderSide::Sell, 20_000.0, 1.0);
+//!
+//! let account: Account = Binance::new_with_config(Some("key".into()), Some("secret".into()), &exchange.config()).unwrap();
+//! let transaction = account.market_buy("BTCUSDT", "0.1".parse::<Amount>().unwrap()).unwrap();
+//! assert_eq!(transaction.status, OrderStatus::Filled);
+//! assert_eq!(exchange.balance("USDT"), (8_000.0, 0.0));
+//! ```
+//!
+//! Supported orders are `LIMIT` (GTC, IOC, FOK, and GTX on futures), `MARKET`
+//! and spot `LIMIT_MAKER`. Spot orders lock and settle balances with the
+//! configured commission; futures fills update the position and the wallet
+//! balance of the quote asset, without margin checks or liquidations.
+//! Requests need an API key and the `timestamp` and `signature` parameters,
+//! but signatures aren't verified.
+//!
+//! Listen keys are served at `<ws_endpoint>/<listenKey>` and public trades at
+//! `<ws_endpoint>/<symbol>@trade`, or under the futures websocket endpoint
+//! for futures trades.
+
+mod engine;
+mod http;
+
+use self::engine::{from_units, to_units, Exchange};
+use crate::config::Config;
+use crate::errors::*;
+use crate::util::lock;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::StatusCode;
+use tungstenite::Message;
+
+pub use crate::model::{OrderSide, OrderStatus};
+
+/// Market an order book belongs to, the same symbol has one book on each.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Venue {
+    Spot,
+    Futures,
+}
+
+/// A running mock exchange, the servers stop when it is dropped.
+pub struct MockExchange {
+    exchange: Arc<Mutex<Exchange>>,
+    rest_addr: SocketAddr,
+    ws_addr: SocketAddr,
+    running: Arc<AtomicBool>,
+}
+
+impl MockExchange {
+    /// Starts the REST and websocket servers on free local ports.
+    pub fn start() -> Result<Self> {
+        let rest_listener = TcpListener::bind("127.0.0.1:0")?;
+        let ws_listener = TcpListener::bind("127.0.0.1:0")?;
+        let mock = MockExchange {
+            exchange: Arc::new(Mutex::new(Exchange::new())),
+            rest_addr: rest_listener.local_addr()?,
+            ws_addr: ws_listener.local_addr()?,
+            running: Arc::new(AtomicBool::new(true)),
+        };
+
+        let exchange = mock.exchange.clone();
+        let running = mock.running.clone();
+        thread::spawn(move || {
+            for stream in rest_listener.incoming() {
+                if !running.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    let exchange = exchange.clone();
+                    thread::spawn(move || {
+                        http::serve(stream, |request| lock(&exchange).handle(request))
+                    });
+                }
+            }
+        });
+
+        let exchange = mock.exchange.clone();
+        let running = mock.running.clone();
+        thread::spawn(move || {
+            for stream in ws_listener.incoming() {
+                if !running.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    let exchange = exchange.clone();
+                    thread::spawn(move || serve_websocket(stream, &exchange));
+                }
+            }
+        });
+
+        Ok(mock)
+    }
+
+    pub fn rest_endpoint(&self) -> String {
+        format!("http://{}", self.rest_addr)
+    }
+
+    pub fn ws_endpoint(&self) -> String {
+        format!("ws://{}/ws", self.ws_addr)
+    }
+
+    pub fn futures_ws_endpoint(&self) -> String {
+        format!("ws://{}/futures/ws", self.ws_addr)
+    }
+
+    /// A default config with every endpoint pointing at the mock exchange.
+    pub fn config(&self) -> Config {
+        Config::default()
+            .set_rest_api_endpoint(self.rest_endpoint())
+            .set_futures_rest_api_endpoint(self.rest_endpoint())
+            .set_ws_endpoint(self.ws_endpoint())
+            .set_futures_ws_endpoint(self.futures_ws_endpoint())
+    }
+
+    /// Only accept this API key, by default any non-empty key is.
+    pub fn set_api_key<S: Into<String>>(&self, api_key: S) {
+        lock(&self.exchange).set_api_key(api_key.into());
+    }
+
+    /// Maker and taker commission in basis points, 10 each by default.
+    pub fn set_commission(&self, maker_bps: u32, taker_bps: u32) {
+        lock(&self.exchange).set_commission(maker_bps.into(), taker_bps.into());
+    }
+
+    /// Lists `symbol` on both the spot and futures markets.
+    pub fn add_symbol(&self, symbol: &str, base_asset: &str, quote_asset: &str) {
+        lock(&self.exchange).add_symbol(symbol, base_asset, quote_asset);
+    }
+
+    /// Sets the free spot balance of `asset`.
+    pub fn set_balance(&self, asset: &str, free: f64) {
+        lock(&self.exchange).set_balance(asset, to_units(free));
+    }
+
+    /// Free and locked spot balance of `asset`.
+    pub fn balance(&self, asset: &str) -> (f64, f64) {
+        let (free, locked) = lock(&self.exchange).balance(asset);
+        (from_units(free), from_units(locked))
+    }
+
+    pub fn set_futures_balance(&self, asset: &str, balance: f64) {
+        lock(&self.exchange).set_futures_balance(asset, to_units(balance));
+    }
+
+    /// Futures wallet balance of `asset`, including realized profits and commissions.
+    pub fn futures_balance(&self, asset: &str) -> f64 {
+        from_units(lock(&self.exchange).futures_balance(asset))
+    }
+
+    /// Futures position amount, negative when short, and entry price.
+    pub fn position(&self, symbol: &str) -> (f64, f64) {
+        let (amount, entry_price) = lock(&self.exchange).position(symbol);
+        (from_units(amount), from_units(entry_price))
+    }
+
+    /// Number of open orders placed through the API.
+    pub fn open_order_count(&self, venue: Venue) -> usize {
+        lock(&self.exchange).open_order_count(venue)
+    }
+
+    /// Rests an order from another trader in the book, user orders trade against it.
+    ///
+    /// Panics if `symbol` isn't listed or the order is invalid.
+    pub fn add_liquidity(&self, venue: Venue, symbol: &str, side: OrderSide, price: f64, qty: f64) {
+        lock(&self.exchange).add_liquidity(venue, symbol, side, to_units(price), to_units(qty));
+    }
+}
+
+impl Drop for MockExchange {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        lock(&self.exchange).close_streams();
+        // Wake the accept loops up so that they see the flag.
+        let _ = TcpStream::connect(self.rest_addr);
+        let _ = TcpStream::connect(self.ws_addr);
+    }
+}
+
+fn serve_websocket(stream: TcpStream, exchange: &Mutex<Exchange>) {
+    let (sender, receiver) = channel();
+    // Subscribing before the handshake completes means no event published
+    // after the client connected can be missed.
+    #[allow(clippy::result_large_err)]
+    let callback = |request: &Request, response: Response| {
+        let path = request.uri().path();
+        let (venue, stream_name) = match path.strip_prefix("/futures/ws/") {
+            Some(stream_name) => (Venue::Futures, stream_name),
+            None => (Venue::Spot, path.trim_start_matches("/ws/")),
+        };
+        if lock(exchange).subscribe(stream_name, venue, sender) {
+            Ok(response)
+        } else {
+            let mut not_found = ErrorResponse::new(Some(format!("Unknown stream {}", stream_name)));
+            *not_found.status_mut() = StatusCode::NOT_FOUND;
+            Err(not_found)
+        }
+    };
+    let mut socket = match tungstenite::accept_hdr(stream, callback) {
+        Ok(socket) => socket,
+        Err(_) => return,
+    };
+
+    // Ends once the exchange drops the sender, or the client goes away.
+    for frame in receiver {
+        if socket.write_message(Message::Text(frame)).is_err() {
+            return;
+        }
+    }
+    let _ = socket.close(None);
+    let _ = socket.write_pending();
+}
@@ -0,0 +1,145 @@
+//! Orders shared by the spot and futures accounts, live or simulated.
+//!
+//! Strategies written against [`Trading`] run unchanged on an
+//! [`Account`](crate::account::Account), a
+//! [`FuturesAccount`](crate::futures::account::FuturesAccount) or a
+//! [`PaperAccount`](crate::paper::PaperAccount):
+//!
+//! ```rust,no_run
+//! use binance::account::*;
+//! use binance::api::*;
+//! use binance::model::Amount;
+//! use binance::paper::*;
+//! use binance::trading::*;
+//!
+//! fn buy_the_dip(trader: &dyn Trading) {
+//!     let qty: Amount = "0.01".parse().unwrap();
+//!     let price: Amount = "19000".parse().unwrap();
+//!     trader.limit_buy("BTCUSDT", qty, price).unwrap();
+//! }
+//!
+//! let account: Account = Binance::new(Some("key".into()), Some("secret".into())).unwrap();
+//! let paper = PaperAccount::spot(&account.get_account().unwrap());
+//! paper.add_symbol("BTCUSDT", "BTC", "USDT");
+//!
+//! buy_the_dip(&paper);
+//! ```
+
+use crate::account::Account;
+use crate::errors::*;
+use crate::futures::account::FuturesAccount;
+use crate::futures::model as futures_model;
+use crate::model::{self, Amount, OrderSide, OrderStatus, OrderType, TimeInForce};
+
+/// State of an order once placed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderReport {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub status: OrderStatus,
+    pub orig_qty: Amount,
+    pub executed_qty: Amount,
+    pub cumulative_quote_qty: Amount,
+}
+
+impl From<model::Transaction> for OrderReport {
+    fn from(transaction: model::Transaction) -> Self {
+        OrderReport {
+            symbol: transaction.symbol,
+            order_id: transaction.order_id,
+            client_order_id: transaction.client_order_id,
+            side: transaction.side,
+            order_type: transaction.type_name,
+            status: transaction.status,
+            orig_qty: transaction.orig_qty,
+            executed_qty: transaction.executed_qty,
+            cumulative_quote_qty: transaction.cummulative_quote_qty,
+        }
+    }
+}
+
+impl From<futures_model::Transaction> for OrderReport {
+    fn from(transaction: futures_model::Transaction) -> Self {
+        OrderReport {
+            symbol: transaction.symbol,
+            order_id: transaction.order_id,
+            client_order_id: transaction.client_order_id,
+            side: transaction.side,
+            order_type: transaction.type_name,
+            status: transaction.status,
+            orig_qty: transaction.orig_qty,
+            executed_qty: transaction.executed_qty,
+            cumulative_quote_qty: transaction.cum_quote,
+        }
+    }
+}
+
+/// Placing and canceling orders. Limit orders are good till canceled.
+pub trait Trading {
+    fn limit_buy(&self, symbol: &str, qty: Amount, price: Amount) -> Result<OrderReport>;
+
+    fn limit_sell(&self, symbol: &str, qty: Amount, price: Amount) -> Result<OrderReport>;
+
+    fn market_buy(&self, symbol: &str, qty: Amount) -> Result<OrderReport>;
+
+    fn market_sell(&self, symbol: &str, qty: Amount) -> Result<OrderReport>;
+
+    fn cancel_order(&self, symbol: &str, order_id: u64) -> Result<()>;
+
+    fn cancel_all_open_orders(&self, symbol: &str) -> Result<()>;
+}
+
+impl Trading for Account {
+    fn limit_buy(&self, symbol: &str, qty: Amount, price: Amount) -> Result<OrderReport> {
+        Account::limit_buy(self, symbol, qty, price).map(OrderReport::from)
+    }
+
+    fn limit_sell(&self, symbol: &str, qty: Amount, price: Amount) -> Result<OrderReport> {
+        Account::limit_sell(self, symbol, qty, price).map(OrderReport::from)
+    }
+
+    fn market_buy(&self, symbol: &str, qty: Amount) -> Result<OrderReport> {
+        Account::market_buy(self, symbol, qty).map(OrderReport::from)
+    }
+
+    fn market_sell(&self, symbol: &str, qty: Amount) -> Result<OrderReport> {
+        Account::market_sell(self, symbol, qty).map(OrderReport::from)
+    }
+
+    fn cancel_order(&self, symbol: &str, order_id: u64) -> Result<()> {
+        Account::cancel_order(self, symbol, order_id).map(|_| ())
+    }
+
+    fn cancel_all_open_orders(&self, symbol: &str) -> Result<()> {
+        Account::cancel_all_open_orders(self, symbol).map(|_| ())
+    }
+}
+
+impl Trading for FuturesAccount {
+    fn limit_buy(&self, symbol: &str, qty: Amount, price: Amount) -> Result<OrderReport> {
+        FuturesAccount::limit_buy(self, symbol, qty, price, TimeInForce::GTC).map(OrderReport::from)
+    }
+
+    fn limit_sell(&self, symbol: &str, qty: Amount, price: Amount) -> Result<OrderReport> {
+        FuturesAccount::limit_sell(self, symbol, qty, price, TimeInForce::GTC).map(OrderReport::from)
+    }
+
+    fn market_buy(&self, symbol: &str, qty: Amount) -> Result<OrderReport> {
+        FuturesAccount::market_buy(self, symbol, qty, TimeInForce::GTC).map(OrderReport::from)
+    }
+
+    fn market_sell(&self, symbol: &str, qty: Amount) -> Result<OrderReport> {
+        FuturesAccount::market_sell(self, symbol, qty, TimeInForce::GTC).map(OrderReport::from)
+    }
+
+    fn cancel_order(&self, symbol: &str, order_id: u64) -> Result<()> {
+        FuturesAccount::cancel_order(self, symbol, order_id)
+    }
+
+    fn cancel_all_open_orders(&self, symbol: &str) -> Result<()> {
+        FuturesAccount::cancel_all_open_orders(self, symbol)
+    }
+}
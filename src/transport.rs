@@ -0,0 +1,354 @@
+//! The HTTP layer under the REST clients, and recording and replay of REST
+//! interactions and websocket frames so that tests and benches run offline.
+//!
+//! Record once against the exchange:
+//!
+//! ```rust,no_run
+//! use binance::api::*;
+//! use binance::config::*;
+//! use binance::market::*;
+//! use binance::transport::*;
+//!
+//! let config = Config::default();
+//! let recorder = RecordingTransport::new(ReqwestTransport::from_config(&config).unwrap(), "btcusdt.jsonl").unwrap();
+//! let market: Market = Binance::new_with_config(None, None, &config.set_transport(recorder)).unwrap();
+//! market.get_price("BTCUSDT").unwrap();
+//! ```
+//!
+//! Then serve the recording, without any network access:
+//!
+//! ```rust,no_run
+//! # use binance::api::*;
+//! # use binance::config::*;
+//! # use binance::market::*;
+//! # use binance::transport::*;
+//! let replay = ReplayTransport::from_file("btcusdt.jsonl").unwrap();
+//! let config = Config::default().set_transport(replay);
+//! let market: Market = Binance::new_with_config(None, None, &config).unwrap();
+//! market.get_price("BTCUSDT").unwrap();
+//! ```
+//!
+//! Recordings are JSON lines. API keys are never written, and the `timestamp`
+//! and `signature` parameters are left out and ignored when matching, so a
+//! recording of signed requests replays with any credentials.
+
+use crate::config::{Config, TlsBackend};
+use crate::errors::*;
+use crate::util::lock;
+use reqwest::blocking::ClientBuilder;
+use reqwest::header::HeaderValue;
+use reqwest::{Method, Proxy};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+// Parameters that change on every request, left out of recordings.
+const VOLATILE_PARAMETERS: &[&str] = &["timestamp", "signature"];
+const API_KEY_HEADER: &str = "x-mbx-apikey";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: String,
+    /// Full URL, including the query string.
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// Form-encoded body, empty if there is none.
+    pub body: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpResponse {
+    pub status: u16,
+    /// Lowercase header names.
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Sends REST requests. Non-success statuses are responses, not errors.
+pub trait Transport: fmt::Debug + Send + Sync {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        (**self).send(request)
+    }
+}
+
+/// The default transport, over a reqwest blocking client.
+#[derive(Clone, Debug)]
+pub struct ReqwestTransport {
+    client: reqwest::blocking::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: reqwest::blocking::Client) -> Self {
+        ReqwestTransport { client }
+    }
+
+    /// Builds the client from the HTTP options of `config`.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Ok(ReqwestTransport::new(build_http_client(config)?))
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let method = Method::from_bytes(request.method.as_bytes())
+            .map_err(|_| format!("Invalid HTTP method {}", request.method))?;
+
+        let mut request_builder = self.client.request(method, request.url.as_str());
+        for (name, value) in &request.headers {
+            let mut value = HeaderValue::from_str(value)?;
+            value.set_sensitive(name.eq_ignore_ascii_case(API_KEY_HEADER));
+            request_builder = request_builder.header(name.as_str(), value);
+        }
+        if !request.body.is_empty() {
+            request_builder = request_builder.body(request.body);
+        }
+
+        let response = request_builder.send()?;
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                value.to_str().ok().map(|value| (name.as_str().to_string(), value.to_string()))
+            })
+            .collect();
+        let body = response.text()?;
+
+        Ok(HttpResponse { status, headers, body })
+    }
+}
+
+/// A request as it is recorded: without host, API key, timestamp or signature.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub query: String,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub fn from_request(request: &HttpRequest) -> Result<Self> {
+        let url = Url::parse(&request.url)?;
+        Ok(RecordedRequest {
+            method: request.method.clone(),
+            path: url.path().to_string(),
+            query: strip_volatile(url.query().unwrap_or_default()),
+            body: strip_volatile(&request.body),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: HttpResponse,
+}
+
+/// Passes requests on to another transport and appends every interaction to a file.
+#[derive(Debug)]
+pub struct RecordingTransport {
+    inner: Box<dyn Transport>,
+    file: Mutex<BufWriter<File>>,
+}
+
+impl RecordingTransport {
+    /// Creates or truncates the recording at `path`.
+    pub fn new<T: Transport + 'static, P: AsRef<Path>>(inner: T, path: P) -> Result<Self> {
+        Ok(RecordingTransport {
+            inner: Box::new(inner),
+            file: Mutex::new(BufWriter::new(File::create(path)?)),
+        })
+    }
+}
+
+impl Transport for RecordingTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let recorded = RecordedRequest::from_request(&request)?;
+        let response = self.inner.send(request)?;
+
+        let mut headers = response.headers.clone();
+        headers.retain(|(name, _)| name != "set-cookie");
+        let interaction = Interaction {
+            request: recorded,
+            response: HttpResponse { headers, ..response.clone() },
+        };
+
+        let mut file = lock(&self.file);
+        serde_json::to_writer(&mut *file, &interaction)?;
+        file.write_all(b"\n")?;
+        file.flush()?;
+
+        Ok(response)
+    }
+}
+
+/// Serves recorded responses. Matching interactions are served in recorded
+/// order, and the last one keeps being served once they are used up, so
+/// polling code sees the final state. Unknown requests fail.
+#[derive(Debug)]
+pub struct ReplayTransport {
+    interactions: Mutex<Vec<(Interaction, bool)>>,
+}
+
+impl ReplayTransport {
+    pub fn new(interactions: Vec<Interaction>) -> Self {
+        ReplayTransport {
+            interactions: Mutex::new(interactions.into_iter().map(|i| (i, false)).collect()),
+        }
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut interactions = Vec::new();
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            interactions.push(serde_json::from_str(&line).map_err(|e| BinanceError::decode(e, &line))?);
+        }
+        Ok(ReplayTransport::new(interactions))
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let request = RecordedRequest::from_request(&request)?;
+        let mut interactions = lock(&self.interactions);
+
+        let unused = interactions
+            .iter()
+            .position(|(interaction, used)| !used && interaction.request == request);
+        let index = match unused {
+            Some(index) => index,
+            None => match interactions.iter().rposition(|(interaction, _)| interaction.request == request) {
+                Some(index) => index,
+                None => bail!(format!(
+                    "No recorded response for {} {}?{}",
+                    request.method, request.path, request.query
+                )),
+            },
+        };
+
+        interactions[index].1 = true;
+        Ok(interactions[index].0.response.clone())
+    }
+}
+
+/// Appends websocket text frames to a file, one per line.
+#[derive(Debug)]
+pub struct FrameRecorder {
+    file: BufWriter<File>,
+}
+
+impl FrameRecorder {
+    /// Creates or truncates the recording at `path`.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(FrameRecorder {
+            file: BufWriter::new(File::create(path)?),
+        })
+    }
+}
+
+/// Destination of the text frames received by
+/// [`WebSockets::event_loop`](crate::websockets::WebSockets::event_loop).
+pub trait FrameSink {
+    fn record(&mut self, frame: &str) -> Result<()>;
+}
+
+impl FrameSink for FrameRecorder {
+    fn record(&mut self, frame: &str) -> Result<()> {
+        // Newlines in JSON can only be whitespace, those inside strings are escaped.
+        let frame = frame.replace(['\r', '\n'], " ");
+        self.file.write_all(frame.as_bytes())?;
+        self.file.write_all(b"\n")?;
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+/// Reads the frames written by a [`FrameRecorder`].
+pub fn read_frames<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
+    let mut frames = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            frames.push(line);
+        }
+    }
+    Ok(frames)
+}
+
+fn strip_volatile(params: &str) -> String {
+    params
+        .split('&')
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or_default();
+            !pair.is_empty() && !VOLATILE_PARAMETERS.contains(&key)
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+pub(crate) fn build_http_client(config: &Config) -> Result<reqwest::blocking::Client> {
+    if let Some(http_client) = &config.http_client {
+        return Ok(http_client.clone());
+    }
+
+    let mut builder = reqwest::blocking::Client::builder()
+        .pool_idle_timeout(None)
+        .user_agent(config.user_agent.as_str())
+        .tcp_nodelay_(config.tcp_nodelay)
+        .timeout(config.timeout)
+        .connect_timeout(config.connect_timeout);
+    if let Some(max) = config.pool_max_idle_per_host {
+        builder = builder.pool_max_idle_per_host(max);
+    }
+    if let Some(proxy) = &config.proxy {
+        builder = builder.proxy(Proxy::all(proxy.as_str())?);
+    }
+    builder = match config.tls_backend {
+        TlsBackend::Default => builder,
+        TlsBackend::NativeTls => use_native_tls(builder)?,
+        TlsBackend::Rustls => use_rustls_tls(builder)?,
+    };
+
+    Ok(builder.build()?)
+}
+
+#[cfg(feature = "native-tls")]
+fn use_native_tls(builder: ClientBuilder) -> Result<ClientBuilder> {
+    Ok(builder.use_native_tls())
+}
+
+#[cfg(not(feature = "native-tls"))]
+fn use_native_tls(_builder: ClientBuilder) -> Result<ClientBuilder> {
+    bail!("native-tls support requires the native-tls feature")
+}
+
+#[cfg(feature = "rustls-tls")]
+fn use_rustls_tls(builder: ClientBuilder) -> Result<ClientBuilder> {
+    Ok(builder.use_rustls_tls())
+}
+
+#[cfg(not(feature = "rustls-tls"))]
+fn use_rustls_tls(_builder: ClientBuilder) -> Result<ClientBuilder> {
+    bail!("Rustls support requires the rustls-tls feature")
+}
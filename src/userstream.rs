@@ -1,9 +1,22 @@
 use crate::model::*;
 use crate::client::*;
+use crate::config::Config;
+use crate::credentials::Credentials;
 use crate::errors::*;
+use crate::util::lock;
 use crate::api::API;
 use crate::api::Futures;
+use crate::api::Sapi;
 use crate::api::Spot;
+use crate::websockets::{WebSockets, WebsocketEvent};
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+// Listen keys expire after 60 minutes without a keepalive.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30 * 60);
 
 #[derive(Clone)]
 pub struct UserStream {
@@ -40,4 +53,209 @@ impl UserStream {
         self.client
             .delete(API::Futures(Futures::UserDataStream), listen_key)
     }
+
+    // Cross margin user stream
+    pub fn start_margin(&self) -> Result<UserDataStream> {
+        self.client.post(API::Sapi(Sapi::UserDataStream))
+    }
+
+    pub fn keep_alive_margin(&self, listen_key: &str) -> Result<Success> {
+        self.client.put(API::Sapi(Sapi::UserDataStream), listen_key)
+    }
+
+    pub fn close_margin(&self, listen_key: &str) -> Result<Success> {
+        self.client
+            .delete(API::Sapi(Sapi::UserDataStream), listen_key)
+    }
+}
+
+/// Account a user data stream reports on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserStreamType {
+    Spot,
+    /// Cross margin.
+    Margin,
+    /// USD-M futures.
+    Futures,
+}
+
+impl UserStreamType {
+    fn start(self, user_stream: &UserStream) -> Result<UserDataStream> {
+        match self {
+            UserStreamType::Spot => user_stream.start(),
+            UserStreamType::Margin => user_stream.start_margin(),
+            UserStreamType::Futures => user_stream.start_futures(),
+        }
+    }
+
+    fn keep_alive(self, user_stream: &UserStream, listen_key: &str) -> Result<Success> {
+        match self {
+            UserStreamType::Spot => user_stream.keep_alive(listen_key),
+            UserStreamType::Margin => user_stream.keep_alive_margin(listen_key),
+            UserStreamType::Futures => user_stream.keep_alive_futures(listen_key),
+        }
+    }
+
+    fn close(self, user_stream: &UserStream, listen_key: &str) -> Result<Success> {
+        match self {
+            UserStreamType::Spot => user_stream.close(listen_key),
+            UserStreamType::Margin => user_stream.close_margin(listen_key),
+            UserStreamType::Futures => user_stream.close_futures(listen_key),
+        }
+    }
+}
+
+/// Runs a user data stream: gets a listen key, keeps it alive from a
+/// background thread, and gets a new one and reconnects when the stream
+/// reports it expired. The listen key is closed on drop.
+///
+/// ```rust,no_run
+/// use binance::config::*;
+/// use binance::userstream::*;
+/// use binance::websockets::*;
+/// use std::sync::atomic::AtomicBool;
+///
+/// let config = Config::default();
+/// let mut manager = UserDataStreamManager::new(Some("api_key".into()), UserStreamType::Futures, &config).unwrap();
+/// manager
+///     .run(
+///         |event: WebsocketEvent| {
+///             println!("{:?}", event);
+///             Ok(())
+///         },
+///         &AtomicBool::new(true),
+///     )
+///     .unwrap();
+/// ```
+pub struct UserDataStreamManager {
+    user_stream: UserStream,
+    stream_type: UserStreamType,
+    ws_endpoint: String,
+    keep_alive_interval: Duration,
+    listen_key: Arc<Mutex<Option<String>>>,
+    keep_alive: Option<(Sender<()>, JoinHandle<()>)>,
+}
+
+impl UserDataStreamManager {
+    /// Uses the REST and websocket endpoints of `config` for `stream_type`.
+    pub fn new(api_key: Option<String>, stream_type: UserStreamType, config: &Config) -> Result<Self> {
+        let (rest_api_endpoint, ws_endpoint) = match stream_type {
+            UserStreamType::Spot | UserStreamType::Margin => {
+                (config.rest_api_endpoint.clone(), config.ws_endpoint.clone())
+            }
+            UserStreamType::Futures => {
+                // The futures endpoint serves both raw and combined streams.
+                let endpoint = config.futures_ws_endpoint.trim_end_matches('/');
+                let ws_endpoint = if endpoint.ends_with("/ws") {
+                    endpoint.to_string()
+                } else {
+                    format!("{}/ws", endpoint)
+                };
+                (config.futures_rest_api_endpoint.clone(), ws_endpoint)
+            }
+        };
+        let credentials = Credentials::new(api_key, None);
+        Ok(UserDataStreamManager {
+            user_stream: UserStream {
+                client: Client::new(credentials, rest_api_endpoint, config)?,
+                recv_window: config.recv_window,
+            },
+            stream_type,
+            ws_endpoint,
+            keep_alive_interval: KEEP_ALIVE_INTERVAL,
+            listen_key: Arc::new(Mutex::new(None)),
+            keep_alive: None,
+        })
+    }
+
+    /// Every 30 minutes by default, listen keys expire after 60.
+    pub fn set_keep_alive_interval(mut self, keep_alive_interval: Duration) -> Self {
+        self.keep_alive_interval = keep_alive_interval;
+        self
+    }
+
+    /// The listen key in use, once started.
+    pub fn listen_key(&self) -> Option<String> {
+        lock(&self.listen_key).clone()
+    }
+
+    /// Gets a listen key and starts keeping it alive, unless already started.
+    pub fn start(&mut self) -> Result<String> {
+        if let Some(listen_key) = self.listen_key() {
+            return Ok(listen_key);
+        }
+        self.rekey()
+    }
+
+    /// Connects to the stream and hands its events to `handler` until
+    /// `running` is cleared or the connection fails. The `ListenKeyExpired`
+    /// event is handed over too before reconnecting with a new key.
+    pub fn run<Callback>(&mut self, mut handler: Callback, running: &AtomicBool) -> Result<()>
+    where
+        Callback: FnMut(WebsocketEvent) -> Result<()>,
+    {
+        let mut listen_key = self.start()?;
+        loop {
+            let mut expired = false;
+            let result = {
+                let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+                    if let WebsocketEvent::ListenKeyExpired(_) = event {
+                        expired = true;
+                        handler(event)?;
+                        // Ends the event loop, the stream sends nothing more.
+                        bail!("Listen key expired");
+                    }
+                    handler(event)
+                });
+                web_socket.connect_wss(format!("{}/{}", self.ws_endpoint.trim_end_matches('/'), listen_key))?;
+                let result = web_socket.event_loop(running);
+                let _ = web_socket.disconnect();
+                result
+            };
+            if !expired {
+                return result;
+            }
+            listen_key = self.rekey()?;
+        }
+    }
+
+    fn rekey(&mut self) -> Result<String> {
+        let listen_key = self.stream_type.start(&self.user_stream)?.listen_key;
+        *lock(&self.listen_key) = Some(listen_key.clone());
+        if self.keep_alive.is_none() {
+            self.spawn_keep_alive();
+        }
+        Ok(listen_key)
+    }
+
+    fn spawn_keep_alive(&mut self) {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let user_stream = self.user_stream.clone();
+        let stream_type = self.stream_type;
+        let interval = self.keep_alive_interval;
+        let listen_key = self.listen_key.clone();
+        let handle = thread::spawn(move || {
+            // Dropping the sender stops the thread without waiting for the next keepalive.
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                let current = lock(&listen_key).clone();
+                if let Some(current) = current {
+                    // An expired key is replaced once the stream reports it.
+                    let _ = stream_type.keep_alive(&user_stream, &current);
+                }
+            }
+        });
+        self.keep_alive = Some((stop, handle));
+    }
+}
+
+impl Drop for UserDataStreamManager {
+    fn drop(&mut self) {
+        if let Some((stop, handle)) = self.keep_alive.take() {
+            drop(stop);
+            let _ = handle.join();
+        }
+        if let Some(listen_key) = lock(&self.listen_key).take() {
+            let _ = self.stream_type.close(&self.user_stream, &listen_key);
+        }
+    }
 }
@@ -1,7 +1,60 @@
 use crate::errors::*;
+use crate::model::Amount;
 use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
 use std::time::{SystemTime, UNIX_EPOCH};
+use serde::Serialize;
 use serde_json::Value;
+use url::form_urlencoded;
+
+/// Parameters of a request.
+///
+/// Keys are kept sorted so that the encoded query, and therefore its
+/// signature, is deterministic. Values are percent-encoded on output.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RequestParams {
+    parameters: BTreeMap<String, String>,
+}
+
+impl RequestParams {
+    pub fn new() -> Self {
+        RequestParams::default()
+    }
+
+    pub fn insert<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.parameters.insert(key.into(), value.into());
+        self
+    }
+
+    /// Inserts a value serialized as JSON, e.g. `orderIdList=[1,2]`.
+    pub fn insert_json<K, V>(&mut self, key: K, value: &V) -> Result<&mut Self>
+    where
+        K: Into<String>,
+        V: Serialize + ?Sized,
+    {
+        let value = serde_json::to_string(value)?;
+        Ok(self.insert(key, value))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.parameters.get(key).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty()
+    }
+
+    /// Percent-encoded `key=value` pairs joined by `&`.
+    pub fn to_query(&self) -> String {
+        form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(&self.parameters)
+            .finish()
+    }
+}
 
 #[derive(Clone, Default)]
 pub struct RequestBuilder {
@@ -10,7 +63,7 @@ pub struct RequestBuilder {
 
 impl RequestBuilder {
     pub fn build_signed_request(
-        &self, mut parameters: BTreeMap<String, String>, recv_window: u64,
+        &self, mut parameters: RequestParams, recv_window: u64,
     ) -> Result<String> {
         let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH)?;
         let time = since_epoch.as_secs() * 1000 + u64::from(since_epoch.subsec_nanos()) / 1_000_000;
@@ -20,48 +73,64 @@ impl RequestBuilder {
             time - self.timestamp_shift.abs() as u64
         };
         if recv_window > 0 {
-            parameters.insert("recvWindow".into(), recv_window.to_string());
+            parameters.insert("recvWindow", recv_window.to_string());
         }
-        parameters.insert("timestamp".into(), time.to_string());
+        parameters.insert("timestamp", time.to_string());
         Ok(build_request(parameters))
     }
 }
 
-pub fn build_request(parameters: BTreeMap<String, String>) -> String {
-    let mut request = String::new();
-    for (key, value) in parameters {
-        let param = format!("{}={}&", key, value);
-        request.push_str(param.as_ref());
-    }
-    request.pop();
-    request
+pub fn build_request(parameters: RequestParams) -> String {
+    parameters.to_query()
 }
 
 pub fn build_signed_request(
-    parameters: BTreeMap<String, String>, recv_window: u64,
+    parameters: RequestParams, recv_window: u64,
 ) -> Result<String> {
     build_signed_request_custom(parameters, recv_window, SystemTime::now())
 }
 
 pub fn build_signed_request_custom(
-    mut parameters: BTreeMap<String, String>, recv_window: u64, start: SystemTime,
+    mut parameters: RequestParams, recv_window: u64, start: SystemTime,
 ) -> Result<String> {
     if recv_window > 0 {
-        parameters.insert("recvWindow".into(), recv_window.to_string());
+        parameters.insert("recvWindow", recv_window.to_string());
     }
     if let Ok(timestamp) = get_timestamp(start) {
-        parameters.insert("timestamp".into(), timestamp.to_string());
+        parameters.insert("timestamp", timestamp.to_string());
         return Ok(build_request(parameters));
     }
     bail!("Failed to get timestamp")
 }
 
-pub fn to_i64(v: &Value) -> i64 {
-    v.as_i64().unwrap()
+pub fn to_i64(v: &Value) -> Result<i64> {
+    match v.as_i64() {
+        Some(i) => Ok(i),
+        None => bail!(format!("Expected an integer, got {}", v)),
+    }
+}
+
+pub fn to_f64(v: &Value) -> Result<f64> {
+    Ok(to_str(v)?.parse()?)
+}
+
+pub fn to_amount(v: &Value) -> Result<Amount> {
+    let s = to_str(v)?;
+    s.parse()
+        .map_err(|e| format!("Invalid amount {:?}: {}", s, e).into())
+}
+
+fn to_str(v: &Value) -> Result<&str> {
+    match v.as_str() {
+        Some(s) => Ok(s),
+        None => bail!(format!("Expected a string, got {}", v)),
+    }
 }
 
-pub fn to_f64(v: &Value) -> f64 {
-    v.as_str().unwrap().parse().unwrap()
+// Locks the mutex even if a holder panicked, so that a panic on one thread,
+// e.g. one connection of the mock exchange, doesn't take the others down.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
 }
 
 fn get_timestamp(start: SystemTime) -> Result<u64> {
@@ -1,14 +1,22 @@
 use crate::errors::*;
 use crate::config::*;
 use crate::model::*;
+use crate::transport::{read_frames, FrameRecorder, FrameSink};
 use url::Url;
 use serde::{Deserialize, Serialize};
 
+use std::io;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
 use tungstenite::{connect, Message};
 use tungstenite::protocol::WebSocket;
 use tungstenite::client::AutoStream;
 use tungstenite::handshake::client::Response;
+use tungstenite::stream::Stream;
 
 #[allow(clippy::all)]
 enum WebsocketAPI {
@@ -25,7 +33,8 @@ impl WebsocketAPI {
                 "wss://stream.binance.com:9443/stream?streams={}",
                 subscription
             ),
-            WebsocketAPI::Custom(url) => url,
+            WebsocketAPI::Custom(url) if subscription.is_empty() => url,
+            WebsocketAPI::Custom(url) => format!("{}/{}", url.trim_end_matches('/'), subscription),
         }
     }
 }
@@ -37,6 +46,12 @@ pub enum WebsocketEvent {
     FuturesAccountUpdate(crate::futures::model::AccountUpdateEvent),
     OrderTrade(OrderTradeEvent),
     FuturesOrderUpdate(crate::futures::model::OrderUpdateEvent),
+    AccountPosition(AccountPositionEvent),
+    BalanceUpdate(BalanceUpdateEvent),
+    ListStatus(ListStatusEvent),
+    FuturesMarginCall(crate::futures::model::MarginCallEvent),
+    FuturesAccountConfigUpdate(crate::futures::model::AccountConfigUpdateEvent),
+    ListenKeyExpired(ListenKeyExpiredEvent),
     AggrTrades(AggrTradesEvent),
     Trade(TradeEvent),
     OrderBook(OrderBook),
@@ -50,6 +65,8 @@ pub enum WebsocketEvent {
 pub struct WebSockets<'a> {
     pub socket: Option<(WebSocket<AutoStream>, Response)>,
     handler: Box<dyn FnMut(WebsocketEvent) -> Result<()> + 'a>,
+    recorder: Option<Box<dyn FrameSink + 'a>>,
+    replay: Option<Box<dyn Iterator<Item = Result<String>> + 'a>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -77,6 +94,8 @@ impl<'a> WebSockets<'a> {
         WebSockets {
             socket: None,
             handler: Box::new(handler),
+            recorder: None,
+            replay: None,
         }
     }
 
@@ -84,6 +103,10 @@ impl<'a> WebSockets<'a> {
         self.connect_wss(WebsocketAPI::Default.params(subscription))
     }
 
+    /// Connects to `subscription` under `config.ws_endpoint`, e.g. `btcusdt@trade`.
+    ///
+    /// Up to 0.14 the subscription was ignored and the endpoint used as is. An
+    /// endpoint that already names the stream takes an empty subscription.
     pub fn connect_with_config(&mut self, subscription: &str, config: &Config) -> Result<()> {
         self.connect_wss(WebsocketAPI::Custom(config.ws_endpoint.clone()).params(subscription))
     }
@@ -92,15 +115,46 @@ impl<'a> WebSockets<'a> {
         self.connect_wss(WebsocketAPI::MultiStream.params(&endpoints.join("/")))
     }
 
-    fn connect_wss(&mut self, wss: String) -> Result<()> {
-        let url = Url::parse(&wss)?;
-        match connect(url) {
-            Ok(answer) => {
-                self.socket = Some(answer);
-                Ok(())
-            }
-            Err(e) => bail!(format!("Error during handshake {}", e)),
-        }
+    pub(crate) fn connect_wss(&mut self, wss: String) -> Result<()> {
+        self.socket = Some(connect_socket(wss)?);
+        Ok(())
+    }
+
+    /// Serves the frames recorded at `path` to `event_loop` instead of a live
+    /// stream. The loop returns once they are all handled.
+    pub fn connect_replay<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        self.replay_frames(read_frames(path)?.into_iter().map(Ok));
+        Ok(())
+    }
+
+    /// Serves `frames` to `event_loop` instead of a live stream, the first
+    /// error ends the loop.
+    pub fn replay_frames<I>(&mut self, frames: I)
+    where
+        I: IntoIterator<Item = Result<String>>,
+        I::IntoIter: 'a,
+    {
+        self.replay = Some(Box::new(frames.into_iter()));
+    }
+
+    /// Serves the frames of a [`Recorder`](crate::recorder::Recorder) file or
+    /// directory to `event_loop` instead of a live stream.
+    #[cfg(feature = "recorder")]
+    pub fn connect_recording<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let frames = crate::recorder::read_recording(path)?;
+        self.replay_frames(frames.map(|frame| frame.map(|frame| frame.frame)));
+        Ok(())
+    }
+
+    /// Appends every text frame received by `event_loop` to `path`, for `connect_replay`.
+    pub fn record_frames<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        self.record_to(FrameRecorder::new(path)?);
+        Ok(())
+    }
+
+    /// Hands every text frame received by `event_loop` to `sink` before it is handled.
+    pub fn record_to<S: FrameSink + 'a>(&mut self, sink: S) {
+        self.recorder = Some(Box::new(sink));
     }
 
     pub fn disconnect(&mut self) -> Result<()> {
@@ -116,41 +170,66 @@ impl<'a> WebSockets<'a> {
     }
 
     fn handle_msg(&mut self, msg: &str) -> Result<()> {
-        let value: serde_json::Value = serde_json::from_str(msg)?;
+        let value: serde_json::Value =
+            serde_json::from_str(msg).map_err(|e| BinanceError::decode(e, msg))?;
 
         if let Some(data) = value.get("data") {
             self.handle_msg(&data.to_string())?;
             return Ok(());
         }
 
-        if let Ok(events) = serde_json::from_value::<Events>(value) {
-            let action = match events {
-                Events::Vec(v) => WebsocketEvent::DayTickerAll(v),
-                Events::BookTickerEvent(v) => WebsocketEvent::BookTicker(v),
-                Events::AccountUpdateEvent(v) => WebsocketEvent::AccountUpdate(v),
-                Events::OrderTradeEvent(v) => WebsocketEvent::OrderTrade(v),
-                Events::AggrTradesEvent(v) => WebsocketEvent::AggrTrades(v),
-                Events::TradeEvent(v) => WebsocketEvent::Trade(v),
-                Events::DayTickerEvent(v) => WebsocketEvent::DayTicker(v),
-                Events::KlineEvent(v) => WebsocketEvent::Kline(v),
-                Events::OrderBook(v) => WebsocketEvent::OrderBook(v),
-                Events::DepthOrderBookEvent(v) => WebsocketEvent::DepthOrderBook(v),
-                Events::FuturesAccountUpdateEvent(v) => WebsocketEvent::FuturesAccountUpdate(v),
-                Events::FuturesOrderUpdate(v) => WebsocketEvent::FuturesOrderUpdate(v),
-            };
-            (self.handler)(action)?;
-        } else {
-            println!("msg: {}", msg);
+        // These are told apart by their event type, their fields overlap with other events.
+        let user_data = match value.get("e").and_then(|e| e.as_str()) {
+            Some("outboundAccountPosition") => Some(serde_json::from_str(msg).map(WebsocketEvent::AccountPosition)),
+            Some("balanceUpdate") => Some(serde_json::from_str(msg).map(WebsocketEvent::BalanceUpdate)),
+            Some("listStatus") => Some(serde_json::from_str(msg).map(WebsocketEvent::ListStatus)),
+            Some("MARGIN_CALL") => Some(serde_json::from_str(msg).map(WebsocketEvent::FuturesMarginCall)),
+            Some("ACCOUNT_CONFIG_UPDATE") => {
+                Some(serde_json::from_str(msg).map(WebsocketEvent::FuturesAccountConfigUpdate))
+            }
+            Some("listenKeyExpired") => Some(serde_json::from_str(msg).map(WebsocketEvent::ListenKeyExpired)),
+            _ => None,
+        };
+        if let Some(event) = user_data {
+            let event = event.map_err(|e| BinanceError::decode(e, msg))?;
+            return (self.handler)(event);
         }
-        Ok(())
+
+        let events = serde_json::from_value::<Events>(value).map_err(|e| BinanceError::decode(e, msg))?;
+        let action = match events {
+            Events::Vec(v) => WebsocketEvent::DayTickerAll(v),
+            Events::BookTickerEvent(v) => WebsocketEvent::BookTicker(v),
+            Events::AccountUpdateEvent(v) => WebsocketEvent::AccountUpdate(v),
+            Events::OrderTradeEvent(v) => WebsocketEvent::OrderTrade(v),
+            Events::AggrTradesEvent(v) => WebsocketEvent::AggrTrades(v),
+            Events::TradeEvent(v) => WebsocketEvent::Trade(v),
+            Events::DayTickerEvent(v) => WebsocketEvent::DayTicker(v),
+            Events::KlineEvent(v) => WebsocketEvent::Kline(v),
+            Events::OrderBook(v) => WebsocketEvent::OrderBook(v),
+            Events::DepthOrderBookEvent(v) => WebsocketEvent::DepthOrderBook(v),
+            Events::FuturesAccountUpdateEvent(v) => WebsocketEvent::FuturesAccountUpdate(v),
+            Events::FuturesOrderUpdate(v) => WebsocketEvent::FuturesOrderUpdate(v),
+        };
+        (self.handler)(action)
     }
 
     pub fn event_loop(&mut self, running: &AtomicBool) -> Result<()> {
         while running.load(Ordering::Relaxed) {
-            if let Some(ref mut socket) = self.socket {
+            if let Some(ref mut replay) = self.replay {
+                let msg = match replay.next() {
+                    Some(msg) => msg?,
+                    None => return Ok(()),
+                };
+                if let Err(e) = self.handle_msg(&msg) {
+                    bail!(format!("Error on handling stream message: {}", e));
+                }
+            } else if let Some(ref mut socket) = self.socket {
                 let message = socket.0.read_message()?;
                 match message {
                     Message::Text(msg) => {
+                        if let Some(ref mut recorder) = self.recorder {
+                            recorder.record(&msg)?;
+                        }
                         if let Err(e) = self.handle_msg(&msg) {
                             bail!(format!("Error on handling stream message: {}", e));
                         }
@@ -163,3 +242,156 @@ impl<'a> WebSockets<'a> {
         Ok(())
     }
 }
+
+fn connect_socket(wss: String) -> Result<(WebSocket<AutoStream>, Response)> {
+    let url = Url::parse(&wss)?;
+    match connect(url) {
+        Ok(answer) => Ok(answer),
+        Err(e) => bail!(format!("Error during handshake {}", e)),
+    }
+}
+
+// How often the reading thread of an `EventStream` checks for a shutdown while no message comes.
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Events of a websocket read on a thread of its own, for consumers that
+/// would rather receive them than be called back.
+///
+/// The events come through an `mpsc` channel, or by iterating over the
+/// stream, which blocks until the next event:
+///
+/// ```rust,no_run
+/// use binance::websockets::*;
+///
+/// let events = EventStream::connect("btcusdt@trade").unwrap();
+/// for event in events.take(10) {
+///     println!("{:?}", event.unwrap());
+/// }
+/// ```
+///
+/// Messages that can't be decoded, or that are of an unknown event type, come
+/// as errors and the stream carries on.
+/// The channel disconnects once the socket is closed, after a last error
+/// unless the stream was shut down.
+pub struct EventStream {
+    receiver: Receiver<Result<WebsocketEvent>>,
+    shutdown: ShutdownHandle,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Shuts an [`EventStream`] down from another thread than the one consuming it.
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    stopped: Arc<AtomicBool>,
+}
+
+impl EventStream {
+    pub fn connect(subscription: &str) -> Result<Self> {
+        Self::connect_wss(WebsocketAPI::Default.params(subscription))
+    }
+
+    /// Connects to `subscription` under `config.ws_endpoint`, see
+    /// [`WebSockets::connect_with_config`].
+    pub fn connect_with_config(subscription: &str, config: &Config) -> Result<Self> {
+        Self::connect_wss(WebsocketAPI::Custom(config.ws_endpoint.clone()).params(subscription))
+    }
+
+    pub fn connect_multiple_streams(endpoints: &[String]) -> Result<Self> {
+        Self::connect_wss(WebsocketAPI::MultiStream.params(&endpoints.join("/")))
+    }
+
+    fn connect_wss(wss: String) -> Result<Self> {
+        let (socket, _) = connect_socket(wss)?;
+        let tcp = match socket.get_ref() {
+            Stream::Plain(tcp) => tcp,
+            Stream::Tls(tls) => tls.get_ref(),
+        };
+        tcp.set_read_timeout(Some(SHUTDOWN_POLL_INTERVAL))?;
+        let shutdown = ShutdownHandle {
+            stopped: Arc::new(AtomicBool::new(false)),
+        };
+        let (sender, receiver) = mpsc::channel();
+        let stopped = shutdown.stopped.clone();
+        let handle = thread::spawn(move || read_events(socket, &sender, &stopped));
+        Ok(EventStream {
+            receiver,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// The channel the events are sent to, for `recv_timeout` or `try_recv`.
+    pub fn receiver(&self) -> &Receiver<Result<WebsocketEvent>> {
+        &self.receiver
+    }
+
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Closes the socket and waits for the reading thread to end. Events
+    /// already received can still be read from the channel.
+    pub fn shutdown(&mut self) {
+        self.shutdown.shutdown();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Iterator for EventStream {
+    type Item = Result<WebsocketEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl ShutdownHandle {
+    /// Stops the stream without waiting for the next message, the socket is
+    /// closed within a few tens of milliseconds.
+    pub fn shutdown(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+fn read_events(
+    mut socket: WebSocket<AutoStream>, sender: &Sender<Result<WebsocketEvent>>, stopped: &AtomicBool,
+) {
+    let events = sender.clone();
+    let mut decoder = WebSockets::new(move |event: WebsocketEvent| {
+        events.send(Ok(event)).map_err(|_| "Event receiver dropped".into())
+    });
+    while !stopped.load(Ordering::SeqCst) {
+        match socket.read_message() {
+            Ok(Message::Text(msg)) => {
+                // Fails to send as well if the receiver is gone.
+                if let Err(e) = decoder.handle_msg(&msg) {
+                    if sender.send(Err(e)).is_err() {
+                        break;
+                    }
+                }
+            }
+            Ok(Message::Close(e)) => {
+                let _ = sender.send(Err(format!("Disconnected {:?}", e).into()));
+                break;
+            }
+            Ok(_) => (),
+            // The read timed out, time to check whether to stop.
+            Err(tungstenite::Error::Io(ref e))
+                if e.kind() == io::ErrorKind::WouldBlock || e.kind() == io::ErrorKind::TimedOut => {}
+            Err(e) => {
+                let _ = sender.send(Err(e.into()));
+                break;
+            }
+        }
+    }
+    let _ = socket.close(None);
+    let _ = socket.write_pending();
+}
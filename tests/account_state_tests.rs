@@ -0,0 +1,248 @@
+use binance::account::*;
+use binance::account_state::*;
+use binance::api::*;
+use binance::config::*;
+use binance::futures::account::*;
+use binance::middleware::*;
+use binance::model::*;
+use binance::websockets::*;
+use serde_json::Value;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::amount;
+    use mockito::{mock, Matcher};
+    use std::fmt;
+    use std::sync::mpsc::channel;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    // A user data fixture with some fields replaced, through the websocket decoding.
+    fn event(fixture: &str, changes: Value) -> WebsocketEvent {
+        let msg = std::fs::read_to_string(format!("tests/mocks/websocket/user_data/{}", fixture)).unwrap();
+        let mut msg: Value = serde_json::from_str(&msg).unwrap();
+        for (path, value) in changes.as_object().unwrap() {
+            *msg.pointer_mut(path).unwrap() = value.clone();
+        }
+        let mut events = Vec::new();
+        {
+            let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+                events.push(event);
+                Ok(())
+            });
+            web_socket.test_handle_msg(&msg.to_string()).unwrap();
+        }
+        events.pop().unwrap()
+    }
+
+    // Applies `event` to `state` once the open orders of a snapshot arrived,
+    // before the reconciliation takes the lock.
+    struct EventDuringSnapshot {
+        state: Mutex<Option<AccountState>>,
+        event: WebsocketEvent,
+    }
+
+    impl fmt::Debug for EventDuringSnapshot {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("EventDuringSnapshot")
+        }
+    }
+
+    impl Middleware for EventDuringSnapshot {
+        fn after_receive(&self, request: &RequestInfo, _response: &ResponseInfo) {
+            if request.path == "/api/v3/openOrders" {
+                if let Some(state) = self.state.lock().unwrap().take() {
+                    state.handle_event(&self.event);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn spot_account_state() {
+        let mock_account = mock("GET", "/api/v3/account")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/get_account.json")
+            .expect(2)
+            .create();
+        let mock_open_orders = mock("GET", "/api/v3/openOrders")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/get_open_orders.json")
+            .expect(2)
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let state = AccountState::spot(account).unwrap();
+
+        assert_eq!(state.balance("BTC").free, amount("4723846.89208129"));
+        assert_eq!(state.balance("LTC").free, amount("4763368.68006011"));
+        assert_eq!(state.balance("XRP").free, Amount::default());
+        let orders = state.open_orders("LTCBTC");
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].order_id, 1);
+        assert_eq!(orders[0].status, OrderStatus::New);
+
+        // Sent before the order was last updated.
+        state.handle_event(&event(
+            "execution_report.json",
+            serde_json::json!({"/s": "LTCBTC", "/i": 1, "/X": "CANCELED", "/T": 1499827319000u64}),
+        ));
+        assert_eq!(state.open_order(1).unwrap().status, OrderStatus::New);
+
+        state.handle_event(&event("execution_report.json", serde_json::json!({})));
+        state.handle_event(&event("outbound_account_position.json", serde_json::json!({})));
+        state.handle_event(&event(
+            "execution_report.json",
+            serde_json::json!({"/s": "LTCBTC", "/i": 1, "/x": "TRADE", "/X": "FILLED", "/T": 1499827319600u64}),
+        ));
+
+        assert_eq!(state.open_orders("ETHBTC")[0].order_id, 4293153);
+        assert!(state.open_order(1).is_none());
+        assert_eq!(state.balance("BTC").free, amount("0.5"));
+        assert_eq!(state.balance("BTC").locked, amount("0.25"));
+        assert_eq!(state.balance("ETH").free, amount("10000"));
+
+        // The REST API still reports the seeding responses.
+        let drift = state.reconcile().unwrap();
+
+        mock_account.assert();
+        mock_open_orders.assert();
+
+        assert_eq!(drift.balances, vec!["BTC".to_string(), "ETH".to_string()]);
+        assert_eq!(drift.orders, vec![1, 4293153]);
+        assert!(drift.positions.is_empty());
+        assert_eq!(state.balance("BTC").free, amount("4723846.89208129"));
+        assert_eq!(state.balance("ETH").free, Amount::default());
+        assert!(state.open_order(1).is_some());
+        assert!(state.open_order(4293153).is_none());
+    }
+
+    #[test]
+    fn futures_account_state() {
+        let mock_balance = mock("GET", "/fapi/v2/balance")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/futures/account/account_balance.json")
+            .expect(2)
+            .create();
+        let mock_positions = mock("GET", "/fapi/v2/positionRisk")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/futures/account/position_information.json")
+            .expect(2)
+            .create();
+        let mock_open_orders = mock("GET", "/fapi/v1/openOrders")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/futures/account/get_all_open_orders.json")
+            .expect(2)
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let state = AccountState::futures(account).unwrap();
+
+        assert_eq!(state.balances().len(), 1);
+        assert_eq!(state.balance("USDT").free, amount("122607.35137903"));
+        let position = state.position("BTCUSDT").unwrap();
+        assert_eq!(position.amount, amount("0.5"));
+        assert_eq!(position.entry_price, amount("20000"));
+        assert!(state.position("ETHUSDT").is_none());
+        assert_eq!(state.open_orders("BTCUSDT")[0].order_id, 1917641);
+
+        state.handle_event(&event("account_update.json", serde_json::json!({})));
+        state.handle_event(&event("order_trade_update.json", serde_json::json!({})));
+        state.handle_event(&event(
+            "order_trade_update.json",
+            serde_json::json!({"/o/i": 1917641, "/o/x": "CANCELED", "/o/X": "CANCELED", "/o/T": 1579276757000u64}),
+        ));
+
+        assert_eq!(state.balance("USDT").free, amount("122624.12345678"));
+        assert_eq!(state.positions().len(), 2);
+        let long = state
+            .positions()
+            .into_iter()
+            .find(|position| position.position_side == "LONG")
+            .unwrap();
+        assert_eq!(long.amount, amount("20"));
+        assert_eq!(long.unrealized_pnl, amount("2850.212"));
+        assert!(state.open_order(1917641).is_none());
+        assert_eq!(state.open_order(8886774).unwrap().status, OrderStatus::New);
+
+        let (sender, receiver) = channel();
+        let reconciler = state.reconcile_every(Duration::from_millis(100), move |drift| {
+            let _ = sender.send(drift.unwrap());
+        });
+        let drift = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        drop(reconciler);
+
+        mock_balance.assert();
+        mock_positions.assert();
+        mock_open_orders.assert();
+
+        assert_eq!(drift.balances, vec!["USDT".to_string()]);
+        assert_eq!(drift.orders, vec![1917641, 8886774]);
+        assert_eq!(drift.positions, vec![("BTCUSDT".to_string(), "LONG".to_string())]);
+        assert_eq!(state.positions().len(), 1);
+        assert!(state.open_order(1917641).is_some());
+    }
+
+    #[test]
+    fn order_filled_during_reconcile() {
+        let mock_account = mock("GET", "/api/v3/account")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/get_account.json")
+            .expect(3)
+            .create();
+        let mock_open_orders = mock("GET", "/api/v3/openOrders")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/get_open_orders.json")
+            .expect(3)
+            .create();
+
+        let fill = Arc::new(EventDuringSnapshot {
+            state: Mutex::new(None),
+            event: event(
+                "execution_report.json",
+                serde_json::json!({"/s": "LTCBTC", "/i": 1, "/x": "TRADE", "/X": "FILLED", "/T": 1499827319600u64}),
+            ),
+        });
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234)
+            .add_middleware(fill.clone());
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
+        let state = AccountState::spot(account).unwrap();
+        assert!(state.open_order(1).is_some());
+
+        // The snapshot still lists the order, filled before the lock is taken.
+        *fill.state.lock().unwrap() = Some(state.clone());
+        let drift = state.reconcile().unwrap();
+        assert!(drift.is_empty());
+        assert!(state.open_order(1).is_none());
+        assert!(state.open_orders("LTCBTC").is_empty());
+
+        // Later snapshots are authoritative again.
+        let drift = state.reconcile().unwrap();
+
+        mock_account.assert();
+        mock_open_orders.assert();
+
+        assert_eq!(drift.orders, vec![1]);
+        assert!(state.open_order(1).is_some());
+    }
+}
@@ -3,9 +3,12 @@ use binance::config::*;
 use binance::account::*;
 use binance::model::*;
 
+mod common;
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::amount;
     use mockito::{mock, Matcher};
     use float_cmp::*;
 
@@ -21,7 +24,7 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let account = account.get_account().unwrap();
 
@@ -39,13 +42,13 @@ mod tests {
 
         let first_balance = &account.balances[0];
         assert_eq!(first_balance.asset, "BTC");
-        assert_eq!(first_balance.free, "4723846.89208129");
-        assert_eq!(first_balance.locked, "0.00000000");
+        assert_eq!(first_balance.free, amount("4723846.89208129"));
+        assert_eq!(first_balance.locked, amount("0.00000000"));
 
         let second_balance = &account.balances[1];
         assert_eq!(second_balance.asset, "LTC");
-        assert_eq!(second_balance.free, "4763368.68006011");
-        assert_eq!(second_balance.locked, "0.00000000");
+        assert_eq!(second_balance.free, amount("4763368.68006011"));
+        assert_eq!(second_balance.locked, amount("0.00000000"));
 
     }
 
@@ -61,15 +64,15 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let balance = account.get_balance("BTC").unwrap();
 
         mock_get_account.assert();
 
         assert_eq!(balance.asset, "BTC");
-        assert_eq!(balance.free, "4723846.89208129");
-        assert_eq!(balance.locked, "0.00000000");
+        assert_eq!(balance.free, amount("4723846.89208129"));
+        assert_eq!(balance.locked, amount("0.00000000"));
 
     }
 
@@ -85,7 +88,7 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let open_orders = account.get_open_orders("LTCBTC").unwrap();
 
@@ -98,20 +101,20 @@ mod tests {
         assert_eq!(open_order.order_id, 1);
         assert_eq!(open_order.order_list_id, -1);
         assert_eq!(open_order.client_order_id, "myOrder1");
-        assert!(approx_eq!(f64, open_order.price, 0.1, ulps = 2));
-        assert_eq!(open_order.orig_qty, "1.0");
-        assert_eq!(open_order.executed_qty, "0.0");
-        assert_eq!(open_order.cummulative_quote_qty, "0.0");
-        assert_eq!(open_order.status, "NEW");
-        assert_eq!(open_order.time_in_force, "GTC"); //Migrate to TimeInForce enum
-        assert_eq!(open_order.type_name, "LIMIT");
-        assert_eq!(open_order.side, "BUY");
-        assert!(approx_eq!(f64, open_order.stop_price, 0.0, ulps = 2));
-        assert_eq!(open_order.iceberg_qty, "0.0");
+        assert_eq!(open_order.price, amount("0.1"));
+        assert_eq!(open_order.orig_qty, amount("1.0"));
+        assert_eq!(open_order.executed_qty, amount("0.0"));
+        assert_eq!(open_order.cummulative_quote_qty, amount("0.0"));
+        assert_eq!(open_order.status, OrderStatus::New);
+        assert_eq!(open_order.time_in_force, TimeInForce::GTC);
+        assert_eq!(open_order.type_name, OrderType::Limit);
+        assert_eq!(open_order.side, OrderSide::Buy);
+        assert_eq!(open_order.stop_price, amount("0.0"));
+        assert_eq!(open_order.iceberg_qty, amount("0.0"));
         assert_eq!(open_order.time, 1499827319559);
         assert_eq!(open_order.update_time, 1499827319559);
         assert_eq!(open_order.is_working, true);
-        assert_eq!(open_order.orig_quote_order_qty, "0.000000");
+        assert_eq!(open_order.orig_quote_order_qty, amount("0.000000"));
 
     }
 
@@ -127,7 +130,7 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let open_orders = account.get_all_open_orders().unwrap();
 
@@ -140,20 +143,20 @@ mod tests {
         assert_eq!(open_order.order_id, 1);
         assert_eq!(open_order.order_list_id, -1);
         assert_eq!(open_order.client_order_id, "myOrder1");
-        assert!(approx_eq!(f64, open_order.price, 0.1, ulps = 2));
-        assert_eq!(open_order.orig_qty, "1.0");
-        assert_eq!(open_order.executed_qty, "0.0");
-        assert_eq!(open_order.cummulative_quote_qty, "0.0");
-        assert_eq!(open_order.status, "NEW");
-        assert_eq!(open_order.time_in_force, "GTC"); //Migrate to TimeInForce enum
-        assert_eq!(open_order.type_name, "LIMIT");
-        assert_eq!(open_order.side, "BUY");
-        assert!(approx_eq!(f64, open_order.stop_price, 0.0, ulps = 2));
-        assert_eq!(open_order.iceberg_qty, "0.0");
+        assert_eq!(open_order.price, amount("0.1"));
+        assert_eq!(open_order.orig_qty, amount("1.0"));
+        assert_eq!(open_order.executed_qty, amount("0.0"));
+        assert_eq!(open_order.cummulative_quote_qty, amount("0.0"));
+        assert_eq!(open_order.status, OrderStatus::New);
+        assert_eq!(open_order.time_in_force, TimeInForce::GTC);
+        assert_eq!(open_order.type_name, OrderType::Limit);
+        assert_eq!(open_order.side, OrderSide::Buy);
+        assert_eq!(open_order.stop_price, amount("0.0"));
+        assert_eq!(open_order.iceberg_qty, amount("0.0"));
         assert_eq!(open_order.time, 1499827319559);
         assert_eq!(open_order.update_time, 1499827319559);
         assert_eq!(open_order.is_working, true);
-        assert_eq!(open_order.orig_quote_order_qty, "0.000000");
+        assert_eq!(open_order.orig_quote_order_qty, amount("0.000000"));
 
     }
 
@@ -169,7 +172,7 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let cancel_all_open_orders = account.cancel_all_open_orders("BTCUSDT").unwrap();
 
@@ -203,7 +206,7 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let order_status: Order = account.order_status("LTCBTC", 1).unwrap();
 
@@ -213,20 +216,20 @@ mod tests {
         assert_eq!(order_status.order_id, 1);
         assert_eq!(order_status.order_list_id, -1);
         assert_eq!(order_status.client_order_id, "myOrder1");
-        assert!(approx_eq!(f64, order_status.price, 0.1, ulps = 2));
-        assert_eq!(order_status.orig_qty, "1.0");
-        assert_eq!(order_status.executed_qty, "0.0");
-        assert_eq!(order_status.cummulative_quote_qty, "0.0");
-        assert_eq!(order_status.status, "NEW");
-        assert_eq!(order_status.time_in_force, "GTC"); //Migrate to TimeInForce enum
-        assert_eq!(order_status.type_name, "LIMIT");
-        assert_eq!(order_status.side, "BUY");
-        assert!(approx_eq!(f64, order_status.stop_price, 0.0, ulps = 2));
-        assert_eq!(order_status.iceberg_qty, "0.0");
+        assert_eq!(order_status.price, amount("0.1"));
+        assert_eq!(order_status.orig_qty, amount("1.0"));
+        assert_eq!(order_status.executed_qty, amount("0.0"));
+        assert_eq!(order_status.cummulative_quote_qty, amount("0.0"));
+        assert_eq!(order_status.status, OrderStatus::New);
+        assert_eq!(order_status.time_in_force, TimeInForce::GTC);
+        assert_eq!(order_status.type_name, OrderType::Limit);
+        assert_eq!(order_status.side, OrderSide::Buy);
+        assert_eq!(order_status.stop_price, amount("0.0"));
+        assert_eq!(order_status.iceberg_qty, amount("0.0"));
         assert_eq!(order_status.time, 1499827319559);
         assert_eq!(order_status.update_time, 1499827319559);
         assert_eq!(order_status.is_working, true);
-        assert_eq!(order_status.orig_quote_order_qty, "0.000000");
+        assert_eq!(order_status.orig_quote_order_qty, amount("0.000000"));
 
     }
 
@@ -242,7 +245,7 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         account.test_order_status("LTCBTC", 1).unwrap();
 
@@ -262,9 +265,9 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
-        let transaction: Transaction = account.limit_buy("LTCBTC", 1, 0.1).unwrap();
+        let transaction: Transaction = account.limit_buy("LTCBTC", 1, amount("0.1")).unwrap();
 
         mock_limit_buy.assert();
 
@@ -273,14 +276,40 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.cummulative_quote_qty, 0.0, ulps = 2));
-        assert_eq!(transaction.status, "NEW");
-        assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
-        assert_eq!(transaction.type_name, "LIMIT");
-        assert_eq!(transaction.side, "BUY");
+        assert_eq!(transaction.price, amount("0.1"));
+        assert_eq!(transaction.orig_qty, amount("1.0"));
+        assert_eq!(transaction.executed_qty, amount("1.0"));
+        assert_eq!(transaction.cummulative_quote_qty, amount("0.0"));
+        assert_eq!(transaction.status, OrderStatus::New);
+        assert_eq!(transaction.time_in_force, TimeInForce::GTC);
+        assert_eq!(transaction.type_name, OrderType::Limit);
+        assert_eq!(transaction.side, OrderSide::Buy);
+
+    }
+
+    #[test]
+    fn limit_buy_signed_in_body() {
+
+        let mock_limit_buy = mock("POST", "/api/v3/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_header("content-type", "application/x-www-form-urlencoded")
+            .match_query(Matcher::Missing)
+            .match_body(Matcher::Regex("^price=0.1&quantity=1&recvWindow=1234&side=BUY&symbol=LTCBTC&timeInForce=GTC&timestamp=\\d+&type=LIMIT&signature=[0-9a-f]{64}$".into()))
+            .with_body_from_file("tests/mocks/account/limit_buy.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234)
+            .set_signed_payload_in_body(true);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let transaction: Transaction = account.limit_buy("LTCBTC", 1, amount("0.1")).unwrap();
+
+        mock_limit_buy.assert();
+
+        assert_eq!(transaction.symbol, "LTCBTC");
+        assert_eq!(transaction.order_id, 1);
 
     }
 
@@ -296,9 +325,9 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
-        account.test_limit_buy("LTCBTC", 1, 0.1).unwrap();
+        account.test_limit_buy("LTCBTC", 1, amount("0.1")).unwrap();
 
         mock_test_limit_buy.assert();
 
@@ -316,9 +345,9 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
-        let transaction: Transaction = account.limit_sell("LTCBTC", 1, 0.1).unwrap();
+        let transaction: Transaction = account.limit_sell("LTCBTC", 1, amount("0.1")).unwrap();
 
         mock_limit_sell.assert();
 
@@ -327,14 +356,14 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.cummulative_quote_qty, 0.0, ulps = 2));
-        assert_eq!(transaction.status, "NEW");
-        assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
-        assert_eq!(transaction.type_name, "LIMIT");
-        assert_eq!(transaction.side, "SELL");
+        assert_eq!(transaction.price, amount("0.1"));
+        assert_eq!(transaction.orig_qty, amount("1.0"));
+        assert_eq!(transaction.executed_qty, amount("1.0"));
+        assert_eq!(transaction.cummulative_quote_qty, amount("0.0"));
+        assert_eq!(transaction.status, OrderStatus::New);
+        assert_eq!(transaction.time_in_force, TimeInForce::GTC);
+        assert_eq!(transaction.type_name, OrderType::Limit);
+        assert_eq!(transaction.side, OrderSide::Sell);
 
     }
 
@@ -350,9 +379,9 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
-        account.test_limit_sell("LTCBTC", 1, 0.1).unwrap();
+        account.test_limit_sell("LTCBTC", 1, amount("0.1")).unwrap();
 
         mock_test_limit_sell.assert();
 
@@ -370,7 +399,7 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let transaction: Transaction = account.market_buy("LTCBTC", 1).unwrap();
 
@@ -381,14 +410,14 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.cummulative_quote_qty, 0.0, ulps = 2));
-        assert_eq!(transaction.status, "NEW");
-        assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
-        assert_eq!(transaction.type_name, "MARKET");
-        assert_eq!(transaction.side, "BUY");
+        assert_eq!(transaction.price, amount("0.1"));
+        assert_eq!(transaction.orig_qty, amount("1.0"));
+        assert_eq!(transaction.executed_qty, amount("1.0"));
+        assert_eq!(transaction.cummulative_quote_qty, amount("0.0"));
+        assert_eq!(transaction.status, OrderStatus::New);
+        assert_eq!(transaction.time_in_force, TimeInForce::GTC);
+        assert_eq!(transaction.type_name, OrderType::Market);
+        assert_eq!(transaction.side, OrderSide::Buy);
 
     }
 
@@ -404,7 +433,7 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         account.test_market_buy("LTCBTC", 1).unwrap();
 
@@ -424,9 +453,9 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
-        match account.market_buy_using_quote_quantity("BNBBTC", 0.002) {
+        match account.market_buy_using_quote_quantity("BNBBTC", amount("0.002")) {
             Ok(answer) => {
                 assert!(answer.order_id == 1);
             }
@@ -449,9 +478,9 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
-        account.test_market_buy_using_quote_quantity("BNBBTC", 0.002).unwrap();
+        account.test_market_buy_using_quote_quantity("BNBBTC", amount("0.002")).unwrap();
 
         mock_test_market_buy_using_quote_quantity.assert();
 
@@ -469,7 +498,7 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let transaction: Transaction = account.market_sell("LTCBTC", 1).unwrap();
 
@@ -480,14 +509,14 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.cummulative_quote_qty, 0.0, ulps = 2));
-        assert_eq!(transaction.status, "NEW");
-        assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
-        assert_eq!(transaction.type_name, "MARKET");
-        assert_eq!(transaction.side, "SELL");
+        assert_eq!(transaction.price, amount("0.1"));
+        assert_eq!(transaction.orig_qty, amount("1.0"));
+        assert_eq!(transaction.executed_qty, amount("1.0"));
+        assert_eq!(transaction.cummulative_quote_qty, amount("0.0"));
+        assert_eq!(transaction.status, OrderStatus::New);
+        assert_eq!(transaction.time_in_force, TimeInForce::GTC);
+        assert_eq!(transaction.type_name, OrderType::Market);
+        assert_eq!(transaction.side, OrderSide::Sell);
 
     }
 
@@ -503,7 +532,7 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let _ = account.test_market_sell("LTCBTC", 1).unwrap();
 
@@ -523,9 +552,9 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
-        match account.market_sell_using_quote_quantity("BNBBTC", 0.002) {
+        match account.market_sell_using_quote_quantity("BNBBTC", amount("0.002")) {
             Ok(answer) => {
                 assert!(answer.order_id == 1);
             }
@@ -548,9 +577,9 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
-        account.test_market_sell_using_quote_quantity("BNBBTC", 0.002).unwrap();
+        account.test_market_sell_using_quote_quantity("BNBBTC", amount("0.002")).unwrap();
 
         mock_test_market_sell_using_quote_quantity.assert();
 
@@ -568,9 +597,9 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
-        let transaction: Transaction = account.stop_limit_buy_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC).unwrap();
+        let transaction: Transaction = account.stop_limit_buy_order("LTCBTC", 1, amount("0.1"), amount("0.09"), TimeInForce::GTC).unwrap();
 
         mock_stop_limit_buy_order.assert();
 
@@ -579,15 +608,15 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.cummulative_quote_qty, 0.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.stop_price, 0.09, ulps = 2));
-        assert_eq!(transaction.status, "NEW");
-        assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
-        assert_eq!(transaction.type_name, "STOP_LOSS_LIMIT");
-        assert_eq!(transaction.side, "BUY");
+        assert_eq!(transaction.price, amount("0.1"));
+        assert_eq!(transaction.orig_qty, amount("1.0"));
+        assert_eq!(transaction.executed_qty, amount("1.0"));
+        assert_eq!(transaction.cummulative_quote_qty, amount("0.0"));
+        assert_eq!(transaction.stop_price, amount("0.09"));
+        assert_eq!(transaction.status, OrderStatus::New);
+        assert_eq!(transaction.time_in_force, TimeInForce::GTC);
+        assert_eq!(transaction.type_name, OrderType::StopLossLimit);
+        assert_eq!(transaction.side, OrderSide::Buy);
 
     }
 
@@ -603,9 +632,9 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
-        let _ = account.test_stop_limit_buy_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC).unwrap();
+        let _ = account.test_stop_limit_buy_order("LTCBTC", 1, amount("0.1"), amount("0.09"), TimeInForce::GTC).unwrap();
 
         mock_test_stop_limit_buy_order.assert();
 
@@ -623,9 +652,9 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
-        let transaction: Transaction = account.stop_limit_sell_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC).unwrap();
+        let transaction: Transaction = account.stop_limit_sell_order("LTCBTC", 1, amount("0.1"), amount("0.09"), TimeInForce::GTC).unwrap();
 
         mock_stop_limit_sell_order.assert();
 
@@ -634,15 +663,15 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.cummulative_quote_qty, 0.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.stop_price, 0.09, ulps = 2));
-        assert_eq!(transaction.status, "NEW");
-        assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
-        assert_eq!(transaction.type_name, "STOP_LOSS_LIMIT");
-        assert_eq!(transaction.side, "SELL");
+        assert_eq!(transaction.price, amount("0.1"));
+        assert_eq!(transaction.orig_qty, amount("1.0"));
+        assert_eq!(transaction.executed_qty, amount("1.0"));
+        assert_eq!(transaction.cummulative_quote_qty, amount("0.0"));
+        assert_eq!(transaction.stop_price, amount("0.09"));
+        assert_eq!(transaction.status, OrderStatus::New);
+        assert_eq!(transaction.time_in_force, TimeInForce::GTC);
+        assert_eq!(transaction.type_name, OrderType::StopLossLimit);
+        assert_eq!(transaction.side, OrderSide::Sell);
 
     }
 
@@ -658,9 +687,9 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
-        let _ = account.test_stop_limit_sell_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC).unwrap();
+        let _ = account.test_stop_limit_sell_order("LTCBTC", 1, amount("0.1"), amount("0.09"), TimeInForce::GTC).unwrap();
 
         mock_test_stop_limit_sell_order.assert();
 
@@ -678,10 +707,10 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let transaction: Transaction = account.custom_order(
-            "LTCBTC", 1, 0.1, None, OrderSide::Buy, OrderType::Market, TimeInForce::GTC).unwrap();
+            "LTCBTC", 1, amount("0.1"), None, OrderSide::Buy, OrderType::Market, TimeInForce::GTC).unwrap();
 
         mock_custom_order.assert();
 
@@ -690,15 +719,15 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.cummulative_quote_qty, 0.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.stop_price, 0.09, ulps = 2));
-        assert_eq!(transaction.status, "NEW");
-        assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
-        assert_eq!(transaction.type_name, "STOP_LOSS_LIMIT");
-        assert_eq!(transaction.side, "SELL");
+        assert_eq!(transaction.price, amount("0.1"));
+        assert_eq!(transaction.orig_qty, amount("1.0"));
+        assert_eq!(transaction.executed_qty, amount("1.0"));
+        assert_eq!(transaction.cummulative_quote_qty, amount("0.0"));
+        assert_eq!(transaction.stop_price, amount("0.09"));
+        assert_eq!(transaction.status, OrderStatus::New);
+        assert_eq!(transaction.time_in_force, TimeInForce::GTC);
+        assert_eq!(transaction.type_name, OrderType::StopLossLimit);
+        assert_eq!(transaction.side, OrderSide::Sell);
 
     }
 
@@ -714,10 +743,10 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let _ = account.test_custom_order(
-            "LTCBTC", 1, 0.1, None, OrderSide::Buy, OrderType::Market, TimeInForce::GTC).unwrap();
+            "LTCBTC", 1, amount("0.1"), None, OrderSide::Buy, OrderType::Market, TimeInForce::GTC).unwrap();
 
         mock_test_custom_order.assert();
 
@@ -735,7 +764,7 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let cancelled_order = account.cancel_order("BTCUSDT", 1).unwrap();
 
@@ -748,6 +777,52 @@ mod tests {
 
     }
 
+    #[test]
+    fn cancel_order_signed_in_body() {
+
+        let mock_cancel_order = mock("DELETE", "/api/v3/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Missing)
+            .match_body(Matcher::Regex("^orderId=1&recvWindow=1234&symbol=BTCUSDT&timestamp=\\d+&signature=[0-9a-f]{64}$".into()))
+            .with_body_from_file("tests/mocks/account/cancel_order.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234)
+            .set_signed_payload_in_body(true);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let cancelled_order = account.cancel_order("BTCUSDT", 1).unwrap();
+
+        mock_cancel_order.assert();
+
+        assert_eq!(cancelled_order.order_id.unwrap(), 4);
+
+    }
+
+    #[test]
+    fn get_account_signed_in_query() {
+
+        let mock_get_account = mock("GET", "/api/v3/account")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/get_account.json")
+            .create();
+
+        // Signed GET requests keep their parameters in the query string
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234)
+            .set_signed_payload_in_body(true);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        account.get_account().unwrap();
+
+        mock_get_account.assert();
+
+    }
+
     #[test]
     fn test_cancel_order() {
 
@@ -760,7 +835,7 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         account.test_cancel_order("BTCUSDT", 1).unwrap();
 
@@ -780,7 +855,7 @@ mod tests {
         let config = Config::default()
             .set_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: Account = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let histories = account.trade_history("BTCUSDT").unwrap();
 
@@ -791,9 +866,9 @@ mod tests {
         let history: TradeHistory = histories[0].clone();
 
         assert_eq!(history.id, 28457);
-        assert!(approx_eq!(f64, history.price, 4.00000100, ulps = 2));
-        assert!(approx_eq!(f64, history.qty, 12.00000000, ulps = 2));
-        assert_eq!(history.commission, "10.10000000");
+        assert_eq!(history.price, amount("4.00000100"));
+        assert_eq!(history.qty, amount("12.00000000"));
+        assert_eq!(history.commission, amount("10.10000000"));
         assert_eq!(history.commission_asset, "BNB");
         assert_eq!(history.time, 1499865549590);
         assert_eq!(history.is_buyer, true);
@@ -802,4 +877,15 @@ mod tests {
 
     }
 
+    #[test]
+    fn order_enums() {
+        let status: OrderStatus = serde_json::from_str("\"PARTIALLY_FILLED\"").unwrap();
+        assert_eq!(status, OrderStatus::PartiallyFilled);
+        let status: OrderStatus = serde_json::from_str("\"NEW_STATUS\"").unwrap();
+        assert_eq!(status, OrderStatus::Other("NEW_STATUS".into()));
+        let order_type: OrderType = serde_json::from_str("\"LIMIT_MAKER\"").unwrap();
+        assert_eq!(order_type, OrderType::LimitMaker);
+        assert_eq!(serde_json::to_string(&OrderSide::Sell).unwrap(), "\"SELL\"");
+        assert_eq!(serde_json::to_string(&TimeInForce::IOC).unwrap(), "\"IOC\"");
+    }
 }
@@ -0,0 +1,44 @@
+use binance::api::*;
+use binance::config::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+
+    #[test]
+    fn shared_rate_limit_usage() {
+        let mock_get_price = mock("GET", "/api/v3/ticker/price")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_header("x-mbx-used-weight-1m", "12")
+            .with_header("x-mbx-order-count-10s", "3")
+            .match_query(Matcher::Regex("symbol=LTCBTC".into()))
+            .with_body_from_file("tests/mocks/market/get_price.json")
+            .create();
+        let mock_futures_ping = mock("GET", "/fapi/v1/ping")
+            .with_header("x-mbx-used-weight-1m", "1")
+            .with_body("{}")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_futures_rest_api_endpoint(mockito::server_url());
+        let binance = BinanceClient::new(None, None, &config).unwrap();
+
+        let price = binance.spot().market().get_price("LTCBTC").unwrap();
+        assert_eq!(price.symbol, "LTCBTC");
+        binance.futures().general().ping().unwrap();
+        mock_get_price.assert();
+        mock_futures_ping.assert();
+
+        // Usage seen by the market is visible from any other spot struct.
+        let usage = binance.spot().account().client.rate_limit_usage();
+        assert_eq!(usage.used_weight.get("1m"), Some(&12));
+        assert_eq!(usage.order_count.get("10s"), Some(&3));
+        assert_eq!(binance.spot().rate_limit_usage(), usage);
+
+        let futures_usage = binance.futures().rate_limit_usage();
+        assert_eq!(futures_usage.used_weight.get("1m"), Some(&1));
+        assert!(futures_usage.order_count.is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+use binance::candles::*;
+use binance::model::*;
+use binance::websockets::*;
+use std::time::Duration;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::amount;
+
+    fn trade(symbol: &str, time: u64, price: &str, qty: &str, is_buyer_maker: bool) -> WebsocketEvent {
+        let trade = format!(
+            r#"{{"e":"trade","E":{time},"s":"{symbol}","t":1,"p":"{price}","q":"{qty}","b":1,"a":2,"T":{time},"m":{is_buyer_maker},"M":true}}"#,
+        );
+        WebsocketEvent::Trade(serde_json::from_str(&trade).unwrap())
+    }
+
+    fn agg_trade(time: u64, price: &str, qty: &str, first_id: u64, last_id: u64) -> WebsocketEvent {
+        let trade = format!(
+            r#"{{"e":"aggTrade","E":{time},"s":"BTCUSDT","a":1,"p":"{price}","q":"{qty}","f":{first_id},"l":{last_id},"T":{time},"m":true,"M":true}}"#,
+        );
+        WebsocketEvent::AggrTrades(serde_json::from_str(&trade).unwrap())
+    }
+
+    fn open_times(candles: &[KlineSummary]) -> Vec<i64> {
+        candles.iter().map(|candle| candle.open_time).collect()
+    }
+
+    #[test]
+    fn time_candles() {
+        let mut builder = CandleBuilder::new("BTCUSDT", BarType::Time(Duration::from_secs(3)));
+
+        assert!(builder.handle_event(&trade("BTCUSDT", 1000, "10", "1", false)).is_empty());
+        assert!(builder.handle_event(&trade("BTCUSDT", 2500, "12", "2", true)).is_empty());
+        // Out of order within the candle, it is the low but not the close.
+        assert!(builder.handle_event(&trade("BTCUSDT", 2000, "9", "1", false)).is_empty());
+        assert!(builder.handle_event(&trade("ETHUSDT", 4000, "1", "1", false)).is_empty());
+        assert_eq!(builder.current().unwrap().number_of_trades, 3);
+
+        let closed = builder.handle_event(&trade("BTCUSDT", 3100, "11", "1", false));
+        assert_eq!(closed.len(), 1);
+        let candle = &closed[0];
+        assert_eq!((candle.open_time, candle.close_time), (0, 2999));
+        assert_eq!(candle.open, amount("10"));
+        assert_eq!(candle.high, amount("12"));
+        assert_eq!(candle.low, amount("9"));
+        assert_eq!(candle.close, amount("12"));
+        assert_eq!(candle.volume, amount("4"));
+        assert_eq!(candle.quote_asset_volume, amount("43"));
+        assert_eq!(candle.number_of_trades, 3);
+        assert_eq!(candle.taker_buy_base_asset_volume, amount("2"));
+        assert_eq!(candle.taker_buy_quote_asset_volume, amount("19"));
+
+        // No trades from 6000 to 8999.
+        let closed = builder.handle_event(&trade("BTCUSDT", 10000, "13", "1", false));
+        assert_eq!(open_times(&closed), vec![3000, 6000]);
+        assert_eq!(closed[1].close_time, 8999);
+        assert_eq!(closed[1].open, amount("11"));
+        assert_eq!(closed[1].close, amount("11"));
+        assert_eq!(closed[1].volume, Amount::default());
+        assert_eq!(closed[1].number_of_trades, 0);
+
+        assert!(builder.handle_event(&trade("BTCUSDT", 5000, "1", "1", false)).is_empty());
+        assert_eq!(builder.late_events(), 1);
+
+        assert!(builder.advance_to(11999).is_empty());
+        assert_eq!(open_times(&builder.advance_to(13000)), vec![9000]);
+        assert!(builder.current().is_none());
+        assert!(builder.flush().is_empty());
+    }
+
+    #[test]
+    fn allowed_lateness_without_gaps() {
+        let mut builder = CandleBuilder::new("BTCUSDT", BarType::Time(Duration::from_secs(1)))
+            .set_allowed_lateness(Duration::from_secs(1))
+            .set_fill_gaps(false);
+
+        builder.handle_event(&trade("BTCUSDT", 100, "10", "1", false));
+        assert!(builder.handle_event(&trade("BTCUSDT", 1500, "11", "1", false)).is_empty());
+        // Late, but the candle is still open.
+        assert!(builder.handle_event(&trade("BTCUSDT", 900, "8", "1", false)).is_empty());
+
+        let closed = builder.handle_event(&trade("BTCUSDT", 2100, "12", "1", false));
+        assert_eq!(open_times(&closed), vec![0]);
+        assert_eq!(closed[0].number_of_trades, 2);
+        assert_eq!(closed[0].close, amount("8"));
+
+        let closed = builder.handle_event(&trade("BTCUSDT", 5000, "13", "1", false));
+        assert_eq!(open_times(&closed), vec![1000, 2000]);
+        assert_eq!(builder.late_events(), 0);
+
+        assert!(builder.handle_event(&trade("BTCUSDT", 4500, "14", "1", false)).is_empty());
+        assert_eq!(open_times(&builder.flush()), vec![4000, 5000]);
+    }
+
+    #[test]
+    fn volume_tick_and_dollar_bars() {
+        let mut volume = CandleBuilder::new("BTCUSDT", BarType::Volume(amount("3")));
+        let mut tick = CandleBuilder::new("BTCUSDT", BarType::Tick(5));
+        let mut dollar = CandleBuilder::new("BTCUSDT", BarType::Dollar(amount("50")));
+
+        let events = vec![
+            agg_trade(1000, "10", "1", 1, 2),
+            agg_trade(2000, "11", "1.5", 3, 3),
+            agg_trade(3000, "12", "1", 4, 6),
+            agg_trade(4000, "13", "2", 7, 7),
+        ];
+        let mut bars = (Vec::new(), Vec::new(), Vec::new());
+        for event in &events {
+            bars.0.extend(volume.handle_event(event));
+            bars.1.extend(tick.handle_event(event));
+            bars.2.extend(dollar.handle_event(event));
+        }
+
+        // Closes on the trade crossing the threshold, without splitting it.
+        assert_eq!(bars.0.len(), 1);
+        assert_eq!((bars.0[0].open_time, bars.0[0].close_time), (1000, 3000));
+        assert_eq!(bars.0[0].volume, amount("3.5"));
+        assert_eq!(bars.0[0].close, amount("12"));
+        assert_eq!(bars.0[0].number_of_trades, 6);
+
+        assert_eq!(bars.1.len(), 1);
+        assert_eq!(bars.1[0].close_time, 3000);
+
+        assert_eq!(open_times(&bars.2), vec![1000]);
+        assert_eq!(bars.2[0].quote_asset_volume, amount("64.5"));
+        assert_eq!(bars.2[0].taker_buy_base_asset_volume, Amount::default());
+
+        let rest = volume.flush();
+        assert_eq!((rest[0].open_time, rest[0].close_time), (4000, 4000));
+        assert_eq!(rest[0].high, amount("13"));
+        assert!(volume.flush().is_empty());
+    }
+}
@@ -0,0 +1,6 @@
+use binance::model::Amount;
+
+// Parsed from a string so that the values are exact with the `decimal` feature too.
+pub fn amount(value: &str) -> Amount {
+    value.parse().unwrap()
+}
@@ -0,0 +1,73 @@
+use binance::api::*;
+use binance::config::*;
+use binance::general::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::mock;
+    use std::net::TcpListener;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn custom_user_agent() {
+        let mock_ping = mock("GET", "/api/v3/ping")
+            .match_header("user-agent", "my-bot/1.0")
+            .with_body("{}")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_user_agent("my-bot/1.0");
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
+        general.ping().unwrap();
+        mock_ping.assert();
+    }
+
+    #[test]
+    fn injected_http_client() {
+        let mock_time = mock("GET", "/api/v3/time")
+            .with_body(r#"{"serverTime":1499827319559}"#)
+            .create();
+
+        let http_client = reqwest::blocking::Client::builder().build().unwrap();
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_http_client(http_client);
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
+        assert_eq!(general.get_server_time().unwrap().server_time, 1499827319559);
+        mock_time.assert();
+    }
+
+    #[test]
+    fn request_timeout() {
+        // Accepts connections but never answers.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+
+        let config = Config::default()
+            .set_rest_api_endpoint(endpoint)
+            .set_timeout(Duration::from_millis(200));
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
+
+        let start = Instant::now();
+        assert!(general.ping().is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+        drop(listener);
+    }
+
+    #[test]
+    fn invalid_proxy() {
+        let config = Config::default().set_proxy("not a proxy url");
+        let general: Result<General, _> = Binance::new_with_config(None, None, &config);
+        assert!(general.is_err());
+    }
+
+    #[cfg(not(feature = "rustls-tls"))]
+    #[test]
+    fn rustls_requires_feature() {
+        let config = Config::default().set_tls_backend(TlsBackend::Rustls);
+        let general: Result<General, _> = Binance::new_with_config(None, None, &config);
+        assert!(general.is_err());
+    }
+}
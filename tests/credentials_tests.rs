@@ -0,0 +1,76 @@
+use binance::api::*;
+use binance::config::*;
+use binance::account::*;
+use binance::credentials::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+
+    #[test]
+    fn secrets_are_not_printed() {
+        let credentials = Credentials::new(Some("my_api_key".into()), Some("my_secret_key".into()));
+        let debug = format!("{:?}", credentials);
+
+        assert!(!debug.contains("my_api_key"));
+        assert!(!debug.contains("my_secret_key"));
+        assert_eq!(credentials.secret_key.unwrap().expose(), "my_secret_key");
+    }
+
+    #[test]
+    fn env_credentials() {
+        std::env::set_var("BINANCE_RS_TEST_API_KEY", "env_api_key");
+        std::env::set_var("BINANCE_RS_TEST_SECRET_KEY", "env_secret_key");
+
+        let provider = EnvCredentials::new("BINANCE_RS_TEST_API_KEY", "BINANCE_RS_TEST_SECRET_KEY");
+        let credentials = provider.credentials().unwrap();
+
+        assert_eq!(credentials.api_key.unwrap().expose(), "env_api_key");
+        assert_eq!(credentials.secret_key.unwrap().expose(), "env_secret_key");
+    }
+
+    #[test]
+    fn env_credentials_missing() {
+        let provider = EnvCredentials::new("BINANCE_RS_TEST_UNSET_KEY", "BINANCE_RS_TEST_UNSET_SECRET");
+        assert!(provider.credentials().is_err());
+    }
+
+    #[test]
+    fn file_credentials() {
+        let dir = std::env::temp_dir();
+        let api_key_path = dir.join("binance_rs_test_api_key");
+        let secret_key_path = dir.join("binance_rs_test_secret_key");
+        std::fs::write(&api_key_path, "file_api_key\n").unwrap();
+        std::fs::write(&secret_key_path, "file_secret_key\n").unwrap();
+
+        let provider = FileCredentials::new(&api_key_path, &secret_key_path);
+        let credentials = provider.credentials().unwrap();
+
+        assert_eq!(credentials.api_key.unwrap().expose(), "file_api_key");
+        assert_eq!(credentials.secret_key.unwrap().expose(), "file_secret_key");
+
+        std::fs::remove_file(api_key_path).unwrap();
+        std::fs::remove_file(secret_key_path).unwrap();
+    }
+
+    #[test]
+    fn callback_credentials() {
+        let mock_get_account = mock("GET", "/api/v3/account")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_header("x-mbx-apikey", "vault_api_key")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/get_account.json")
+            .create();
+
+        let vault = || Ok(Credentials::new(Some("vault_api_key".into()), Some("vault_secret_key".into())));
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_provider(&vault, &config).unwrap();
+        let _ = env_logger::try_init();
+        account.get_account().unwrap();
+
+        mock_get_account.assert();
+    }
+}
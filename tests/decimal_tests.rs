@@ -0,0 +1,73 @@
+#![cfg(feature = "decimal")]
+
+use binance::api::*;
+use binance::config::*;
+use binance::account::*;
+use binance::market::*;
+use binance::model::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+
+    #[test]
+    fn get_balance() {
+        let mock_get_account = mock("GET", "/api/v3/account")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/get_account.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let balance = account.get_balance("BTC").unwrap();
+
+        mock_get_account.assert();
+
+        assert_eq!(balance.free.to_string(), "4723846.89208129");
+        assert!(balance.locked.is_zero());
+    }
+
+    #[test]
+    fn get_price() {
+        let mock_get_price = mock("GET", "/api/v3/ticker/price")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("symbol=LTCBTC".into()))
+            .with_body_from_file("tests/mocks/market/get_price.json")
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
+
+        let symbol = market.get_price("LTCBTC").unwrap();
+        mock_get_price.assert();
+
+        assert_eq!(symbol.price, "4.000002".parse::<Amount>().unwrap());
+    }
+
+    #[test]
+    fn limit_buy() {
+        let mock_limit_buy = mock("POST", "/api/v3/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("price=0.10000001&quantity=1.5&recvWindow=1234&side=BUY&symbol=LTCBTC&timeInForce=GTC&timestamp=\\d+&type=LIMIT".into()))
+            .with_body_from_file("tests/mocks/account/limit_buy.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let qty: Amount = "1.5".parse().unwrap();
+        let price: Amount = "0.10000001".parse().unwrap();
+        let transaction = account.limit_buy("LTCBTC", qty, price).unwrap();
+
+        mock_limit_buy.assert();
+
+        assert_eq!(transaction.price, "0.1".parse::<Amount>().unwrap());
+    }
+}
@@ -0,0 +1,115 @@
+use binance::api::*;
+use binance::config::*;
+use binance::errors::*;
+use binance::general::*;
+use binance::market::*;
+use std::error::Error as _;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+    use std::time::Duration;
+
+    #[test]
+    fn api_error() {
+        let mock_ping = mock("GET", "/api/v3/ping")
+            .with_status(400)
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body(r#"{"code":-1121,"msg":"Invalid symbol."}"#)
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
+        let err = general.ping().unwrap_err();
+        mock_ping.assert();
+
+        match err {
+            BinanceError::Api { code, msg } => {
+                assert_eq!(code, -1121);
+                assert_eq!(msg, "Invalid symbol.");
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn rate_limited() {
+        let mock_server_time = mock("GET", "/api/v3/time")
+            .with_status(429)
+            .with_header("retry-after", "12")
+            .with_body(r#"{"code":-1003,"msg":"Too many requests."}"#)
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
+        let err = match general.get_server_time() {
+            Ok(_) => panic!("Expected an error"),
+            Err(e) => e,
+        };
+        mock_server_time.assert();
+
+        match err {
+            BinanceError::RateLimited { retry_after } => {
+                assert_eq!(retry_after, Some(Duration::from_secs(12)));
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn http_status() {
+        let mock_exchange_info = mock("GET", "/api/v3/exchangeInfo")
+            .with_status(503)
+            .with_body("<html>Service Unavailable</html>")
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
+        let err = general.exchange_info().unwrap_err();
+        mock_exchange_info.assert();
+
+        match err {
+            BinanceError::Http { status, body } => {
+                assert_eq!(status, 503);
+                assert_eq!(body, "<html>Service Unavailable</html>");
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn decode_failure() {
+        let mock_get_price = mock("GET", "/api/v3/ticker/price")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("symbol=LTCBTC".into()))
+            .with_body(r#"{"symbol":"LTCBTC"}"#)
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
+        let err = market.get_price("LTCBTC").unwrap_err();
+        mock_get_price.assert();
+
+        match err {
+            BinanceError::Decode { body, .. } => assert_eq!(body, r#"{"symbol":"LTCBTC"}"#),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn transport_error_source() {
+        let config = Config::default().set_rest_api_endpoint("http://127.0.0.1:1");
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
+        let err = general.ping().unwrap_err();
+
+        assert!(matches!(err, BinanceError::Transport(_)));
+        assert!(err.source().unwrap().downcast_ref::<reqwest::Error>().is_some());
+    }
+
+    #[test]
+    fn error_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+        assert_send_sync::<BinanceError>();
+    }
+}
@@ -0,0 +1,145 @@
+use binance::api::*;
+use binance::config::*;
+use binance::exchange_info::*;
+use binance::general::*;
+use binance::errors::*;
+use binance::model::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::mock;
+
+    fn cache(ttl: Duration) -> ExchangeInfoCache<General> {
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
+        ExchangeInfoCache::new(general, ttl)
+    }
+
+    struct Pair {
+        symbol: String,
+        status: SymbolStatus,
+    }
+
+    impl SymbolInfo for Pair {
+        fn symbol(&self) -> &str {
+            &self.symbol
+        }
+
+        fn base_asset(&self) -> &str {
+            "BTC"
+        }
+
+        fn quote_asset(&self) -> &str {
+            "USDT"
+        }
+
+        fn status(&self) -> &SymbolStatus {
+            &self.status
+        }
+    }
+
+    // Counts the downloads, each one taking a while.
+    struct SlowSource {
+        fetches: Arc<AtomicUsize>,
+    }
+
+    impl ExchangeInfoSource for SlowSource {
+        type Symbol = Pair;
+
+        fn fetch_symbols(&self) -> Result<Vec<Pair>> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(200));
+            Ok(vec![Pair {
+                symbol: "BTCUSDT".into(),
+                status: SymbolStatus::Trading,
+            }])
+        }
+    }
+
+    fn get_concurrently(cache: &Arc<ExchangeInfoCache<SlowSource>>) {
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let cache = cache.clone();
+                thread::spawn(move || cache.get_symbol("BTCUSDT").unwrap())
+            })
+            .collect();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap().symbol, "BTCUSDT");
+        }
+    }
+
+    #[test]
+    fn fetches_once_within_ttl() {
+        let mock_exchange_info = mock("GET", "/api/v3/exchangeInfo")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body_from_file("tests/mocks/general/exchange_info.json")
+            .expect(1)
+            .create();
+
+        let cache = cache(Duration::from_secs(3600));
+
+        let symbol = cache.get_symbol("ltcbtc").unwrap();
+        assert_eq!(symbol.symbol, "LTCBTC");
+        assert_eq!(symbol.status, SymbolStatus::Trading);
+        assert!(cache.get_symbol("BTCUSDT").is_err());
+
+        let btc_pairs = cache.symbols_by_quote_asset("BTC").unwrap();
+        assert_eq!(btc_pairs.len(), 3);
+        let bnb_pairs = cache.symbols_by_base_asset("BNB").unwrap();
+        assert_eq!(bnb_pairs.len(), 1);
+        assert_eq!(bnb_pairs[0].symbol, "BNBBTC");
+        assert!(cache.symbols_by_base_asset("DOGE").unwrap().is_empty());
+        assert_eq!(cache.symbols_with_status(&SymbolStatus::Trading).unwrap().len(), 3);
+        assert!(cache.symbols_with_status(&SymbolStatus::Halt).unwrap().is_empty());
+        assert_eq!(cache.symbols().unwrap().len(), 3);
+
+        mock_exchange_info.assert();
+    }
+
+    #[test]
+    fn refresh() {
+        let mock_exchange_info = mock("GET", "/api/v3/exchangeInfo")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body_from_file("tests/mocks/general/exchange_info.json")
+            .expect(2)
+            .create();
+
+        let cache = cache(Duration::from_secs(3600));
+
+        cache.get_symbol("ETHBTC").unwrap();
+        cache.refresh().unwrap();
+        cache.get_symbol("ETHBTC").unwrap();
+
+        mock_exchange_info.assert();
+    }
+
+    #[test]
+    fn symbol_status() {
+        let status: SymbolStatus = serde_json::from_str("\"BREAK\"").unwrap();
+        assert_eq!(status, SymbolStatus::Break);
+        let status: SymbolStatus = serde_json::from_str("\"NEW_STATUS\"").unwrap();
+        assert_eq!(status, SymbolStatus::Other("NEW_STATUS".into()));
+        assert_eq!(serde_json::to_string(&SymbolStatus::Halt).unwrap(), "\"HALT\"");
+    }
+
+    #[test]
+    fn one_download_at_a_time() {
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let source = SlowSource { fetches: fetches.clone() };
+        let cache = Arc::new(ExchangeInfoCache::new(source, Duration::from_millis(500)));
+
+        // Without a copy yet, the callers wait for the first download.
+        get_concurrently(&cache);
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        // Once expired, the others keep the old copy while one downloads.
+        thread::sleep(Duration::from_millis(600));
+        get_concurrently(&cache);
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+}
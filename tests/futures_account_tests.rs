@@ -1,12 +1,15 @@
 use binance::api::*;
 use binance::config::*;
 use binance::futures::account::*;
+use binance::futures::model::OrderStatus;
+
+mod common;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::amount;
     use mockito::{mock, Matcher};
-    use float_cmp::*;
     
     #[test]
     fn change_initial_leverage() {
@@ -19,7 +22,7 @@ mod tests {
         let config = Config::default()
             .set_futures_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         let response = account.change_initial_leverage("LTCUSDT", 2).unwrap();
 
@@ -27,7 +30,7 @@ mod tests {
 
         assert_eq!(response.leverage, 2);
         assert_eq!(response.symbol, "LTCUSDT");
-        assert!(approx_eq!(f64, response.max_notional_value, 9223372036854776000.0, ulps = 2));
+        assert_eq!(response.max_notional_value, amount("9223372036854776000.0"));
     }
 
     #[test]
@@ -41,7 +44,27 @@ mod tests {
         let config = Config::default()
             .set_futures_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        account.cancel_all_open_orders("BTCUSDT").unwrap();
+
+        mock.assert();
+    }
+
+    #[test]
+    fn cancel_all_open_orders_signed_in_body() {
+        let mock = mock("DELETE", "/fapi/v1/allOpenOrders")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Missing)
+            .match_body(Matcher::Regex("^recvWindow=1234&symbol=BTCUSDT&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/futures/account/cancel_all_open_orders.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234)
+            .set_signed_payload_in_body(true);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         account.cancel_all_open_orders("BTCUSDT").unwrap();
 
@@ -59,10 +82,88 @@ mod tests {
         let config = Config::default()
             .set_futures_rest_api_endpoint(mockito::server_url())
             .set_recv_window(1234);
-        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config).unwrap();
         let _ = env_logger::try_init();
         account.change_position_mode(true).unwrap();
 
         mock.assert();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn get_all_open_orders() {
+        let mock = mock("GET", "/fapi/v1/openOrders")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/futures/account/get_all_open_orders.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let orders = account.get_all_open_orders().unwrap();
+
+        mock.assert();
+
+        assert_eq!(orders.len(), 1);
+        let order = &orders[0];
+        assert_eq!(order.order_id, 1917641);
+        assert_eq!(order.symbol, "BTCUSDT");
+        assert_eq!(order.status, OrderStatus::New);
+        assert_eq!(order.price, amount("19000.0"));
+        assert_eq!(order.orig_qty, amount("0.4"));
+    }
+
+    #[test]
+    fn position_information() {
+        let mock = mock("GET", "/fapi/v2/positionRisk")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/futures/account/position_information.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let positions = account.position_information().unwrap();
+
+        mock.assert();
+
+        assert_eq!(positions.len(), 2);
+        let position = &positions[0];
+        assert_eq!(position.symbol, "BTCUSDT");
+        assert_eq!(position.position_side, "BOTH");
+        assert_eq!(position.margin_type, "cross");
+        assert_eq!(position.position_amount, amount("0.5"));
+        assert_eq!(position.entry_price, amount("20000.0"));
+        assert_eq!(position.unrealized_profit, amount("50.0"));
+        assert_eq!(position.leverage, amount("10.0"));
+    }
+
+    #[test]
+    fn account_balance() {
+        let mock = mock("GET", "/fapi/v2/balance")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/futures/account/account_balance.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let balances = account.account_balance().unwrap();
+
+        mock.assert();
+
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].asset, "USDT");
+        assert_eq!(balances[0].balance, amount("122607.35137903"));
+        assert_eq!(balances[0].available_balance, amount("23.72469206"));
+        assert_eq!(balances[0].update_time, 1617939110373);
+    }
+}
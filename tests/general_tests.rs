@@ -3,9 +3,12 @@ use binance::config::*;
 use binance::general::*;
 use binance::model::*;
 
+mod common;
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::amount;
     use mockito::mock;
     use float_cmp::*;
 
@@ -17,7 +20,7 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let general: General = Binance::new_with_config(None, None, &config);
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
 
         let pong = general.ping().unwrap();
         mock_ping.assert();
@@ -33,7 +36,7 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let general: General = Binance::new_with_config(None, None, &config);
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
 
         let server_time = general.get_server_time().unwrap();
         mock_server_time.assert();
@@ -49,7 +52,7 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let general: General = Binance::new_with_config(None, None, &config);
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
 
         let exchange_info = general.exchange_info().unwrap();
         mock_exchange_info.assert();
@@ -65,13 +68,13 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let general: General = Binance::new_with_config(None, None, &config);
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
 
         let symbol = general.get_symbol_info("BNBBTC").unwrap();
         mock_exchange_info.assert();
 
         assert_eq!(symbol.symbol, "BNBBTC");
-        assert_eq!(symbol.status, "TRADING");
+        assert_eq!(symbol.status, SymbolStatus::Trading);
         assert_eq!(symbol.base_asset, "BNB");
         assert_eq!(symbol.base_asset_precision, 8);
         assert_eq!(symbol.quote_asset, "BTC");
@@ -93,23 +96,23 @@ mod tests {
         for filter in symbol.filters.into_iter() {
             match filter {
                 Filters::PriceFilter { min_price, max_price, tick_size } => {
-                    assert_eq!(min_price, "0.00000010");
-                    assert_eq!(max_price, "100000.00000000");
-                    assert_eq!(tick_size, "0.00000010");
+                    assert_eq!(min_price, amount("0.00000010"));
+                    assert_eq!(max_price, amount("100000.00000000"));
+                    assert_eq!(tick_size, amount("0.00000010"));
                 },
                 Filters::PercentPrice { multiplier_up, multiplier_down, avg_price_mins } => {
-                    assert_eq!(multiplier_up, "5");
-                    assert_eq!(multiplier_down, "0.2");
+                    assert_eq!(multiplier_up, amount("5.0"));
+                    assert_eq!(multiplier_down, amount("0.2"));
                     assert!(approx_eq!(f64, avg_price_mins.unwrap(), 5.0, ulps = 2));
                 },
                 Filters::LotSize { min_qty, max_qty, step_size } => {
-                    assert_eq!(min_qty, "0.01000000");
-                    assert_eq!(max_qty, "100000.00000000");
-                    assert_eq!(step_size, "0.01000000");
+                    assert_eq!(min_qty, amount("0.01000000"));
+                    assert_eq!(max_qty, amount("100000.00000000"));
+                    assert_eq!(step_size, amount("0.01000000"));
                 },
                 Filters::MinNotional { notional, min_notional, apply_to_market, avg_price_mins } => {
                     assert!(notional.is_none());
-                    assert_eq!(min_notional.unwrap(), "0.00010000");
+                    assert_eq!(min_notional.unwrap(), amount("0.00010000"));
                     assert_eq!(apply_to_market.unwrap(), true);
                     assert!(approx_eq!(f64, avg_price_mins.unwrap(), 5.0, ulps = 2));
                 },
@@ -117,9 +120,9 @@ mod tests {
                     assert_eq!(limit.unwrap(), 10);
                 },
                 Filters::MarketLotSize { min_qty, max_qty, step_size } => {
-                    assert_eq!(min_qty, "0.00000000");
-                    assert_eq!(max_qty, "8528.32329395");
-                    assert_eq!(step_size, "0.00000000");
+                    assert_eq!(min_qty, amount("0.00000000"));
+                    assert_eq!(max_qty, amount("8528.32329395"));
+                    assert_eq!(step_size, amount("0.00000000"));
                 },
                 Filters::MaxNumOrders { max_num_orders } => {
                     assert_eq!(max_num_orders.unwrap(), 200);
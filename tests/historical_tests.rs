@@ -0,0 +1,178 @@
+#![cfg(feature = "historical")]
+
+use binance::api::*;
+use binance::config::*;
+use binance::errors::*;
+use binance::historical::*;
+use binance::market::*;
+use binance::model::*;
+use std::fs;
+use std::path::PathBuf;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::amount;
+    use mockito::{mock, Matcher};
+
+    const AGG_TRADES_ARCHIVE: &str = "tests/mocks/historical/BTCUSDT-aggTrades-2024-01.zip";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("binance-historical-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn archive_paths() {
+        let archive = Archive::monthly(
+            ArchiveMarket::Spot,
+            DataType::Klines(KlineInterval::OneMinute),
+            "BTCUSDT",
+            2024,
+            1,
+        );
+        assert_eq!(archive.file_name(), "BTCUSDT-1m-2024-01.zip");
+        assert_eq!(archive.path(), "data/spot/monthly/klines/BTCUSDT/1m/BTCUSDT-1m-2024-01.zip");
+
+        let archive = Archive::daily(ArchiveMarket::Futures, DataType::Trades, "ETHUSDT", 2024, 3, 5);
+        assert_eq!(archive.path(), "data/futures/um/daily/trades/ETHUSDT/ETHUSDT-trades-2024-03-05.zip");
+    }
+
+    #[test]
+    fn load_klines_csv() {
+        let klines: Vec<KlineSummary> = load("tests/mocks/historical/BTCUSDT-1m-2024-01-01.csv").unwrap();
+
+        assert_eq!(klines.len(), 3);
+        assert_eq!(klines[0].open_time, 1704067200000);
+        assert_eq!(klines[0].close_time, 1704067259999);
+        assert_eq!(klines[0].open, amount("42283.58"));
+        assert_eq!(klines[0].close, amount("42298.61"));
+        assert_eq!(klines[0].number_of_trades, 1327);
+        assert_eq!(klines[2].taker_buy_quote_asset_volume, amount("407705.8654239"));
+        assert_eq!(resume_from(&klines), 1704067320001);
+    }
+
+    #[test]
+    fn load_verified_zip() {
+        let trades: Vec<AggTrade> = load(AGG_TRADES_ARCHIVE).unwrap();
+
+        assert_eq!(trades.len(), 3);
+        assert_eq!(trades[0].agg_id, 3198115419);
+        assert_eq!(trades[0].price, amount("42283.58"));
+        assert_eq!(trades[0].qty, amount("0.004"));
+        assert_eq!(trades[0].first_id, 3368931431);
+        assert_eq!(trades[1].last_id, 3368931433);
+        assert_eq!(trades[0].time, 1704067200045);
+        assert!(trades[0].maker);
+        assert!(!trades[1].maker);
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        let dir = temp_dir("checksum");
+        let path = dir.join("BTCUSDT-aggTrades-2024-01.zip");
+        fs::copy(AGG_TRADES_ARCHIVE, &path).unwrap();
+        fs::write(
+            dir.join("BTCUSDT-aggTrades-2024-01.zip.CHECKSUM"),
+            "0000000000000000000000000000000000000000000000000000000000000000  BTCUSDT-aggTrades-2024-01.zip\n",
+        )
+        .unwrap();
+
+        assert!(verify_checksum(&path).is_err());
+        match load::<AggTrade, _>(&path).unwrap_err() {
+            BinanceError::Msg(msg) => assert!(msg.starts_with("Checksum mismatch")),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn futures_csv_with_header_and_microseconds() {
+        let file = fs::File::open("tests/mocks/historical/BTCUSDT-trades-2025-01-01.csv").unwrap();
+        let trades: Vec<Trade> = read_csv(file).unwrap();
+
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].id, 5697845801);
+        assert_eq!(trades[0].time, 1735689600000);
+        assert_eq!(trades[1].quote_qty, amount("14036.415"));
+        assert!(trades[1].is_buyer_maker);
+    }
+
+    #[test]
+    fn merge_with_rest() {
+        let mock_agg_trades = mock("GET", "/api/v3/aggTrades")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("fromId=3198115422&limit=1000&symbol=BTCUSDT".into()))
+            .with_body(
+                r#"[{"a":3198115422,"p":"42286.00","q":"0.1","f":3368931435,"l":3368931435,"T":1704067201000,"m":true,"M":true},
+                    {"a":3198115423,"p":"42287.00","q":"0.2","f":3368931436,"l":3368931437,"T":1704067202000,"m":false,"M":true}]"#,
+            )
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
+        let mut trades: Vec<AggTrade> = load(AGG_TRADES_ARCHIVE).unwrap();
+        let recent = market.agg_trades_from("BTCUSDT", resume_from(&trades));
+        merge(&mut trades, recent).unwrap();
+
+        mock_agg_trades.assert();
+        let ids: Vec<u64> = trades.iter().map(|trade| trade.agg_id).collect();
+        assert_eq!(ids, vec![3198115419, 3198115420, 3198115421, 3198115422, 3198115423]);
+
+        // Records already archived are skipped.
+        let archived = trades.clone();
+        merge(&mut trades, archived.into_iter().map(Ok)).unwrap();
+        assert_eq!(trades.len(), 5);
+    }
+
+    #[test]
+    fn merge_skips_open_kline() {
+        let loaded: Vec<KlineSummary> = load("tests/mocks/historical/BTCUSDT-1m-2024-01-01.csv").unwrap();
+        let mut klines = loaded[..1].to_vec();
+        let mut open = loaded[2].clone();
+        open.close_time = i64::MAX;
+
+        merge(&mut klines, vec![Ok(loaded[1].clone()), Ok(open)]).unwrap();
+        assert_eq!(klines.len(), 2);
+        assert_eq!(resume_from(&klines), loaded[1].open_time as u64 + 1);
+
+        // Once closed, the next merge takes it.
+        merge(&mut klines, vec![Ok(loaded[2].clone())]).unwrap();
+        assert_eq!(klines.len(), 3);
+        assert_eq!(klines[2].close_time, loaded[2].close_time);
+    }
+
+    #[test]
+    fn download() {
+        let archive = Archive::monthly(ArchiveMarket::Spot, DataType::AggTrades, "BTCUSDT", 2024, 1);
+        let mock_archive = mock("GET", format!("/{}", archive.path()).as_str())
+            .with_body_from_file(AGG_TRADES_ARCHIVE)
+            .create();
+        let mock_checksum = mock("GET", format!("/{}.CHECKSUM", archive.path()).as_str())
+            .with_body_from_file(format!("{}.CHECKSUM", AGG_TRADES_ARCHIVE))
+            .create();
+        let mock_missing = mock("GET", Matcher::Regex("2024-02".into())).with_status(404).create();
+
+        let dir = temp_dir("download");
+        let config = Config::default().set_data_endpoint(mockito::server_url());
+        let downloader = Downloader::new(&config).unwrap();
+        let path = downloader.download(&archive, &dir).unwrap();
+
+        mock_archive.assert();
+        mock_checksum.assert();
+        assert_eq!(path, dir.join("BTCUSDT-aggTrades-2024-01.zip"));
+        assert_eq!(load::<AggTrade, _>(&path).unwrap().len(), 3);
+
+        let missing = Archive::monthly(ArchiveMarket::Spot, DataType::AggTrades, "BTCUSDT", 2024, 2);
+        match downloader.download(&missing, &dir).unwrap_err() {
+            BinanceError::Http { status, .. } => assert_eq!(status, 404),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        mock_missing.assert();
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,97 @@
+use binance::api::*;
+use binance::config::*;
+use binance::errors::*;
+use binance::account::*;
+use binance::futures::market::FuturesMarket;
+use binance::general::*;
+use binance::market::*;
+use binance::model::*;
+use binance::websockets::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+
+    const FIXTURES: &[&str] = &[
+        "empty_object.json",
+        "html.json",
+        "null.json",
+        "short_kline_row.json",
+        "truncated.json",
+        "wrong_fields.json",
+        "wrong_types.json",
+    ];
+
+    // Every endpoint is served the same malformed body, so this stays a single test
+    // to keep the mocks of the shared server from overlapping.
+    #[test]
+    fn malformed_responses_are_errors() {
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_futures_rest_api_endpoint(mockito::server_url());
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
+        let futures_market: FuturesMarket = Binance::new_with_config(None, None, &config).unwrap();
+
+        for fixture in FIXTURES {
+            let _mock = mock("GET", Matcher::Any)
+                .with_header("content-type", "application/json;charset=UTF-8")
+                .with_body_from_file(format!("tests/mocks/malformed/{}", fixture))
+                .create();
+
+            assert!(general.get_server_time().is_err(), "{}", fixture);
+            assert!(general.exchange_info().is_err(), "{}", fixture);
+            assert!(general.get_symbol_info("BNBBTC").is_err(), "{}", fixture);
+            assert!(general.get_symbol_rules("BNBBTC").is_err(), "{}", fixture);
+
+            assert!(market.get_depth("BNBBTC").is_err(), "{}", fixture);
+            assert!(market.get_all_prices().is_err(), "{}", fixture);
+            assert!(market.get_price("BNBBTC").is_err(), "{}", fixture);
+            assert!(market.get_24h_price_stats("BNBBTC").is_err(), "{}", fixture);
+            assert!(market.get_klines("BNBBTC", "1m", None, None, None).is_err(), "{}", fixture);
+            let mut klines = market.klines_range("BNBBTC", KlineInterval::OneMinute, 0, 60_000);
+            assert!(klines.next().unwrap().is_err(), "{}", fixture);
+            assert!(klines.next().is_none(), "{}", fixture);
+
+            assert!(account.get_account().is_err(), "{}", fixture);
+            assert!(account.get_balance("BNB").is_err(), "{}", fixture);
+            assert!(account.get_open_orders("BNBBTC").is_err(), "{}", fixture);
+            assert!(account.order_status("BNBBTC", 1).is_err(), "{}", fixture);
+
+            assert!(futures_market.get_depth("BTCUSDT").is_err(), "{}", fixture);
+            assert!(futures_market.get_klines("BTCUSDT", "1m", None, None, None).is_err(), "{}", fixture);
+            assert!(futures_market.get_mark_prices().is_err(), "{}", fixture);
+            assert!(futures_market.get_all_liquidation_orders().is_err(), "{}", fixture);
+        }
+    }
+
+    #[test]
+    fn malformed_stream_messages_do_not_panic() {
+        let mut web_socket = WebSockets::new(|_event: WebsocketEvent| Ok(()));
+
+        for fixture in FIXTURES {
+            let msg = std::fs::read_to_string(format!("tests/mocks/malformed/{}", fixture)).unwrap();
+            let _ = web_socket.test_handle_msg(&msg);
+        }
+        let _ = web_socket.test_handle_msg(r#"{"stream":"bnbbtc@kline_1m","data":{"e":"kline","k":[]}}"#);
+    }
+
+    #[test]
+    fn unknown_stream_event_is_an_error() {
+        let mut handled = 0;
+        let mut web_socket = WebSockets::new(|_event: WebsocketEvent| {
+            handled += 1;
+            Ok(())
+        });
+
+        let msg = r#"{"e":"newEvent","E":1640995201102,"s":"BTCUSDT"}"#;
+        match web_socket.test_handle_msg(msg).unwrap_err() {
+            BinanceError::Decode { body, .. } => assert_eq!(body, msg),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        drop(web_socket);
+        assert_eq!(handled, 0);
+    }
+}
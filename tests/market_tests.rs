@@ -3,11 +3,13 @@ use binance::config::*;
 use binance::market::*;
 use binance::model::*;
 
+mod common;
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::amount;
     use mockito::{mock, Matcher};
-    use float_cmp::*;
 
     #[test]
     fn get_depth() {
@@ -19,13 +21,13 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let market: Market = Binance::new_with_config(None, None, &config);
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
 
         let order_book = market.get_depth("LTCBTC").unwrap();
         mock_get_depth.assert();
 
         assert_eq!(order_book.last_update_id, 1027024);
-        assert_eq!(order_book.bids[0], Bids::new(4.00000000, 431.00000000));
+        assert_eq!(order_book.bids[0], Bids::new(amount("4.00000000"), amount("431.00000000")));
 
     }
 
@@ -39,13 +41,13 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let market: Market = Binance::new_with_config(None, None, &config);
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
 
         let order_book = market.get_custom_depth("LTCBTC", 10).unwrap();
         mock_get_custom_depth.assert();
 
         assert_eq!(order_book.last_update_id, 1027024);
-        assert_eq!(order_book.bids[0], Bids::new(4.00000000, 431.00000000));
+        assert_eq!(order_book.bids[0], Bids::new(amount("4.00000000"), amount("431.00000000")));
 
     }
 
@@ -58,7 +60,7 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let market: Market = Binance::new_with_config(None, None, &config);
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
 
         let prices: Prices = market.get_all_prices().unwrap();
         mock_get_all_prices.assert();
@@ -68,10 +70,10 @@ mod tests {
                 assert!(!symbols.is_empty());
                 let first_symbol = symbols[0].clone();
                 assert_eq!(first_symbol.symbol, "LTCBTC");
-                assert!(approx_eq!(f64, first_symbol.price, 4.00000200, ulps = 2));
+                assert_eq!(first_symbol.price, amount("4.00000200"));
                 let second_symbol = symbols[1].clone();
                 assert_eq!(second_symbol.symbol, "ETHBTC");
-                assert!(approx_eq!(f64, second_symbol.price, 0.07946600, ulps = 2));
+                assert_eq!(second_symbol.price, amount("0.07946600"));
             }
         }
 
@@ -87,13 +89,13 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let market: Market = Binance::new_with_config(None, None, &config);
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
 
         let symbol = market.get_price("LTCBTC").unwrap();
         mock_get_price.assert();
 
         assert_eq!(symbol.symbol, "LTCBTC");
-        assert!(approx_eq!(f64, symbol.price, 4.00000200, ulps = 2));
+        assert_eq!(symbol.price, amount("4.00000200"));
 
     }
 
@@ -107,13 +109,13 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let market: Market = Binance::new_with_config(None, None, &config);
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
 
         let symbol = market.get_average_price("LTCBTC").unwrap();
         mock_get_average_price.assert();
 
         assert_eq!(symbol.mins, 5);
-        assert!(approx_eq!(f64, symbol.price, 9.35751834, ulps = 2));
+        assert_eq!(symbol.price, amount("9.35751834"));
         
     }
 
@@ -126,7 +128,7 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let market: Market = Binance::new_with_config(None, None, &config);
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
 
         let book_tickers = market.get_all_book_tickers().unwrap();
         mock_get_all_book_tickers.assert();
@@ -137,16 +139,16 @@ mod tests {
                 assert!(!tickers.is_empty());
                 let first_ticker = tickers[0].clone();
                 assert_eq!(first_ticker.symbol, "LTCBTC");
-                assert!(approx_eq!(f64, first_ticker.bid_price, 4.00000000, ulps = 2));
-                assert!(approx_eq!(f64, first_ticker.bid_qty, 431.00000000, ulps = 2));
-                assert!(approx_eq!(f64, first_ticker.ask_price, 4.00000200, ulps = 2));
-                assert!(approx_eq!(f64, first_ticker.ask_qty, 9.00000000, ulps = 2));
+                assert_eq!(first_ticker.bid_price, amount("4.00000000"));
+                assert_eq!(first_ticker.bid_qty, amount("431.00000000"));
+                assert_eq!(first_ticker.ask_price, amount("4.00000200"));
+                assert_eq!(first_ticker.ask_qty, amount("9.00000000"));
                 let second_ticker = tickers[1].clone();
                 assert_eq!(second_ticker.symbol, "ETHBTC");
-                assert!(approx_eq!(f64, second_ticker.bid_price, 0.07946700, ulps = 2));
-                assert!(approx_eq!(f64, second_ticker.bid_qty, 9.00000000, ulps = 2));
-                assert!(approx_eq!(f64, second_ticker.ask_price, 100000.00000000, ulps = 2));
-                assert!(approx_eq!(f64, second_ticker.ask_qty, 1000.00000000, ulps = 2));
+                assert_eq!(second_ticker.bid_price, amount("0.07946700"));
+                assert_eq!(second_ticker.bid_qty, amount("9.00000000"));
+                assert_eq!(second_ticker.ask_price, amount("100000.00000000"));
+                assert_eq!(second_ticker.ask_qty, amount("1000.00000000"));
                 
             }
         }
@@ -163,16 +165,16 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let market: Market = Binance::new_with_config(None, None, &config);
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
 
         let book_ticker = market.get_book_ticker("LTCBTC").unwrap();
         mock_get_book_ticker.assert();
 
         assert_eq!(book_ticker.symbol, "LTCBTC");
-        assert!(approx_eq!(f64, book_ticker.bid_price, 4.00000000, ulps = 2));
-        assert!(approx_eq!(f64, book_ticker.bid_qty, 431.00000000, ulps = 2));
-        assert!(approx_eq!(f64, book_ticker.ask_price, 4.00000200, ulps = 2));
-        assert!(approx_eq!(f64, book_ticker.ask_qty, 9.00000000, ulps = 2));
+        assert_eq!(book_ticker.bid_price, amount("4.00000000"));
+        assert_eq!(book_ticker.bid_qty, amount("431.00000000"));
+        assert_eq!(book_ticker.ask_price, amount("4.00000200"));
+        assert_eq!(book_ticker.ask_qty, amount("9.00000000"));
         
     }
 
@@ -186,23 +188,23 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let market: Market = Binance::new_with_config(None, None, &config);
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
 
         let price_stats = market.get_24h_price_stats("BNBBTC").unwrap();
         mock_get_24h_price_stats.assert();
 
         assert_eq!(price_stats.symbol, "BNBBTC");
-        assert_eq!(price_stats.price_change, "-94.99999800");
-        assert_eq!(price_stats.price_change_percent, "-95.960");
-        assert_eq!(price_stats.weighted_avg_price, "0.29628482");
-        assert!(approx_eq!(f64, price_stats.prev_close_price, 0.10002000, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.last_price, 4.00000200, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.bid_price, 4.00000000, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.ask_price, 4.00000200, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.open_price, 99.00000000, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.high_price, 100.00000000, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.low_price, 0.10000000, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.volume, 8913.30000000, ulps = 2));
+        assert_eq!(price_stats.price_change, amount("-94.99999800"));
+        assert_eq!(price_stats.price_change_percent, amount("-95.960"));
+        assert_eq!(price_stats.weighted_avg_price, amount("0.29628482"));
+        assert_eq!(price_stats.prev_close_price, amount("0.10002000"));
+        assert_eq!(price_stats.last_price, amount("4.00000200"));
+        assert_eq!(price_stats.bid_price, amount("4.00000000"));
+        assert_eq!(price_stats.ask_price, amount("4.00000200"));
+        assert_eq!(price_stats.open_price, amount("99.00000000"));
+        assert_eq!(price_stats.high_price, amount("100.00000000"));
+        assert_eq!(price_stats.low_price, amount("0.10000000"));
+        assert_eq!(price_stats.volume, amount("8913.30000000"));
         assert_eq!(price_stats.open_time, 1499783499040);
         assert_eq!(price_stats.close_time, 1499869899040);
         assert_eq!(price_stats.first_id, 28385);
@@ -220,7 +222,7 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let market: Market = Binance::new_with_config(None, None, &config);
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
 
         let prices_stats = market.get_all_24h_price_stats().unwrap();
         mock_get_all_24h_price_stats.assert();
@@ -230,17 +232,17 @@ mod tests {
         let price_stats = prices_stats[0].clone();
 
         assert_eq!(price_stats.symbol, "BNBBTC");
-        assert_eq!(price_stats.price_change, "-94.99999800");
-        assert_eq!(price_stats.price_change_percent, "-95.960");
-        assert_eq!(price_stats.weighted_avg_price, "0.29628482");
-        assert!(approx_eq!(f64, price_stats.prev_close_price, 0.10002000, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.last_price, 4.00000200, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.bid_price, 4.00000000, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.ask_price, 4.00000200, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.open_price, 99.00000000, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.high_price, 100.00000000, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.low_price, 0.10000000, ulps = 2));
-        assert!(approx_eq!(f64, price_stats.volume, 8913.30000000, ulps = 2));
+        assert_eq!(price_stats.price_change, amount("-94.99999800"));
+        assert_eq!(price_stats.price_change_percent, amount("-95.960"));
+        assert_eq!(price_stats.weighted_avg_price, amount("0.29628482"));
+        assert_eq!(price_stats.prev_close_price, amount("0.10002000"));
+        assert_eq!(price_stats.last_price, amount("4.00000200"));
+        assert_eq!(price_stats.bid_price, amount("4.00000000"));
+        assert_eq!(price_stats.ask_price, amount("4.00000200"));
+        assert_eq!(price_stats.open_price, amount("99.00000000"));
+        assert_eq!(price_stats.high_price, amount("100.00000000"));
+        assert_eq!(price_stats.low_price, amount("0.10000000"));
+        assert_eq!(price_stats.volume, amount("8913.30000000"));
         assert_eq!(price_stats.open_time, 1499783499040);
         assert_eq!(price_stats.close_time, 1499869899040);
         assert_eq!(price_stats.first_id, 28385);
@@ -259,7 +261,7 @@ mod tests {
             .create();
 
         let config = Config::default().set_rest_api_endpoint(mockito::server_url());
-        let market: Market = Binance::new_with_config(None, None, &config);
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
 
         let klines = market.get_klines("LTCBTC", "5m", 10, None, None).unwrap();
         mock_get_klines.assert();
@@ -272,16 +274,16 @@ mod tests {
                 let kline: KlineSummary = klines[0].clone();
 
                 assert_eq!(kline.open_time, 1499040000000);
-                assert!(approx_eq!(f64, kline.open, 0.01634790, ulps = 2));
-                assert!(approx_eq!(f64, kline.high, 0.80000000, ulps = 2));
-                assert!(approx_eq!(f64, kline.low, 0.01575800, ulps = 2));
-                assert!(approx_eq!(f64, kline.close, 0.01577100, ulps = 2));
-                assert!(approx_eq!(f64, kline.volume, 148976.11427815, ulps = 2));
+                assert_eq!(kline.open, amount("0.01634790"));
+                assert_eq!(kline.high, amount("0.80000000"));
+                assert_eq!(kline.low, amount("0.01575800"));
+                assert_eq!(kline.close, amount("0.01577100"));
+                assert_eq!(kline.volume, amount("148976.11427815"));
                 assert_eq!(kline.close_time, 1499644799999);
-                assert!(approx_eq!(f64, kline.quote_asset_volume, 2434.19055334, ulps = 2));
+                assert_eq!(kline.quote_asset_volume, amount("2434.19055334"));
                 assert_eq!(kline.number_of_trades, 308);
-                assert!(approx_eq!(f64, kline.taker_buy_base_asset_volume, 1756.87402397, ulps = 2));
-                assert!(approx_eq!(f64, kline.taker_buy_quote_asset_volume, 28.46694368, ulps = 2));
+                assert_eq!(kline.taker_buy_base_asset_volume, amount("1756.87402397"));
+                assert_eq!(kline.taker_buy_quote_asset_volume, amount("28.46694368"));
 
             }
 
@@ -289,4 +291,93 @@ mod tests {
 
     }
 
+
+    fn kline_rows(open_times: impl Iterator<Item = u64>) -> String {
+        let rows: Vec<String> = open_times
+            .map(|t| format!(
+                "[{},\"1.0\",\"2.0\",\"0.5\",\"1.5\",\"10.0\",{},\"15.0\",3,\"4.0\",\"6.0\",\"0\"]",
+                t, t + 59_999
+            ))
+            .collect();
+        format!("[{}]", rows.join(","))
+    }
+
+    #[test]
+    fn klines_range() {
+
+        let mock_first_page = mock("GET", "/api/v3/klines")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("endTime=60000000&interval=1m&limit=1000&startTime=0&symbol=ETHBTC".into()))
+            .with_body(kline_rows((0..1000).map(|i| i * 60_000)))
+            .create();
+        let mock_second_page = mock("GET", "/api/v3/klines")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("endTime=60000000&interval=1m&limit=1000&startTime=59940001&symbol=ETHBTC".into()))
+            .with_body(kline_rows(std::iter::once(60_000_000)))
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
+
+        let klines: Vec<KlineSummary> = market
+            .klines_range("ETHBTC", KlineInterval::OneMinute, 0, 60_000_000)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        mock_first_page.assert();
+        mock_second_page.assert();
+
+        assert_eq!(klines.len(), 1001);
+        assert_eq!(klines[0].open_time, 0);
+        assert_eq!(klines[1000].open_time, 60_000_000);
+        assert_eq!(klines[1000].close_time, 60_059_999);
+        assert_eq!(klines[1000].number_of_trades, 3);
+
+    }
+
+    #[test]
+    fn historical_trades_from() {
+
+        let mock_historical_trades = mock("GET", "/api/v3/historicalTrades")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_header("x-mbx-apikey", "api_key")
+            .match_query(Matcher::Regex("fromId=28457&limit=1000&symbol=BNBBTC".into()))
+            .with_body(
+                r#"[{"id":28457,"price":"4.00000100","qty":"12.00000000","quoteQty":"48.000012","time":1499865549590,"isBuyerMaker":true,"isBestMatch":true}]"#,
+            )
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(Some("api_key".into()), None, &config).unwrap();
+
+        let trades: Vec<Trade> = market
+            .historical_trades_from("BNBBTC", 28457)
+            .collect::<binance::errors::Result<_>>()
+            .unwrap();
+        mock_historical_trades.assert();
+
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].id, 28457);
+        assert_eq!(trades[0].price, amount("4.00000100"));
+
+    }
+
+    #[test]
+    fn get_klines_malformed_row() {
+
+        let mock_get_klines = mock("GET", "/api/v3/klines")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("interval=1h&symbol=BNBBTC".into()))
+            .with_body("[[1499040000000,\"not a number\"]]")
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
+
+        let klines = market.get_klines("BNBBTC", KlineInterval::OneHour, None, None, None);
+        mock_get_klines.assert();
+
+        assert!(klines.is_err());
+
+    }
+
 }
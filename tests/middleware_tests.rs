@@ -0,0 +1,105 @@
+use binance::account::*;
+use binance::api::*;
+use binance::config::*;
+use binance::middleware::*;
+use binance::userstream::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Default)]
+    struct Recorder {
+        requests: Mutex<Vec<RequestInfo>>,
+        responses: Mutex<Vec<ResponseInfo>>,
+    }
+
+    impl Middleware for Recorder {
+        fn before_send(&self, request: &RequestInfo) {
+            self.requests.lock().unwrap().push(request.clone());
+        }
+
+        fn after_receive(&self, _request: &RequestInfo, response: &ResponseInfo) {
+            self.responses.lock().unwrap().push(response.clone());
+        }
+    }
+
+    #[test]
+    fn signed_request() {
+        let mock_get_account = mock("GET", "/api/v3/account")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_header("x-mbx-used-weight-1m", "21")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/get_account.json")
+            .create();
+
+        let recorder = Arc::new(Recorder::default());
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234)
+            .add_middleware(recorder.clone())
+            .add_middleware(LogMiddleware);
+        let account: Account = Binance::new_with_config(Some("api_key".into()), Some("secret".into()), &config).unwrap();
+        account.get_account().unwrap();
+        mock_get_account.assert();
+
+        let requests = recorder.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/api/v3/account");
+        assert_eq!(requests[0].weight, 20);
+        assert!(requests[0].signed);
+        assert!(requests[0].payload.starts_with("recvWindow=1234&timestamp="));
+        assert!(requests[0].payload.ends_with("&signature=<redacted>"));
+        assert!(!requests[0].payload.contains("api_key"));
+
+        let responses = recorder.responses.lock().unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].status, Some(200));
+        assert_eq!(responses[0].rate_limit_usage.used_weight.get("1m"), Some(&21));
+    }
+
+    #[test]
+    fn listen_key_redacted() {
+        let mock_keep_alive = mock("PUT", "/api/v3/userDataStream")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body("{}")
+            .create();
+
+        let recorder = Arc::new(Recorder::default());
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .add_middleware(recorder.clone());
+        let user_stream: UserStream = Binance::new_with_config(Some("api_key".into()), None, &config).unwrap();
+        user_stream.keep_alive("secret-listen-key").unwrap();
+        mock_keep_alive.assert();
+
+        let requests = recorder.requests.lock().unwrap();
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(requests[0].payload, "listenKey=<redacted>");
+    }
+
+    #[test]
+    fn failed_request() {
+        let recorder = Arc::new(Recorder::default());
+        let config = Config::default()
+            .set_rest_api_endpoint("http://127.0.0.1:1")
+            .add_middleware(recorder.clone());
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
+        assert!(account.get_account().is_err());
+
+        let responses = recorder.responses.lock().unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].status, None);
+    }
+
+    #[test]
+    fn redact_parameters() {
+        assert_eq!(redact("symbol=BTCUSDT&signature=abc"), "symbol=BTCUSDT&signature=<redacted>");
+        assert_eq!(redact("listenKey=abc"), "listenKey=<redacted>");
+        assert_eq!(redact("signatures=abc"), "signatures=abc");
+        assert_eq!(redact(""), "");
+    }
+}
@@ -0,0 +1,185 @@
+use binance::account::*;
+use binance::api::*;
+use binance::config::*;
+use binance::errors::*;
+use binance::model::*;
+use binance::paper::*;
+use binance::trading::*;
+use binance::websockets::*;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::amount;
+    use mockito::{mock, Matcher};
+
+    fn assert_amount(actual: Amount, expected: &str) {
+        let delta = actual - amount(expected);
+        assert!(delta.abs() < amount("0.00000001"), "{} != {}", actual, expected);
+    }
+
+    fn account_information(commission: f32, balances: &[(&str, &str)]) -> AccountInformation {
+        AccountInformation {
+            maker_commission: commission,
+            taker_commission: commission,
+            buyer_commission: 0.0,
+            seller_commission: 0.0,
+            can_trade: true,
+            can_withdraw: true,
+            can_deposit: true,
+            balances: balances
+                .iter()
+                .map(|(asset, free)| Balance {
+                    asset: asset.to_string(),
+                    free: amount(free),
+                    locked: Amount::default(),
+                })
+                .collect(),
+        }
+    }
+
+    fn book_ticker(bid: &str, ask: &str) -> WebsocketEvent {
+        WebsocketEvent::BookTicker(BookTickerEvent {
+            update_id: 1,
+            symbol: "BTCUSDT".into(),
+            best_bid: amount(bid),
+            best_bid_qty: amount("1"),
+            best_ask: amount(ask),
+            best_ask_qty: amount("1"),
+        })
+    }
+
+    fn trade(price: &str) -> WebsocketEvent {
+        let trade = format!(
+            r#"{{"e":"trade","E":1,"s":"BTCUSDT","t":1,"p":"{}","q":"1","b":1,"a":2,"T":1,"m":true,"M":true}}"#,
+            price
+        );
+        WebsocketEvent::Trade(serde_json::from_str(&trade).unwrap())
+    }
+
+    fn api_error_code(error: BinanceError) -> i16 {
+        match error {
+            BinanceError::Api { code, .. } => code,
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn spot_orders() {
+        let paper = PaperAccount::spot(&account_information(10.0, &[("USDT", "10000")]));
+        paper.add_symbol("BTCUSDT", "BTC", "USDT");
+
+        assert!(paper.market_buy("BTCUSDT", amount("0.25")).is_err());
+
+        paper.handle_event(&book_ticker("19990", "20000"));
+        let market = paper.market_buy("BTCUSDT", amount("0.25")).unwrap();
+        assert_eq!(market.status, OrderStatus::Filled);
+        assert_eq!(market.order_type, OrderType::Market);
+        assert_amount(market.cumulative_quote_qty, "5000");
+        assert_amount(paper.balance("USDT").free, "5000");
+        assert_amount(paper.balance("BTC").free, "0.24975");
+
+        let limit = paper.limit_buy("BTCUSDT", amount("0.1"), amount("19000")).unwrap();
+        assert_eq!(limit.status, OrderStatus::New);
+        assert_amount(paper.balance("USDT").free, "3100");
+        assert_amount(paper.balance("USDT").locked, "1900");
+        assert_eq!(paper.open_orders("BTCUSDT"), vec![limit.clone()]);
+
+        // Trades at the price may have been ahead in the queue.
+        paper.handle_event(&trade("19000"));
+        assert_eq!(paper.open_orders("BTCUSDT").len(), 1);
+        paper.handle_event(&trade("18990"));
+        let filled = paper.order_status(limit.order_id).unwrap();
+        assert_eq!(filled.status, OrderStatus::Filled);
+        assert_amount(filled.cumulative_quote_qty, "1900");
+        assert_amount(paper.balance("USDT").locked, "0");
+        assert_amount(paper.balance("BTC").free, "0.34965");
+
+        let trades = paper.trades();
+        assert_eq!(trades.len(), 2);
+        assert!(!trades[0].is_maker);
+        assert!(trades[1].is_maker);
+        assert_eq!(trades[1].commission_asset, "BTC");
+        assert_amount(trades[1].price, "19000");
+    }
+
+    #[test]
+    fn spot_cancel_and_rejections() {
+        let paper = PaperAccount::spot(&account_information(10.0, &[("BTC", "1")]));
+        paper.add_symbol("BTCUSDT", "BTC", "USDT");
+        paper.handle_event(&book_ticker("19990", "20000"));
+
+        let sell = paper.limit_sell("BTCUSDT", amount("0.4"), amount("21000")).unwrap();
+        assert_amount(paper.balance("BTC").locked, "0.4");
+        paper.cancel_order("BTCUSDT", sell.order_id).unwrap();
+        assert_eq!(paper.order_status(sell.order_id).unwrap().status, OrderStatus::Canceled);
+        assert_amount(paper.balance("BTC").free, "1");
+        assert_eq!(api_error_code(paper.cancel_order("BTCUSDT", sell.order_id).unwrap_err()), -2011);
+
+        assert_eq!(api_error_code(paper.limit_buy("BTCUSDT", amount("1"), amount("100")).unwrap_err()), -2010);
+        assert_eq!(api_error_code(paper.market_sell("ETHUSDT", amount("1")).unwrap_err()), -1121);
+
+        // Crosses the bid, so it takes it.
+        let sell = paper.limit_sell("BTCUSDT", amount("0.5"), amount("19000")).unwrap();
+        assert_eq!(sell.status, OrderStatus::Filled);
+        assert_amount(sell.cumulative_quote_qty, "9995");
+        assert_amount(paper.balance("USDT").free, "9985.0050");
+
+        paper.limit_sell("BTCUSDT", amount("0.1"), amount("21000")).unwrap();
+        paper.limit_sell("BTCUSDT", amount("0.1"), amount("22000")).unwrap();
+        paper.cancel_all_open_orders("BTCUSDT").unwrap();
+        assert!(paper.open_orders("BTCUSDT").is_empty());
+        assert_amount(paper.balance("BTC").free, "0.5");
+    }
+
+    #[test]
+    fn futures_positions() {
+        let paper = PaperAccount::futures(&account_information(4.0, &[("USDT", "1000")]));
+        paper.add_symbol("BTCUSDT", "BTC", "USDT");
+        paper.handle_event(&book_ticker("99", "100"));
+
+        paper.market_buy("BTCUSDT", amount("0.5")).unwrap();
+        let position = paper.position("BTCUSDT");
+        assert_amount(position.amount, "0.5");
+        assert_amount(position.entry_price, "100");
+
+        // Closes the long and opens a short.
+        paper.handle_event(&book_ticker("110", "111"));
+        paper.market_sell("BTCUSDT", amount("1")).unwrap();
+        let position = paper.position("BTCUSDT");
+        assert_amount(position.amount, "-0.5");
+        assert_amount(position.entry_price, "110");
+        assert_amount(position.realized_pnl, "5");
+
+        paper.limit_buy("BTCUSDT", amount("0.5"), amount("105")).unwrap();
+        paper.handle_event(&book_ticker("104", "105"));
+        let position = paper.position("BTCUSDT");
+        assert_amount(position.amount, "0");
+        assert_amount(position.realized_pnl, "7.5");
+        // Less 0.04% of the 50, 110 and 52.5 traded.
+        assert_amount(paper.balance("USDT").free, "1007.415");
+    }
+
+    #[test]
+    fn live_account_through_trading() {
+        let mock_limit_buy = mock("POST", "/api/v3/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("price=0.1&quantity=1&recvWindow=1234&side=BUY&symbol=LTCBTC&timeInForce=GTC&timestamp=\\d+&type=LIMIT".into()))
+            .with_body_from_file("tests/mocks/account/limit_buy.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config).unwrap();
+        let trader: &dyn Trading = &account;
+        let report = trader.limit_buy("LTCBTC", amount("1"), amount("0.1")).unwrap();
+
+        mock_limit_buy.assert();
+        assert_eq!(report.order_id, 1);
+        assert_eq!(report.status, OrderStatus::New);
+        assert_eq!(report.side, OrderSide::Buy);
+    }
+}
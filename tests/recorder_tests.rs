@@ -0,0 +1,137 @@
+#![cfg(feature = "recorder")]
+
+use binance::config::*;
+use binance::recorder::*;
+use binance::transport::*;
+use binance::websockets::*;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+    use tungstenite::Message;
+
+    const FRAMES: &str = "tests/mocks/websocket/ticker_frames.jsonl";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("binance-recorder-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn file_names(dir: &PathBuf) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn rotate_and_replay() {
+        let frames = read_frames(FRAMES).unwrap();
+        let dir = temp_dir("rotate");
+        {
+            let mut recorder = Recorder::new(&dir)
+                .unwrap()
+                .set_prefix("ticker")
+                .set_rotation(Duration::from_secs(60));
+            recorder.record_at(1700000000000, &frames[0]).unwrap();
+            recorder.record_at(1700000001500, &frames[1]).unwrap();
+            recorder.record_at(1700000061000, &frames[2]).unwrap();
+        }
+        assert_eq!(
+            file_names(&dir),
+            vec!["ticker-1700000000000.ndjson.zst", "ticker-1700000061000.ndjson.zst"]
+        );
+
+        let recorded: Vec<RecordedFrame> = read_recording(&dir).unwrap().map(|frame| frame.unwrap()).collect();
+        let received_at: Vec<u64> = recorded.iter().map(|frame| frame.received_at).collect();
+        assert_eq!(received_at, vec![1700000000000, 1700000001500, 1700000061000]);
+        let recorded: Vec<String> = recorded.into_iter().map(|frame| frame.frame).collect();
+        assert_eq!(recorded, frames);
+
+        let single = read_recording(dir.join("ticker-1700000061000.ndjson.zst")).unwrap();
+        assert_eq!(single.count(), 1);
+
+        let mut events = Vec::new();
+        {
+            let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+                events.push(event);
+                Ok(())
+            });
+            web_socket.connect_recording(&dir).unwrap();
+            web_socket.event_loop(&AtomicBool::new(true)).unwrap();
+        }
+        assert_eq!(events.len(), 3);
+        match &events[2] {
+            WebsocketEvent::Trade(trade) => assert_eq!(trade.trade_id, 1215491004),
+            e => panic!("Unexpected event: {:?}", e),
+        }
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn unfinished_file() {
+        let frames = read_frames(FRAMES).unwrap();
+        let dir = temp_dir("unfinished");
+        let mut recorder = Recorder::new(&dir).unwrap();
+        recorder.record_at(1700000000000, &frames[0]).unwrap();
+        recorder.record_at(1700000002000, &frames[1]).unwrap();
+        // A crash, the zstd stream is left without its end.
+        std::mem::forget(recorder);
+
+        let recorded: Vec<RecordedFrame> = read_recording(&dir).unwrap().map(|frame| frame.unwrap()).collect();
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(recorded[1].frame, frames[1]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn record_live_stream() {
+        let frames = fs::read_to_string(FRAMES).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("ws://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            for frame in frames.lines() {
+                socket.write_message(Message::Text(frame.to_string())).unwrap();
+            }
+            socket.close(None).unwrap();
+            // Flush the close frame.
+            while socket.read_message().is_ok() {}
+        });
+
+        let dir = temp_dir("live");
+        let mut count = 0;
+        {
+            let mut web_socket = WebSockets::new(|_event: WebsocketEvent| {
+                count += 1;
+                Ok(())
+            });
+            web_socket.record_to(Recorder::new(&dir).unwrap());
+            let config = Config::default().set_ws_endpoint(endpoint);
+            web_socket.connect_with_config("", &config).unwrap();
+            // The server hanging up ends the loop.
+            assert!(web_socket.event_loop(&AtomicBool::new(true)).is_err());
+        }
+        server.join().unwrap();
+
+        assert_eq!(count, 3);
+        assert_eq!(file_names(&dir).len(), 1);
+        let recorded: Vec<String> = read_recording(&dir)
+            .unwrap()
+            .map(|frame| frame.unwrap().frame)
+            .collect();
+        assert_eq!(recorded, read_frames(FRAMES).unwrap());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
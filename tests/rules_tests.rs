@@ -0,0 +1,166 @@
+use binance::api::*;
+use binance::config::*;
+use binance::general::*;
+use binance::rules::*;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::amount;
+    use mockito::mock;
+
+    fn ethbtc_rules() -> SymbolRules {
+        let mock_exchange_info = mock("GET", "/api/v3/exchangeInfo")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body_from_file("tests/mocks/general/exchange_info.json")
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let general: General = Binance::new_with_config(None, None, &config).unwrap();
+
+        let rules = general.get_symbol_rules("ETHBTC").unwrap();
+        mock_exchange_info.assert();
+        rules
+    }
+
+    #[test]
+    fn get_symbol_rules() {
+        let rules = ethbtc_rules();
+
+        assert_eq!(rules.symbol, "ETHBTC");
+        assert_eq!(rules.price.as_ref().unwrap().tick_size, amount("0.000001"));
+        assert_eq!(rules.lot_size.as_ref().unwrap().step_size, amount("0.001"));
+        assert_eq!(rules.market_lot_size.as_ref().unwrap().max_qty, amount("2456.75855038"));
+        assert_eq!(rules.min_notional.as_ref().unwrap().min_notional, amount("0.0001"));
+        assert!(rules.min_notional.as_ref().unwrap().apply_to_market);
+        assert_eq!(rules.percent_price.as_ref().unwrap().multiplier_up, amount("5"));
+        assert_eq!(rules.max_num_orders, Some(200));
+    }
+
+    #[test]
+    fn round_price_and_qty() {
+        let rules = ethbtc_rules();
+
+        assert_eq!(rules.round_price(amount("0.0345674")), amount("0.034567"));
+        assert_eq!(rules.round_price(amount("0.0345675")), amount("0.034568"));
+        assert_eq!(rules.round_qty(amount("1.2349")), amount("1.234"));
+        assert_eq!(rules.round_qty(amount("0.3")), amount("0.3"));
+        // MARKET_LOT_SIZE has a zero step, so the quantity is left as is
+        assert_eq!(rules.round_market_qty(amount("1.2349")), amount("1.2349"));
+    }
+
+    #[test]
+    fn validate_limit() {
+        let rules = ethbtc_rules();
+
+        assert_eq!(rules.validate_limit(amount("0.034567"), amount("1.5")), Ok(()));
+        assert_eq!(
+            rules.validate_limit(amount("0.0000001"), amount("1.5")),
+            Err(RuleViolation::PriceTooLow {
+                price: amount("0.0000001"),
+                min_price: amount("0.000001"),
+            })
+        );
+        assert_eq!(
+            rules.validate_limit(amount("0.0345675"), amount("1.5")),
+            Err(RuleViolation::PriceNotOnTick {
+                price: amount("0.0345675"),
+                tick_size: amount("0.000001"),
+            })
+        );
+        assert_eq!(
+            rules.validate_limit(amount("0.034567"), amount("1.0005")),
+            Err(RuleViolation::QuantityNotOnStep {
+                qty: amount("1.0005"),
+                step_size: amount("0.001"),
+            })
+        );
+        assert_eq!(
+            rules.validate_limit(amount("0.034567"), amount("200000")),
+            Err(RuleViolation::QuantityTooHigh {
+                qty: amount("200000"),
+                max_qty: amount("100000"),
+            })
+        );
+
+        let violation = rules.validate_limit(amount("0.01"), amount("0.001")).unwrap_err();
+        match violation {
+            RuleViolation::NotionalTooLow { min_notional, .. } => {
+                assert_eq!(min_notional, amount("0.0001"))
+            }
+            _ => panic!("unexpected violation: {:?}", violation),
+        }
+        assert!(violation.to_string().contains("is below the minimum notional"));
+    }
+
+    #[test]
+    fn validate_market() {
+        let rules = ethbtc_rules();
+
+        assert_eq!(rules.validate_market(amount("2"), None), Ok(()));
+        assert_eq!(
+            rules.validate_market(amount("3000"), None),
+            Err(RuleViolation::QuantityTooHigh {
+                qty: amount("3000"),
+                max_qty: amount("2456.75855038"),
+            })
+        );
+        assert!(rules.validate_market(amount("0.001"), Some(amount("0.01"))).is_err());
+    }
+
+    #[test]
+    fn validate_percent_price_and_open_orders() {
+        let rules = ethbtc_rules();
+
+        assert_eq!(rules.validate_percent_price(amount("0.05"), amount("0.03")), Ok(()));
+        assert!(rules.validate_percent_price(amount("0.2"), amount("0.03")).is_err());
+        assert_eq!(rules.validate_open_orders(199), Ok(()));
+        assert_eq!(
+            rules.validate_open_orders(200),
+            Err(RuleViolation::TooManyOrders { max_num_orders: 200 })
+        );
+    }
+
+    #[test]
+    fn steps_counted_from_the_minimum() {
+        let rules = SymbolRules {
+            symbol: "XYZUSDT".into(),
+            price: Some(PriceRule {
+                min_price: amount("0.15"),
+                max_price: amount("1000"),
+                tick_size: amount("0.1"),
+            }),
+            percent_price: None,
+            lot_size: Some(QuantityRule {
+                min_qty: amount("0.015"),
+                max_qty: amount("100"),
+                step_size: amount("0.01"),
+            }),
+            market_lot_size: None,
+            min_notional: None,
+            max_num_orders: None,
+        };
+
+        assert_eq!(rules.round_price(amount("0.32")), amount("0.35"));
+        assert_eq!(rules.round_price(amount("0.29")), amount("0.25"));
+        assert_eq!(rules.round_qty(amount("0.039")), amount("0.035"));
+        assert_eq!(rules.round_qty(amount("0.035")), amount("0.035"));
+        assert_eq!(rules.validate_limit(amount("0.35"), amount("0.035")), Ok(()));
+        assert_eq!(
+            rules.validate_limit(amount("0.3"), amount("0.035")),
+            Err(RuleViolation::PriceNotOnTick {
+                price: amount("0.3"),
+                tick_size: amount("0.1"),
+            })
+        );
+        assert_eq!(
+            rules.validate_limit(amount("0.35"), amount("0.03")),
+            Err(RuleViolation::QuantityNotOnStep {
+                qty: amount("0.03"),
+                step_size: amount("0.01"),
+            })
+        );
+    }
+}
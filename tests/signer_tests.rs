@@ -0,0 +1,75 @@
+use binance::api::*;
+use binance::config::*;
+use binance::account::*;
+use binance::signer::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+
+    const PAYLOAD: &str = "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&recvWindow=5000&timestamp=1499827319559";
+
+    #[test]
+    fn hmac_signature() {
+        // Example from the Binance API documentation
+        let signer =
+            HmacSigner::new("NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j");
+        let signature = signer.sign(PAYLOAD).unwrap();
+
+        assert_eq!(signature, "c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71");
+    }
+
+    #[test]
+    fn ed25519_signature() {
+        let pem = std::fs::read_to_string("tests/keys/ed25519.pem").unwrap();
+        let signer = Ed25519Signer::from_pkcs8_pem(&pem).unwrap();
+        let signature = signer.sign(PAYLOAD).unwrap();
+
+        assert_eq!(
+            signature,
+            "kyCwymZu+UAw6hR8vbdntQwmRb+fF3IxtwTYeBnZn9zUbEKuuyfykFoBP1CFo5juuh+ZZGS9L07o25XnwF1JAQ=="
+        );
+    }
+
+    #[test]
+    fn rsa_signature() {
+        let pem = std::fs::read_to_string("tests/keys/rsa.pem").unwrap();
+        let signer = RsaSigner::from_pkcs8_pem(&pem).unwrap();
+        let signature = signer.sign(PAYLOAD).unwrap();
+
+        assert_eq!(
+            signature,
+            "IcUKl8qSXQALcT3vv3wI+05h5r7Ek5/aILR0dYhlmWWHbtqg1jmnbFYbIz3A3imVZMp55vMyi07Rctv5tPmOMiANW3r1dCWvRyeBnG9FZmgZEaT+bxMYAuWXLSvQm5YdVA+wd2LDmkJlDhOwEARkcfWUX59QKdR/0m46fIn96h10H8lfW26QziZAtNDfjx3vLirKp/8XK1IP4mIQz2ZOsovmJnaG1LyeTopx9ghdgYlvSMVQilfUpASk7NORaxC9ajN7J82vNFgbL/LlQmXk15WpeMDgS3r0cGJOPQNCgGQr+EF2wu24dEWQBP2td8jUtCEzu+2rw8PGvLnD3MaPow=="
+        );
+    }
+
+    #[test]
+    fn invalid_private_key() {
+        assert!(Ed25519Signer::from_pkcs8_pem("not a key").is_err());
+        assert!(RsaSigner::from_pkcs8_pem("not a key").is_err());
+
+        let ed25519_pem = std::fs::read_to_string("tests/keys/ed25519.pem").unwrap();
+        assert!(RsaSigner::from_pkcs8_pem(&ed25519_pem).is_err());
+    }
+
+    #[test]
+    fn signer_from_config() {
+        let mock_get_account = mock("GET", "/api/v3/account")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=[A-Za-z0-9%]+%3D%3D$".into()))
+            .with_body_from_file("tests/mocks/account/get_account.json")
+            .create();
+
+        let pem = std::fs::read_to_string("tests/keys/ed25519.pem").unwrap();
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234)
+            .set_signer(Ed25519Signer::from_pkcs8_pem(&pem).unwrap());
+        let account: Account = Binance::new_with_config(Some("api_key".into()), None, &config).unwrap();
+        let _ = env_logger::try_init();
+        account.get_account().unwrap();
+
+        mock_get_account.assert();
+    }
+}
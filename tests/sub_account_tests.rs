@@ -0,0 +1,229 @@
+use binance::api::*;
+use binance::config::*;
+use binance::sub_account::*;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::amount;
+    use mockito::{mock, Matcher};
+    use float_cmp::*;
+
+    #[test]
+    fn get_sub_account_list() {
+        let mock_sub_account_list = mock("GET", "/sapi/v1/sub-account/list")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("limit=200&page=1&recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/sub_account/get_sub_account_list.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let sub_account: SubAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let list = sub_account.get_sub_account_list(1, 200).unwrap();
+
+        mock_sub_account_list.assert();
+
+        assert_eq!(list.sub_accounts.len(), 2);
+        assert_eq!(list.sub_accounts[0].email, "testsub@gmail.com");
+        assert!(!list.sub_accounts[0].is_freeze);
+        assert_eq!(list.sub_accounts[0].create_time, 1544433328000);
+    }
+
+    #[test]
+    fn get_assets() {
+        let mock_assets = mock("GET", "/sapi/v3/sub-account/assets")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("email=testsub%40gmail.com&recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/sub_account/get_assets.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let sub_account: SubAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let assets = sub_account.get_assets("testsub@gmail.com").unwrap();
+
+        mock_assets.assert();
+
+        assert_eq!(assets.balances.len(), 2);
+        assert_eq!(assets.balances[0].asset, "ADA");
+        assert_eq!(assets.balances[0].free, amount("10000.0"));
+        assert_eq!(assets.balances[0].locked, amount("0.0"));
+    }
+
+    #[test]
+    fn get_futures_account() {
+        let mock_futures_account = mock("GET", "/sapi/v1/sub-account/futures/account")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("email=abc%40test.com&recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/sub_account/get_futures_account.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let sub_account: SubAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let account = sub_account.get_futures_account("abc@test.com").unwrap();
+
+        mock_futures_account.assert();
+
+        assert_eq!(account.email, "abc@test.com");
+        assert_eq!(account.fee_tier, 2);
+        assert_eq!(account.assets.len(), 1);
+        assert_eq!(account.assets[0].asset, "USDT");
+        assert_eq!(account.assets[0].wallet_balance, amount("0.88308"));
+        assert_eq!(account.total_wallet_balance, amount("0.88308"));
+    }
+
+    #[test]
+    fn get_futures_positions() {
+        let mock_positions = mock("GET", "/sapi/v1/sub-account/futures/positionRisk")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("email=abc%40test.com&recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/sub_account/get_futures_positions.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let sub_account: SubAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let positions = sub_account.get_futures_positions("abc@test.com").unwrap();
+
+        mock_positions.assert();
+
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].symbol, "BTCUSDT");
+        assert_eq!(positions[0].entry_price, amount("9975.12"));
+        assert!(approx_eq!(f64, positions[0].leverage, 50.0, ulps = 2));
+        assert_eq!(positions[0].position_amount, amount("0.01"));
+        assert_eq!(positions[0].unrealized_profit, amount("-0.01612295"));
+    }
+
+    #[test]
+    fn transfer_to_sub_account() {
+        let mock_transfer = mock("POST", "/sapi/v1/sub-account/universalTransfer")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("amount=0.5&asset=BTC&fromAccountType=SPOT&recvWindow=1234&timestamp=\\d+&toAccountType=SPOT&toEmail=alice%40test.com&signature=.*".into()))
+            .with_body_from_file("tests/mocks/sub_account/universal_transfer.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let sub_account: SubAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let transfer = sub_account.transfer_to_sub_account("alice@test.com", "BTC", amount("0.5")).unwrap();
+
+        mock_transfer.assert();
+
+        assert_eq!(transfer.tran_id, 11945860693);
+        assert_eq!(transfer.client_tran_id.unwrap(), "test");
+    }
+
+    #[test]
+    fn transfer_from_sub_account() {
+        let mock_transfer = mock("POST", "/sapi/v1/sub-account/universalTransfer")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("amount=0.5&asset=BTC&fromAccountType=SPOT&fromEmail=alice%40test.com&recvWindow=1234&timestamp=\\d+&toAccountType=SPOT&signature=.*".into()))
+            .with_body_from_file("tests/mocks/sub_account/universal_transfer.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let sub_account: SubAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let transfer = sub_account.transfer_from_sub_account("alice@test.com", "BTC", amount("0.5")).unwrap();
+
+        mock_transfer.assert();
+
+        assert_eq!(transfer.tran_id, 11945860693);
+    }
+
+    #[test]
+    fn transfer_between_sub_accounts() {
+        let mock_transfer = mock("POST", "/sapi/v1/sub-account/universalTransfer")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("amount=0.5&asset=BTC&fromAccountType=SPOT&fromEmail=alice%40test.com&recvWindow=1234&timestamp=\\d+&toAccountType=SPOT&toEmail=bob%40test.com&signature=.*".into()))
+            .with_body_from_file("tests/mocks/sub_account/universal_transfer.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let sub_account: SubAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let transfer = sub_account
+            .transfer_between_sub_accounts("alice@test.com", "bob@test.com", "BTC", amount("0.5"))
+            .unwrap();
+
+        mock_transfer.assert();
+
+        assert_eq!(transfer.tran_id, 11945860693);
+    }
+
+    #[test]
+    fn universal_transfer() {
+        let mock_transfer = mock("POST", "/sapi/v1/sub-account/universalTransfer")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("amount=100&asset=USDT&fromAccountType=SPOT&recvWindow=1234&timestamp=\\d+&toAccountType=USDT_FUTURE&toEmail=alice%40test.com&signature=.*".into()))
+            .with_body_from_file("tests/mocks/sub_account/universal_transfer.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let sub_account: SubAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let transfer = sub_account
+            .universal_transfer(
+                None,
+                Some("alice@test.com".into()),
+                AccountType::Spot,
+                AccountType::UsdtFuture,
+                "USDT",
+                amount("100"),
+            )
+            .unwrap();
+
+        mock_transfer.assert();
+
+        assert_eq!(transfer.tran_id, 11945860693);
+    }
+
+    #[test]
+    fn get_universal_transfer_history() {
+        let mock_history = mock("GET", "/sapi/v1/sub-account/universalTransfer")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("fromEmail=abctest%40gmail.com&recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/sub_account/get_universal_transfer_history.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let sub_account: SubAccount = Binance::new_with_config(None, None, &config).unwrap();
+        let _ = env_logger::try_init();
+        let history = sub_account
+            .get_universal_transfer_history(Some("abctest@gmail.com".into()), None)
+            .unwrap();
+
+        mock_history.assert();
+
+        assert_eq!(history.total_count, 1);
+        let transfer = &history.result[0];
+        assert_eq!(transfer.tran_id, 92275823339);
+        assert_eq!(transfer.to_email, "testuser@gmail.com");
+        assert_eq!(transfer.amount, amount("0.01"));
+        assert_eq!(transfer.from_account_type, "USDT_FUTURE");
+        assert_eq!(transfer.status, "SUCCESS");
+    }
+}
@@ -0,0 +1,249 @@
+#![cfg(feature = "testkit")]
+
+use binance::account::*;
+use binance::api::*;
+use binance::errors::*;
+use binance::futures::account::FuturesAccount;
+use binance::market::*;
+use binance::model::{ExecutionType, OrderStatus};
+use binance::testkit::*;
+use binance::userstream::*;
+use binance::websockets::*;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::amount;
+    use float_cmp::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    fn exchange() -> MockExchange {
+        let exchange = MockExchange::start().unwrap();
+        exchange.add_symbol("BTCUSDT", "BTC", "USDT");
+        exchange
+    }
+
+    fn client<T: Binance>(exchange: &MockExchange) -> T {
+        Binance::new_with_config(Some("api_key".into()), Some("secret".into()), &exchange.config()).unwrap()
+    }
+
+    #[test]
+    fn limit_order_lifecycle() {
+        let exchange = exchange();
+        exchange.set_balance("USDT", 10_000.0);
+        let account: Account = client(&exchange);
+
+        let transaction = account.limit_buy("BTCUSDT", amount("0.5"), amount("19000.0")).unwrap();
+        assert_eq!(transaction.status, OrderStatus::New);
+        assert_eq!(exchange.balance("USDT"), (500.0, 9_500.0));
+        assert_eq!(account.get_open_orders("BTCUSDT").unwrap().len(), 1);
+
+        // Another trader sells into the bid, at the bid price.
+        exchange.add_liquidity(Venue::Spot, "BTCUSDT", OrderSide::Sell, 18_900.0, 0.2);
+        let order = account.order_status("BTCUSDT", transaction.order_id).unwrap();
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+        assert_eq!(order.executed_qty, amount("0.2"));
+        assert_eq!(order.cummulative_quote_qty, amount("3800.0"));
+        assert_eq!(exchange.balance("BTC"), (0.1998, 0.0));
+        assert_eq!(exchange.balance("USDT"), (500.0, 5_700.0));
+
+        let canceled = account.cancel_order("BTCUSDT", transaction.order_id).unwrap();
+        assert_eq!(canceled.order_id, Some(transaction.order_id));
+        assert_eq!(exchange.balance("USDT"), (6_200.0, 0.0));
+        assert_eq!(exchange.open_order_count(Venue::Spot), 0);
+        assert_eq!(
+            account.order_status("BTCUSDT", transaction.order_id).unwrap().status,
+            OrderStatus::Canceled
+        );
+
+        let trades = account.trade_history("BTCUSDT").unwrap();
+        assert_eq!(trades.len(), 1);
+        assert!(trades[0].is_buyer && trades[0].is_maker);
+        assert_eq!(trades[0].commission_asset, "BTC");
+    }
+
+    #[test]
+    fn market_order_walks_the_book() {
+        let exchange = exchange();
+        exchange.set_balance("USDT", 10_000.0);
+        exchange.add_liquidity(Venue::Spot, "BTCUSDT", OrderSide::Sell, 20_000.0, 0.1);
+        exchange.add_liquidity(Venue::Spot, "BTCUSDT", OrderSide::Sell, 20_100.0, 0.2);
+        let account: Account = client(&exchange);
+        let market: Market = client(&exchange);
+
+        let transaction = account.market_buy("BTCUSDT", amount("0.25")).unwrap();
+        assert_eq!(transaction.status, OrderStatus::Filled);
+        assert_eq!(transaction.cummulative_quote_qty, amount("5015.0"));
+        let fills = transaction.fills.unwrap();
+        assert_eq!(fills.len(), 2);
+        assert_eq!(fills[1].price, amount("20100.0"));
+        assert_eq!(exchange.balance("USDT"), (4_985.0, 0.0));
+
+        let depth = market.get_depth("BTCUSDT").unwrap();
+        assert!(depth.bids.is_empty());
+        assert_eq!(depth.asks.len(), 1);
+        assert_eq!(depth.asks[0].qty, amount("0.05"));
+
+        match account.limit_buy("BTCUSDT", 1, amount("100000.0")).unwrap_err() {
+            BinanceError::Api { code, .. } => assert_eq!(code, -2010),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        let exchange = exchange();
+        let account: Account = client(&exchange);
+
+        match account.limit_sell("ETHUSDT", 1, amount("1.0")).unwrap_err() {
+            BinanceError::Api { code, .. } => assert_eq!(code, -1121),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        let anonymous: Account = Binance::new_with_config(None, None, &exchange.config()).unwrap();
+        match anonymous.get_account().unwrap_err() {
+            BinanceError::Api { code, .. } => assert_eq!(code, -2014),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn user_data_stream() {
+        let exchange = exchange();
+        exchange.set_balance("USDT", 1_000.0);
+        exchange.add_liquidity(Venue::Spot, "BTCUSDT", OrderSide::Sell, 100.0, 1.0);
+        let account: Account = client(&exchange);
+        let user_stream: UserStream = client(&exchange);
+        let listen_key = user_stream.start().unwrap().listen_key;
+
+        let running = AtomicBool::new(true);
+        let mut reports = Vec::new();
+        let mut balances = Vec::new();
+        {
+            let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+                match event {
+                    WebsocketEvent::OrderTrade(report) => {
+                        if report.order_status == OrderStatus::Filled {
+                            running.store(false, Ordering::Relaxed);
+                        }
+                        reports.push(report);
+                    }
+                    WebsocketEvent::AccountUpdate(update) => balances.push(update.balance),
+                    _ => (),
+                }
+                Ok(())
+            });
+            web_socket.connect_with_config(&listen_key, &exchange.config()).unwrap();
+            account.market_buy("BTCUSDT", amount("0.5")).unwrap();
+            web_socket.event_loop(&running).unwrap();
+        }
+
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].execution_type, ExecutionType::New);
+        assert_eq!(reports[1].execution_type, ExecutionType::Trade);
+        assert_eq!(reports[1].qty_last_filled_trade, amount("0.5"));
+        assert_eq!(reports[1].price_last_filled_trade, amount("100.0"));
+        let usdt = balances.last().unwrap().iter().find(|b| b.asset == "USDT").unwrap();
+        assert_eq!(usdt.free, amount("950.0"));
+
+        user_stream.keep_alive(&listen_key).unwrap();
+        user_stream.close(&listen_key).unwrap();
+        assert!(user_stream.keep_alive(&listen_key).is_err());
+    }
+
+    #[test]
+    fn futures_position_lifecycle() {
+        let exchange = exchange();
+        exchange.set_futures_balance("USDT", 1_000.0);
+        exchange.add_liquidity(Venue::Futures, "BTCUSDT", OrderSide::Sell, 100.0, 1.0);
+        let account: FuturesAccount = client(&exchange);
+
+        let open = account.market_buy("BTCUSDT", amount("0.5"), TimeInForce::GTC).unwrap();
+        assert_eq!(open.status, OrderStatus::Filled);
+        assert_eq!(open.avg_price, amount("100.0"));
+        assert_eq!(exchange.position("BTCUSDT"), (0.5, 100.0));
+
+        exchange.add_liquidity(Venue::Futures, "BTCUSDT", OrderSide::Buy, 110.0, 1.0);
+        account.market_sell("BTCUSDT", amount("0.5"), TimeInForce::GTC).unwrap();
+        assert_eq!(exchange.position("BTCUSDT"), (0.0, 0.0));
+        // 5 realized, less 0.1% of 50 and of 55 in commissions.
+        assert!(approx_eq!(f64, exchange.futures_balance("USDT"), 1_004.895, ulps = 2));
+
+        let resting = account.limit_buy("BTCUSDT", 1, amount("90.0"), TimeInForce::GTC).unwrap();
+        assert_eq!(resting.status, OrderStatus::New);
+        account.cancel_order("BTCUSDT", resting.order_id).unwrap();
+        account.limit_buy("BTCUSDT", 1, amount("90.0"), TimeInForce::GTC).unwrap();
+        account.cancel_all_open_orders("BTCUSDT").unwrap();
+        assert_eq!(exchange.open_order_count(Venue::Futures), 0);
+
+        let leverage = account.change_initial_leverage("BTCUSDT", 10).unwrap();
+        assert_eq!(leverage.leverage, 10);
+    }
+
+    #[test]
+    fn futures_user_data_stream() {
+        let exchange = exchange();
+        exchange.add_liquidity(Venue::Futures, "BTCUSDT", OrderSide::Buy, 100.0, 1.0);
+        let account: FuturesAccount = client(&exchange);
+        let user_stream: UserStream = client(&exchange);
+        let listen_key = user_stream.start_futures().unwrap().listen_key;
+
+        let running = AtomicBool::new(true);
+        let mut position = None;
+        {
+            let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+                if let WebsocketEvent::FuturesAccountUpdate(update) = event {
+                    position = update.data.position.first().map(|p| p.position_amount);
+                    running.store(false, Ordering::Relaxed);
+                }
+                Ok(())
+            });
+            web_socket.connect_with_config(&listen_key, &exchange.config()).unwrap();
+            account.market_sell("BTCUSDT", amount("0.3"), TimeInForce::GTC).unwrap();
+            web_socket.event_loop(&running).unwrap();
+        }
+
+        assert_eq!(position.unwrap(), amount("-0.3"));
+    }
+
+    #[test]
+    fn futures_user_data_stream_manager() {
+        let exchange = exchange();
+        exchange.add_liquidity(Venue::Futures, "BTCUSDT", OrderSide::Buy, 100.0, 1.0);
+        let account: FuturesAccount = client(&exchange);
+        let mut manager =
+            UserDataStreamManager::new(Some("api_key".into()), UserStreamType::Futures, &exchange.config()).unwrap();
+
+        let trader = thread::spawn(move || {
+            // Once the stream is connected.
+            thread::sleep(Duration::from_millis(200));
+            account.market_sell("BTCUSDT", amount("0.3"), TimeInForce::GTC).unwrap();
+        });
+        let running = AtomicBool::new(true);
+        let mut position = None;
+        manager
+            .run(
+                |event: WebsocketEvent| {
+                    if let WebsocketEvent::FuturesAccountUpdate(update) = event {
+                        position = update.data.position.first().map(|p| p.position_amount);
+                        running.store(false, Ordering::Relaxed);
+                    }
+                    Ok(())
+                },
+                &running,
+            )
+            .unwrap();
+        trader.join().unwrap();
+        assert_eq!(position.unwrap(), amount("-0.3"));
+
+        // Closed on drop.
+        let listen_key = manager.listen_key().unwrap();
+        drop(manager);
+        let user_stream: UserStream = client(&exchange);
+        assert!(user_stream.keep_alive_futures(&listen_key).is_err());
+    }
+}
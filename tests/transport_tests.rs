@@ -0,0 +1,175 @@
+use binance::account::*;
+use binance::api::*;
+use binance::config::*;
+use binance::errors::*;
+use binance::market::*;
+use binance::transport::*;
+use binance::websockets::*;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::amount;
+    use mockito::{mock, Matcher};
+    use std::fs;
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::sync::atomic::AtomicBool;
+    use std::thread;
+    use std::time::Duration;
+    use tungstenite::Message;
+
+    // Nothing listens there, replayed clients must not touch the network.
+    const UNREACHABLE: &str = "http://127.0.0.1:1";
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("binance-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn record_then_replay() {
+        let mock_get_price = mock("GET", "/api/v3/ticker/price")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("symbol=LTCBTC".into()))
+            .with_body_from_file("tests/mocks/market/get_price.json")
+            .create();
+        let mock_get_account = mock("GET", "/api/v3/account")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/get_account.json")
+            .create();
+
+        let path = temp_path("record_then_replay.jsonl");
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let recorder = RecordingTransport::new(ReqwestTransport::from_config(&config).unwrap(), &path).unwrap();
+        let config = config.set_transport(recorder);
+
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
+        let account: Account = Binance::new_with_config(Some("api_key".into()), Some("secret".into()), &config).unwrap();
+        let price = market.get_price("LTCBTC").unwrap();
+        let balances = account.get_account().unwrap().balances;
+        mock_get_price.assert();
+        mock_get_account.assert();
+
+        let recording = fs::read_to_string(&path).unwrap();
+        assert_eq!(recording.lines().count(), 2);
+        assert!(!recording.contains("api_key"));
+        assert!(!recording.contains("signature"));
+        assert!(!recording.contains("timestamp"));
+        assert!(recording.contains(r#""query":"recvWindow=1234""#));
+
+        let config = Config::default()
+            .set_rest_api_endpoint(UNREACHABLE)
+            .set_recv_window(1234)
+            .set_transport(ReplayTransport::from_file(&path).unwrap());
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
+        let account: Account = Binance::new_with_config(Some("other_key".into()), Some("other_secret".into()), &config).unwrap();
+        assert_eq!(market.get_price("LTCBTC").unwrap().price, price.price);
+        assert_eq!(account.get_account().unwrap().balances.len(), balances.len());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn replay_in_order() {
+        let config = Config::default()
+            .set_rest_api_endpoint(UNREACHABLE)
+            .set_transport(ReplayTransport::from_file("tests/mocks/replay/market.jsonl").unwrap());
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
+
+        assert_eq!(market.get_price("LTCBTC").unwrap().price, amount("4.000002"));
+        assert_eq!(market.get_price("LTCBTC").unwrap().price, amount("4.000003"));
+        // The last matching response is served once they are used up.
+        assert_eq!(market.get_price("LTCBTC").unwrap().price, amount("4.000003"));
+        assert_eq!(market.client.rate_limit_usage().used_weight.get("1m"), Some(&4));
+
+        match market.get_price("ETHBTC").unwrap_err() {
+            BinanceError::RateLimited { retry_after } => {
+                assert_eq!(retry_after, Some(Duration::from_secs(7)));
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn replay_unknown_request() {
+        let config = Config::default()
+            .set_rest_api_endpoint(UNREACHABLE)
+            .set_transport(ReplayTransport::new(Vec::new()));
+        let market: Market = Binance::new_with_config(None, None, &config).unwrap();
+
+        let err = market.get_price("BNBBTC").unwrap_err();
+        assert!(err.to_string().contains("GET /api/v3/ticker/price?symbol=BNBBTC"));
+    }
+
+    #[test]
+    fn replay_frames() {
+        let mut events = Vec::new();
+        {
+            let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+                events.push(event);
+                Ok(())
+            });
+            web_socket.connect_replay("tests/mocks/websocket/ticker_frames.jsonl").unwrap();
+            web_socket.event_loop(&AtomicBool::new(true)).unwrap();
+        }
+
+        assert_eq!(events.len(), 3);
+        match &events[0] {
+            WebsocketEvent::DayTickerAll(tickers) => assert_eq!(tickers.len(), 3),
+            e => panic!("Unexpected event: {:?}", e),
+        }
+        match &events[1] {
+            WebsocketEvent::DayTicker(ticker) => assert_eq!(ticker.symbol, "BTCUSDT"),
+            e => panic!("Unexpected event: {:?}", e),
+        }
+        match &events[2] {
+            WebsocketEvent::Trade(trade) => assert_eq!(trade.trade_id, 1215491004),
+            e => panic!("Unexpected event: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn record_frames() {
+        let frames = fs::read_to_string("tests/mocks/websocket/ticker_frames.jsonl").unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("ws://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            for frame in frames.lines() {
+                socket.write_message(Message::Text(frame.to_string())).unwrap();
+            }
+            socket.close(None).unwrap();
+            // Flush the close frame.
+            while socket.read_message().is_ok() {}
+        });
+
+        let path = temp_path("record_frames.jsonl");
+        let mut count = 0;
+        {
+            let mut web_socket = WebSockets::new(|_event: WebsocketEvent| {
+                count += 1;
+                Ok(())
+            });
+            web_socket.record_frames(&path).unwrap();
+            let config = Config::default().set_ws_endpoint(endpoint);
+            web_socket.connect_with_config("", &config).unwrap();
+            // The server hanging up ends the loop.
+            assert!(web_socket.event_loop(&AtomicBool::new(true)).is_err());
+        }
+        server.join().unwrap();
+
+        assert_eq!(count, 3);
+        assert_eq!(
+            read_frames(&path).unwrap(),
+            read_frames("tests/mocks/websocket/ticker_frames.jsonl").unwrap()
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,169 @@
+use binance::futures::model::AccountUpdateReason;
+use binance::model::*;
+use binance::websockets::*;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::amount;
+
+    fn event(fixture: &str) -> WebsocketEvent {
+        let msg = std::fs::read_to_string(format!("tests/mocks/websocket/user_data/{}", fixture)).unwrap();
+        let mut events = Vec::new();
+        {
+            let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+                events.push(event);
+                Ok(())
+            });
+            web_socket.test_handle_msg(&msg).unwrap();
+        }
+        assert_eq!(events.len(), 1, "{} not handled", fixture);
+        events.pop().unwrap()
+    }
+
+    #[test]
+    fn outbound_account_position() {
+        match event("outbound_account_position.json") {
+            WebsocketEvent::AccountPosition(event) => {
+                assert_eq!(event.last_update_time, 1564034571073);
+                assert_eq!(event.balances.len(), 2);
+                assert_eq!(event.balances[1].asset, "BTC");
+                assert_eq!(event.balances[1].free, amount("0.5"));
+                assert_eq!(event.balances[1].locked, amount("0.25"));
+            }
+            e => panic!("Unexpected event: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn balance_update() {
+        match event("balance_update.json") {
+            WebsocketEvent::BalanceUpdate(event) => {
+                assert_eq!(event.asset, "BTC");
+                assert_eq!(event.balance_delta, amount("100"));
+                assert_eq!(event.clear_time, 1573200697068);
+            }
+            e => panic!("Unexpected event: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn list_status() {
+        match event("list_status.json") {
+            WebsocketEvent::ListStatus(event) => {
+                assert_eq!(event.symbol, "ETHBTC");
+                assert_eq!(event.order_list_id, 2);
+                assert_eq!(event.contingency_type, "OCO");
+                assert_eq!(event.list_status_type, "EXEC_STARTED");
+                assert_eq!(event.list_order_status, "EXECUTING");
+                assert_eq!(event.list_client_order_id, "F4QN4G8DlFATFlIUQ0cjdD");
+                let ids: Vec<u64> = event.orders.iter().map(|order| order.order_id).collect();
+                assert_eq!(ids, vec![17, 18]);
+            }
+            e => panic!("Unexpected event: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn execution_report() {
+        match event("execution_report.json") {
+            WebsocketEvent::OrderTrade(event) => {
+                assert_eq!(event.order_id, 4293153);
+                assert_eq!(event.execution_type, ExecutionType::New);
+                assert_eq!(event.price, amount("0.1026441"));
+            }
+            e => panic!("Unexpected event: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn listen_key_expired() {
+        match event("listen_key_expired.json") {
+            WebsocketEvent::ListenKeyExpired(event) => {
+                assert_eq!(event.event_time, 1576653824250);
+                assert!(event.listen_key.is_none());
+            }
+            e => panic!("Unexpected event: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn futures_margin_call() {
+        match event("margin_call.json") {
+            WebsocketEvent::FuturesMarginCall(event) => {
+                assert_eq!(event.cross_wallet_balance, Some(amount("3.16812045")));
+                let position = &event.positions[0];
+                assert_eq!(position.symbol, "ETHUSDT");
+                assert_eq!(position.position_side, "LONG");
+                assert_eq!(position.position_amount, amount("1.327"));
+                assert_eq!(position.mark_price, amount("187.17127"));
+                assert_eq!(position.maintenance_margin, amount("1.614445"));
+            }
+            e => panic!("Unexpected event: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn futures_account_config_update() {
+        match event("account_config_update_leverage.json") {
+            WebsocketEvent::FuturesAccountConfigUpdate(event) => {
+                let leverage = event.leverage.unwrap();
+                assert_eq!(leverage.symbol, "BTCUSDT");
+                assert_eq!(leverage.leverage, 25);
+                assert!(event.multi_assets.is_none());
+            }
+            e => panic!("Unexpected event: {:?}", e),
+        }
+        match event("account_config_update_multi_assets.json") {
+            WebsocketEvent::FuturesAccountConfigUpdate(event) => {
+                assert!(event.leverage.is_none());
+                assert!(event.multi_assets.unwrap().multi_assets_mode);
+            }
+            e => panic!("Unexpected event: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn futures_account_update() {
+        match event("account_update.json") {
+            WebsocketEvent::FuturesAccountUpdate(event) => {
+                assert_eq!(event.data.reason, AccountUpdateReason::Order);
+                assert_eq!(event.data.balance_updates[0].balance, amount("122624.12345678"));
+                assert_eq!(event.data.position[0].position_amount, amount("20"));
+            }
+            e => panic!("Unexpected event: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn futures_order_trade_update() {
+        match event("order_trade_update.json") {
+            WebsocketEvent::FuturesOrderUpdate(event) => {
+                assert_eq!(event.data.order_id, 8886774);
+                assert_eq!(event.data.order_type, OrderType::TrailingStopMarket);
+                // Not sent without a commission.
+                assert!(event.data.commission.is_none());
+                assert_eq!(event.data.activation_price, Some(amount("7476.89")));
+            }
+            e => panic!("Unexpected event: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn combined_stream() {
+        let msg = std::fs::read_to_string("tests/mocks/websocket/user_data/balance_update.json").unwrap();
+        let mut handled = false;
+        {
+            let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+                handled = matches!(event, WebsocketEvent::BalanceUpdate(_));
+                Ok(())
+            });
+            web_socket
+                .test_handle_msg(&format!(r#"{{"stream":"listenKey","data":{}}}"#, msg))
+                .unwrap();
+        }
+        assert!(handled);
+    }
+}
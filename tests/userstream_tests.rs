@@ -0,0 +1,131 @@
+use binance::config::*;
+use binance::userstream::*;
+use binance::websockets::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+    use std::time::Duration;
+    use tungstenite::handshake::server::{Request, Response};
+    use tungstenite::Message;
+
+    const TRADE: &str = r#"{"e":"trade","E":1640995201102,"s":"BTCUSDT","t":1215491004,"p":"41010.50000000","q":"0.01200000","b":8966021201,"a":8966021199,"T":1640995201101,"m":false,"M":true}"#;
+    const EXPIRED: &str = r#"{"e":"listenKeyExpired","E":1576653824250,"listenKey":"key1"}"#;
+
+    #[test]
+    fn rekey_on_expiry() {
+        let mock_start = mock("POST", "/api/v3/userDataStream")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body(r#"{"listenKey":"key1"}"#)
+            .expect(1)
+            .create();
+        let mock_restart = mock("POST", "/api/v3/userDataStream")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body(r#"{"listenKey":"key2"}"#)
+            .expect(1)
+            .create();
+        let mock_keep_alive = mock("PUT", "/api/v3/userDataStream")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_body(Matcher::Exact("listenKey=key1".into()))
+            .with_body("{}")
+            .expect_at_least(1)
+            .create();
+        let mock_close = mock("DELETE", "/api/v3/userDataStream")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_body(Matcher::Exact("listenKey=key2".into()))
+            .with_body("{}")
+            .expect(1)
+            .create();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("ws://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut paths = Vec::new();
+            for frames in [vec![TRADE, EXPIRED], vec![TRADE]] {
+                let (stream, _) = listener.accept().unwrap();
+                let mut path = String::new();
+                #[allow(clippy::result_large_err)]
+                let callback = |request: &Request, response: Response| {
+                    path = request.uri().path().to_string();
+                    Ok(response)
+                };
+                let mut socket = tungstenite::accept_hdr(stream, callback).unwrap();
+                paths.push(path);
+                // Leaves time for a keepalive.
+                thread::sleep(Duration::from_millis(100));
+                for frame in frames {
+                    socket.write_message(Message::Text(frame.to_string())).unwrap();
+                }
+                // Until the client hangs up.
+                while socket.read_message().is_ok() {}
+            }
+            paths
+        });
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_ws_endpoint(endpoint);
+        let mut manager = UserDataStreamManager::new(Some("api_key".into()), UserStreamType::Spot, &config)
+            .unwrap()
+            .set_keep_alive_interval(Duration::from_millis(20));
+
+        let running = AtomicBool::new(true);
+        let mut events = Vec::new();
+        manager
+            .run(
+                |event: WebsocketEvent| {
+                    if let WebsocketEvent::ListenKeyExpired(ref expired) = event {
+                        assert_eq!(expired.listen_key.as_deref(), Some("key1"));
+                    }
+                    events.push(event);
+                    if events.len() == 3 {
+                        running.store(false, Ordering::Relaxed);
+                    }
+                    Ok(())
+                },
+                &running,
+            )
+            .unwrap();
+
+        assert_eq!(manager.listen_key().as_deref(), Some("key2"));
+        drop(manager);
+        assert_eq!(server.join().unwrap(), vec!["/key1", "/key2"]);
+
+        assert!(matches!(events[0], WebsocketEvent::Trade(_)));
+        assert!(matches!(events[1], WebsocketEvent::ListenKeyExpired(_)));
+        assert!(matches!(events[2], WebsocketEvent::Trade(_)));
+        mock_start.assert();
+        mock_restart.assert();
+        mock_keep_alive.assert();
+        mock_close.assert();
+    }
+
+    #[test]
+    fn margin_listen_key() {
+        let mock_start = mock("POST", "/sapi/v1/userDataStream")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body(r#"{"listenKey":"marginKey"}"#)
+            .create();
+        let mock_close = mock("DELETE", "/sapi/v1/userDataStream")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_body(Matcher::Exact("listenKey=marginKey".into()))
+            .with_body("{}")
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        {
+            let mut manager =
+                UserDataStreamManager::new(Some("api_key".into()), UserStreamType::Margin, &config).unwrap();
+            assert_eq!(manager.start().unwrap(), "marginKey");
+            // Already started.
+            assert_eq!(manager.start().unwrap(), "marginKey");
+        }
+
+        mock_start.assert();
+        mock_close.assert();
+    }
+}
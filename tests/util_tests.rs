@@ -3,25 +3,50 @@ use binance::util::*;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::BTreeMap;
     use std::time::{SystemTime, UNIX_EPOCH};
     use float_cmp::*;
 
     #[test]
     fn build_request_empty() {
-        let parameters: BTreeMap<String, String> = BTreeMap::new();
+        let parameters = RequestParams::new();
         let result = build_request(parameters);
         assert!(result.is_empty());
     }
 
     #[test]
     fn build_request_not_empty() {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("recvWindow".into(), "1234".to_string());
+        let mut parameters = RequestParams::new();
+        parameters.insert("recvWindow", "1234");
         let result = build_request(parameters);
         assert_eq!(result, format!("recvWindow={}", 1234));
     }
 
+    #[test]
+    fn build_request_sorted() {
+        let mut parameters = RequestParams::new();
+        parameters.insert("symbol", "BTCUSDT").insert("side", "BUY").insert("quantity", "1");
+        let result = build_request(parameters);
+        assert_eq!(result, "quantity=1&side=BUY&symbol=BTCUSDT");
+    }
+
+    #[test]
+    fn build_request_encoded() {
+        let mut parameters = RequestParams::new();
+        parameters.insert("email", "sub+1@test.com");
+        parameters.insert("newClientOrderId", "a b&c=d");
+        let result = build_request(parameters);
+        assert_eq!(result, "email=sub%2B1%40test.com&newClientOrderId=a+b%26c%3Dd");
+    }
+
+    #[test]
+    fn build_request_json() {
+        let mut parameters = RequestParams::new();
+        parameters.insert_json("orderIdList", &[1234u64, 5678]).unwrap();
+        assert_eq!(parameters.get("orderIdList"), Some("[1234,5678]"));
+        let result = build_request(parameters);
+        assert_eq!(result, "orderIdList=%5B1234%2C5678%5D");
+    }
+
     #[test]
     fn build_signed_request() {
         let now = SystemTime::now();
@@ -30,7 +55,7 @@ mod tests {
         let since_epoch = now.duration_since(UNIX_EPOCH).unwrap();
         let timestamp = since_epoch.as_secs() * 1000 + u64::from(since_epoch.subsec_nanos()) / 1_000_000;
 
-        let parameters: BTreeMap<String, String> = BTreeMap::new();
+        let parameters = RequestParams::new();
         let result = binance::util::build_signed_request_custom(parameters, recv_window, now).unwrap();
 
         assert_eq!(result, format!("recvWindow={}&timestamp={}", recv_window, timestamp));
@@ -40,14 +65,29 @@ mod tests {
     fn to_i64() {
         let value_max = serde_json::json!(i64::MAX);
         let value_min = serde_json::json!(i64::MIN);
-        assert_eq!(binance::util::to_i64(&value_max), i64::MAX);
-        assert_eq!(binance::util::to_i64(&value_min), i64::MIN);
+        assert_eq!(binance::util::to_i64(&value_max).unwrap(), i64::MAX);
+        assert_eq!(binance::util::to_i64(&value_min).unwrap(), i64::MIN);
+    }
+
+    #[test]
+    fn to_i64_invalid() {
+        assert!(binance::util::to_i64(&serde_json::json!("1")).is_err());
+        assert!(binance::util::to_i64(&serde_json::json!(1.5)).is_err());
+        assert!(binance::util::to_i64(&serde_json::Value::Null).is_err());
     }
 
     #[test]
     fn to_f64() {
         let value = serde_json::json!("123.3");
-        assert!(approx_eq!(f64, binance::util::to_f64(&value), 123.3, ulps = 2));
+        assert!(approx_eq!(f64, binance::util::to_f64(&value).unwrap(), 123.3, ulps = 2));
+    }
+
+    #[test]
+    fn to_f64_invalid() {
+        assert!(binance::util::to_f64(&serde_json::json!("abc")).is_err());
+        assert!(binance::util::to_f64(&serde_json::json!(123.3)).is_err());
+        assert!(binance::util::to_amount(&serde_json::json!("")).is_err());
+        assert!(binance::util::to_amount(&serde_json::json!([])).is_err());
     }
 
 }
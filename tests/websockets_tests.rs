@@ -0,0 +1,106 @@
+use binance::config::*;
+use binance::errors::*;
+use binance::transport::*;
+use binance::websockets::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+    use std::time::{Duration, Instant};
+    use tungstenite::Message;
+
+    // Serves `frames` to one client, then hangs up or waits for the client to close.
+    fn serve(frames: Vec<String>, hang_up: bool) -> (Config, JoinHandle<Option<Message>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = Config::default().set_ws_endpoint(format!("ws://{}", listener.local_addr().unwrap()));
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            for frame in frames {
+                socket.write_message(Message::Text(frame)).unwrap();
+            }
+            if hang_up {
+                socket.close(None).unwrap();
+                while socket.read_message().is_ok() {}
+                return None;
+            }
+            // What the client sends before going away.
+            socket.read_message().ok()
+        });
+        (config, server)
+    }
+
+    fn ticker_frames() -> Vec<String> {
+        read_frames("tests/mocks/websocket/ticker_frames.jsonl").unwrap()
+    }
+
+    #[test]
+    fn shutdown_without_waiting_for_a_message() {
+        let (config, server) = serve(ticker_frames(), false);
+        let mut events = EventStream::connect_with_config("", &config).unwrap();
+
+        for _ in 0..3 {
+            assert!(events.next().unwrap().is_ok());
+        }
+        // Nothing else comes from the server.
+        assert!(events.receiver().recv_timeout(Duration::from_millis(100)).is_err());
+
+        let started = Instant::now();
+        events.shutdown();
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(matches!(server.join().unwrap(), Some(Message::Close(_))));
+        assert!(events.next().is_none());
+    }
+
+    #[test]
+    fn shutdown_from_another_thread() {
+        let (config, server) = serve(Vec::new(), false);
+        let events = EventStream::connect_with_config("", &config).unwrap();
+        let shutdown = events.shutdown_handle();
+
+        let consumer = thread::spawn(move || events.count());
+        thread::sleep(Duration::from_millis(50));
+        shutdown.shutdown();
+
+        assert_eq!(consumer.join().unwrap(), 0);
+        assert!(matches!(server.join().unwrap(), Some(Message::Close(_))));
+    }
+
+    #[test]
+    fn server_hang_up_ends_the_stream() {
+        let mut frames = ticker_frames();
+        frames.insert(1, "{\"e\":".into());
+        let (config, server) = serve(frames, true);
+        let events: Vec<_> = EventStream::connect_with_config("", &config).unwrap().collect();
+        server.join().unwrap();
+
+        // The message that can't be decoded doesn't end the stream, the server closing does.
+        assert_eq!(events.len(), 5);
+        assert!(events[0].is_ok());
+        assert!(events[1].is_err());
+        assert!(events[2].is_ok());
+        assert!(events[3].is_ok());
+        assert!(events[4].is_err());
+    }
+
+    #[test]
+    fn unknown_event_comes_as_an_error() {
+        let mut frames = ticker_frames();
+        frames.insert(1, r#"{"e":"newEvent","E":1640995201102,"s":"BTCUSDT"}"#.into());
+        let (config, server) = serve(frames, false);
+        let mut events = EventStream::connect_with_config("", &config).unwrap();
+
+        assert!(events.next().unwrap().is_ok());
+        assert!(matches!(events.next().unwrap(), Err(BinanceError::Decode { .. })));
+        for _ in 0..2 {
+            assert!(events.next().unwrap().is_ok());
+        }
+
+        events.shutdown();
+        server.join().unwrap();
+    }
+}